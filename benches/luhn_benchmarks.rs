@@ -1,5 +1,5 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use luhn_tools::{generate, random, validate, GenerateOptions};
+use luhn_tools::{generate, random, random_with_rng, validate, GenerateOptions};
 
 fn benchmark_generate(c: &mut Criterion) {
     let mut group = c.benchmark_group("generate");
@@ -18,15 +18,10 @@ fn benchmark_generate(c: &mut Criterion) {
     });
 
     // Benchmark checksum only vs full number
+    let mut checksum_only = GenerateOptions::default();
+    checksum_only.checksum_only = true;
     group.bench_function("generate_checksum_only", |b| {
-        b.iter(|| {
-            generate(
-                black_box("1234567890"),
-                Some(GenerateOptions {
-                    checksum_only: true,
-                }),
-            )
-        })
+        b.iter(|| generate(black_box("1234567890"), Some(checksum_only)))
     });
 
     group.finish();
@@ -70,6 +65,12 @@ fn benchmark_random(c: &mut Criterion) {
 
     group.bench_function("random_long", |b| b.iter(|| random(black_box("20"))));
 
+    // Reusing one RNG handle across calls
+    let mut rng = rand::thread_rng();
+    group.bench_function("random_with_rng_long", |b| {
+        b.iter(|| random_with_rng(black_box(20), &mut rng))
+    });
+
     group.finish();
 }
 
@@ -0,0 +1,379 @@
+//! Derive macros for [`luhn_tools`](https://docs.rs/luhn_tools).
+//!
+//! Enable the `derive` feature of `luhn_tools` rather than depending on this crate
+//! directly; the macros are re-exported from there.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Index, LitInt, Member};
+
+/// The widest field `#[luhn(width = N)]` accepts.
+const MAX_WIDTH: usize = 1024;
+
+/// Turns a single-field `String` newtype into a validated Luhn number type.
+///
+/// The struct gets a `new(&str)` constructor plus `TryFrom<&str>`, `FromStr`,
+/// `Display` and `AsRef<str>`, all of which reject input that is not a valid Luhn
+/// number. The field holds the validated digits.
+///
+/// Options go in a `#[luhn(...)]` attribute:
+/// * `min_len = N`, `max_len = N` - Inclusive bounds on the number of digits
+/// * `separators` - Strip spaces and dashes before validating
+/// * `serde` - Also implement `Serialize` and a validating `Deserialize`
+///   (requires the `serde` feature of `luhn_tools`)
+///
+/// # Examples
+/// ```
+/// use luhn_tools::LuhnValidated;
+///
+/// #[derive(Debug, LuhnValidated)]
+/// #[luhn(min_len = 8, max_len = 12, separators)]
+/// struct MemberId(String);
+///
+/// let id: MemberId = "7992-7398-713".parse().unwrap();
+/// assert_eq!(id.to_string(), "79927398713");
+/// assert!(MemberId::try_from("79927398714").is_err());
+/// assert!(MemberId::try_from("18").is_err());
+/// ```
+#[proc_macro_derive(LuhnValidated, attributes(luhn))]
+pub fn derive_luhn_validated(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    luhn_validated(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Options parsed from `#[luhn(...)]`.
+#[derive(Default)]
+struct Options {
+    min_len: Option<usize>,
+    max_len: Option<usize>,
+    separators: bool,
+    serde: bool,
+}
+
+impl Options {
+    fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        let mut options = Options::default();
+        for attr in input
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("luhn"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("min_len") {
+                    options.min_len = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+                } else if meta.path.is_ident("max_len") {
+                    options.max_len = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+                } else if meta.path.is_ident("separators") {
+                    options.separators = true;
+                } else if meta.path.is_ident("serde") {
+                    options.serde = true;
+                } else {
+                    return Err(
+                        meta.error("expected `min_len`, `max_len`, `separators` or `serde`")
+                    );
+                }
+                Ok(())
+            })?;
+        }
+
+        if let (Some(min), Some(max)) = (options.min_len, options.max_len) {
+            if min > max {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "`min_len` must not be greater than `max_len`",
+                ));
+            }
+        }
+        Ok(options)
+    }
+}
+
+fn luhn_validated(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let is_newtype = match &input.data {
+        Data::Struct(data) => {
+            matches!(&data.fields, Fields::Unnamed(fields) if fields.unnamed.len() == 1)
+        }
+        _ => false,
+    };
+    if !is_newtype {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "LuhnValidated can only be derived for a struct with a single `String` field",
+        ));
+    }
+
+    let options = Options::parse(input)?;
+    let name = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let min_len = option_tokens(options.min_len);
+    let max_len = option_tokens(options.max_len);
+    let separators = options.separators;
+    let label = format!("invalid {}: ", name);
+
+    let serde = options.serde.then(|| {
+        quote! {
+            impl #impl_generics ::luhn_tools::__private::serde::Serialize for #name #ty_generics #where_clause {
+                fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+                where
+                    S: ::luhn_tools::__private::serde::Serializer,
+                {
+                    serializer.serialize_str(&self.0)
+                }
+            }
+
+            impl<'de> ::luhn_tools::__private::serde::Deserialize<'de> for #name #ty_generics #where_clause {
+                fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+                where
+                    D: ::luhn_tools::__private::serde::Deserializer<'de>,
+                {
+                    let value: ::std::string::String =
+                        ::luhn_tools::__private::serde::Deserialize::deserialize(deserializer)?;
+                    #name::new(&value).map_err(|e| {
+                        <D::Error as ::luhn_tools::__private::serde::de::Error>::custom(
+                            ::core::format_args!("{}{}", #label, e),
+                        )
+                    })
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Validates `value` as a Luhn number and wraps its digits.
+            #vis fn new(value: &str) -> ::core::result::Result<Self, ::luhn_tools::LuhnError> {
+                ::luhn_tools::__private::validate_newtype(value, #min_len, #max_len, #separators)
+                    .map(#name)
+            }
+
+            /// Returns the validated digits.
+            #vis fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl #impl_generics ::core::convert::TryFrom<&str> for #name #ty_generics #where_clause {
+            type Error = ::luhn_tools::LuhnError;
+
+            fn try_from(value: &str) -> ::core::result::Result<Self, Self::Error> {
+                #name::new(value)
+            }
+        }
+
+        impl #impl_generics ::core::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::luhn_tools::LuhnError;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                #name::new(s)
+            }
+        }
+
+        impl #impl_generics ::core::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl #impl_generics ::core::convert::AsRef<str> for #name #ty_generics #where_clause {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        #serde
+    })
+}
+
+/// Renders an optional length as `Some(n)` or `None`.
+fn option_tokens(value: Option<usize>) -> TokenStream2 {
+    match value {
+        Some(n) => quote!(::core::option::Option::Some(#n)),
+        None => quote!(::core::option::Option::None),
+    }
+}
+
+/// Adds a Luhn check digit to a struct whose numeric fields together form one
+/// number, such as a branch code, account number and sequence number.
+///
+/// Fields marked `#[luhn]` are concatenated in declaration order into the payload;
+/// `#[luhn(width = N)]` zero-pads a field to exactly `N` digits, with `N` from 1 to
+/// 1024. Each marked field must `Display` as plain digits. Marking a `u8` field
+/// `#[luhn(check)]` declares it as the stored check digit, which `is_luhn_valid`
+/// compares against. Unmarked fields are ignored.
+///
+/// The struct gets these methods, each failing with a `LuhnError` if a field is
+/// negative or does not fit its width:
+/// * `luhn_payload()` - The concatenated digits
+/// * `luhn_check_digit()` - The check digit for that payload
+/// * `to_luhn_string()` - The payload followed by its check digit
+/// * `is_luhn_valid()` - Whether the `check` field holds the right digit (only
+///   with a `check` field)
+///
+/// # Examples
+/// ```
+/// use luhn_tools::LuhnChecked;
+///
+/// #[derive(LuhnChecked)]
+/// struct Reference {
+///     #[luhn(width = 3)]
+///     branch: u16,
+///     #[luhn(width = 6)]
+///     account: u32,
+///     #[luhn(width = 2)]
+///     sequence: u8,
+///     #[luhn(check)]
+///     check: u8,
+/// }
+///
+/// let reference = Reference { branch: 12, account: 3456, sequence: 7, check: 1 };
+/// assert_eq!(reference.luhn_payload().unwrap(), "01200345607");
+/// assert_eq!(reference.luhn_check_digit().unwrap(), 1);
+/// assert_eq!(reference.to_luhn_string().unwrap(), "012003456071");
+/// assert!(reference.is_luhn_valid().unwrap());
+///
+/// let too_wide = Reference { branch: 1000, ..reference };
+/// assert!(too_wide.luhn_payload().is_err());
+/// ```
+#[proc_macro_derive(LuhnChecked, attributes(luhn))]
+pub fn derive_luhn_checked(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    luhn_checked(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// A field marked `#[luhn]` or `#[luhn(width = N)]`.
+struct PayloadField {
+    member: Member,
+    width: Option<usize>,
+}
+
+fn luhn_checked(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "LuhnChecked can only be derived for a struct",
+            ))
+        }
+    };
+
+    let mut payload = Vec::new();
+    let mut check: Option<Member> = None;
+    for (i, field) in fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(i)),
+        };
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("luhn"))
+        {
+            let mut width = None;
+            let mut is_check = false;
+            if !matches!(attr.meta, syn::Meta::Path(_)) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("width") {
+                        width = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+                    } else if meta.path.is_ident("check") {
+                        is_check = true;
+                    } else {
+                        return Err(meta.error("expected `width` or `check`"));
+                    }
+                    Ok(())
+                })?;
+            }
+
+            if is_check {
+                if width.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "the `check` field is a single digit and takes no `width`",
+                    ));
+                }
+                if check.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "only one field can be the `check` digit",
+                    ));
+                }
+                check = Some(member.clone());
+            } else {
+                if width == Some(0) {
+                    return Err(syn::Error::new_spanned(attr, "`width` must be at least 1"));
+                }
+                if width.map_or(false, |width| width > MAX_WIDTH) {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        format!("`width` must be at most {}", MAX_WIDTH),
+                    ));
+                }
+                payload.push(PayloadField {
+                    member: member.clone(),
+                    width,
+                });
+            }
+        }
+    }
+    if payload.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "LuhnChecked needs at least one field marked `#[luhn]`",
+        ));
+    }
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let pushes = payload.iter().map(|field| {
+        let member = &field.member;
+        let label = match member {
+            Member::Named(ident) => ident.to_string(),
+            Member::Unnamed(index) => index.index.to_string(),
+        };
+        let width = option_tokens(field.width);
+        quote! {
+            ::luhn_tools::__private::push_field(&mut payload, #label, &self.#member, #width)?;
+        }
+    });
+    let is_valid = check.map(|member| {
+        quote! {
+            /// Returns whether the check digit field matches the other fields.
+            #vis fn is_luhn_valid(&self) -> ::core::result::Result<bool, ::luhn_tools::LuhnError> {
+                ::core::result::Result::Ok(self.#member == self.luhn_check_digit()?)
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns the marked fields concatenated into one payload.
+            #vis fn luhn_payload(&self) -> ::core::result::Result<::std::string::String, ::luhn_tools::LuhnError> {
+                let mut payload = ::std::string::String::new();
+                #(#pushes)*
+                ::core::result::Result::Ok(payload)
+            }
+
+            /// Returns the check digit (0–9) for the payload.
+            #vis fn luhn_check_digit(&self) -> ::core::result::Result<u8, ::luhn_tools::LuhnError> {
+                ::luhn_tools::__private::payload_check_digit(&self.luhn_payload()?)
+            }
+
+            /// Returns the payload followed by its check digit.
+            #vis fn to_luhn_string(&self) -> ::core::result::Result<::std::string::String, ::luhn_tools::LuhnError> {
+                let mut number = self.luhn_payload()?;
+                let check = ::luhn_tools::__private::payload_check_digit(&number)?;
+                number.push(<char as ::core::convert::From<u8>>::from(b'0' + check));
+                ::core::result::Result::Ok(number)
+            }
+
+            #is_valid
+        }
+    })
+}
@@ -0,0 +1,216 @@
+//! Resolving numbers with ambiguous digits.
+//!
+//! OCR on scanned cards often cannot tell some digits apart (0/8, 1/7, ...). When
+//! each position is given as a set of candidate digits, the check digit is usually
+//! enough to pick the one reading that is valid. The search keeps one running state
+//! per Luhn sum and a back-pointer per sum and position, so it is linear in the
+//! number of positions rather than exponential in the number of ambiguous ones.
+
+use crate::{luhn_contribution, LuhnError};
+
+/// Finds the single Luhn-valid reading of a number with ambiguous digits.
+///
+/// Each element of `positions` lists the candidate digits for one position, left
+/// to right; a position that was read unambiguously holds a single digit.
+///
+/// # Arguments
+/// * `positions` - The candidate digits for each position, including the check digit
+///
+/// # Returns
+/// * `Ok(Some(String))` - The only combination of candidates that is Luhn-valid
+/// * `Ok(None)` - If no combination is valid, or more than one is
+/// * `Err(LuhnError)` - Error if the candidate sets are malformed
+///
+/// # Examples
+/// ```
+/// use luhn_tools::resolve_ambiguous;
+///
+/// // OCR could not tell 0 from 8 in the eighth position, or 1 from 7 in the tenth
+/// let positions = ["7", "9", "9", "2", "7", "3", "9", "08", "7", "17", "3"];
+/// assert_eq!(resolve_ambiguous(&positions).unwrap().unwrap(), "79927398713");
+///
+/// // Swapping 0 and 9 between neighbours leaves the sum unchanged
+/// let positions = ["7", "9", "9", "2", "7", "3", "9", "8", "09", "90", "3"];
+/// assert_eq!(resolve_ambiguous(&positions).unwrap(), None);
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * `positions` is empty (`EmptyString`)
+/// * There is only one position (`InvalidLength`)
+/// * A position has no candidates (`ParseError`)
+/// * A candidate is not an ASCII digit (`NonNumeric`, with `index` the position)
+pub fn resolve_ambiguous(positions: &[&str]) -> Result<Option<String>, LuhnError> {
+    match positions.len() {
+        0 => return Err(LuhnError::EmptyString),
+        1 => {
+            return Err(LuhnError::InvalidLength(
+                "string must be longer than 1 character".to_string(),
+            ))
+        }
+        _ => {}
+    }
+
+    // For each Luhn sum so far, how many readings reach it (capped at 2). Each step
+    // records, per sum, the previous sum and digit of the first reading to reach it,
+    // so the answer is rebuilt once at the end instead of copied at every step.
+    let mut counts = [0u8; 10];
+    counts[0] = 1;
+    let mut steps: Vec<[(u8, u8); 10]> = Vec::with_capacity(positions.len());
+
+    for (offset, candidates) in positions.iter().rev().enumerate() {
+        let index = positions.len() - 1 - offset;
+        let digits = candidate_digits(candidates, index)?;
+        let doubled = offset % 2 == 1;
+
+        let mut next = [0u8; 10];
+        let mut step = [(0u8, 0u8); 10];
+        for (sum, &count) in counts.iter().enumerate() {
+            if count == 0 {
+                continue;
+            }
+            for &digit in &digits {
+                let contribution = usize::from(luhn_contribution(digit, doubled));
+                let target = (sum + contribution) % 10;
+                if next[target] == 0 {
+                    step[target] = (sum as u8, digit);
+                }
+                next[target] = (next[target] + count).min(2);
+            }
+        }
+        counts = next;
+        steps.push(step);
+    }
+
+    if counts[0] != 1 {
+        return Ok(None);
+    }
+    // Steps run right to left, so walking them backwards yields the leftmost digit first
+    let mut reading = String::with_capacity(steps.len());
+    let mut sum = 0;
+    for step in steps.iter().rev() {
+        let (previous, digit) = step[sum];
+        reading.push(char::from(b'0' + digit));
+        sum = usize::from(previous);
+    }
+    Ok(Some(reading))
+}
+
+/// Parses one position's candidates into distinct digit values.
+fn candidate_digits(candidates: &str, index: usize) -> Result<Vec<u8>, LuhnError> {
+    let mut digits = Vec::with_capacity(candidates.len());
+    for character in candidates.chars() {
+        if !character.is_ascii_digit() {
+            return Err(LuhnError::NonNumeric { index, character });
+        }
+        let digit = character as u8 - b'0';
+        if !digits.contains(&digit) {
+            digits.push(digit);
+        }
+    }
+    if digits.is_empty() {
+        return Err(LuhnError::ParseError(format!(
+            "position {} has no candidate digits",
+            index
+        )));
+    }
+    Ok(digits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate;
+
+    fn positions(value: &str) -> Vec<String> {
+        value.chars().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_unambiguous_input() {
+        let valid = positions("79927398713");
+        let valid: Vec<&str> = valid.iter().map(String::as_str).collect();
+        assert_eq!(resolve_ambiguous(&valid).unwrap().unwrap(), "79927398713");
+
+        let invalid = positions("79927398714");
+        let invalid: Vec<&str> = invalid.iter().map(String::as_str).collect();
+        assert_eq!(resolve_ambiguous(&invalid).unwrap(), None);
+    }
+
+    #[test]
+    fn test_resolves_ambiguous_positions() {
+        let cases: [&[&str]; 3] = [
+            &["7", "9", "9", "2", "7", "3", "9", "8", "7", "17", "38"],
+            &["7", "9", "9", "2", "7", "3", "9", "08", "7", "17", "3"],
+            &[
+                "7",
+                "9",
+                "9",
+                "2",
+                "7",
+                "3",
+                "9",
+                "8",
+                "7",
+                "1",
+                "0123456789",
+            ],
+        ];
+        for case in cases {
+            assert_eq!(resolve_ambiguous(case).unwrap().unwrap(), "79927398713");
+        }
+
+        // A long number with its check digit unknown still resolves
+        let mut long = vec!["7992739871"; 10_000].concat();
+        long.push('0');
+        let mut long = positions(&long);
+        long.last_mut().unwrap().replace_range(.., "0123456789");
+        let long: Vec<&str> = long.iter().map(String::as_str).collect();
+        let resolved = resolve_ambiguous(&long).unwrap().unwrap();
+        assert_eq!(resolved.len(), 100_001);
+        assert!(validate(&resolved).unwrap());
+
+        // Duplicate candidates do not make a reading ambiguous
+        let resolved = resolve_ambiguous(&["0", "18", "88"]).unwrap().unwrap();
+        assert!(validate(&resolved).unwrap());
+    }
+
+    #[test]
+    fn test_several_or_no_readings() {
+        let several = ["7", "9", "9", "2", "7", "3", "9", "8", "09", "90", "3"];
+        assert_eq!(resolve_ambiguous(&several).unwrap(), None);
+        assert_eq!(resolve_ambiguous(&["0", "18"]).unwrap(), None);
+        // A single ambiguous position always has exactly one valid reading
+        assert_eq!(
+            resolve_ambiguous(&["0123456789", "0"]).unwrap().unwrap(),
+            "00"
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(resolve_ambiguous(&[]).unwrap_err(), LuhnError::EmptyString);
+        assert_eq!(
+            resolve_ambiguous(&["18"]).unwrap_err(),
+            LuhnError::InvalidLength("string must be longer than 1 character".to_string())
+        );
+        assert_eq!(
+            resolve_ambiguous(&["1", "", "8"]).unwrap_err(),
+            LuhnError::ParseError("position 1 has no candidate digits".to_string())
+        );
+        assert_eq!(
+            resolve_ambiguous(&["1", "8", "0O"]).unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 2,
+                character: 'O'
+            }
+        );
+        assert_eq!(
+            resolve_ambiguous(&["1", "٣"]).unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 1,
+                character: '٣'
+            }
+        );
+    }
+}
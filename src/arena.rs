@@ -0,0 +1,178 @@
+//! Generation into a caller-provided bump arena.
+//!
+//! Burst workloads that generate many numbers per request can allocate every output
+//! string from a single [`bumpalo::Bump`] and drop them all at once, instead of
+//! paying for one heap allocation per number.
+
+use bumpalo::collections::String as BumpString;
+use bumpalo::Bump;
+
+use crate::{prepare_generate, write_generated, BatchOutcome, GenerateOptions, Limit, LuhnError};
+
+/// Generates a Luhn number or checksum, allocating the output in `bump`.
+///
+/// Behaves exactly like [`generate`](crate::generate), but the returned string
+/// lives in the arena.
+///
+/// # Examples
+/// ```
+/// use bumpalo::Bump;
+/// use luhn_tools::generate_in;
+///
+/// let bump = Bump::new();
+/// let number: &str = generate_in(&bump, "7992739871", None).unwrap();
+/// assert_eq!(number, "79927398713");
+/// ```
+///
+/// # Errors
+/// Returns the same errors as [`generate`](crate::generate).
+pub fn generate_in<'b>(
+    bump: &'b Bump,
+    value: &str,
+    options: Option<GenerateOptions>,
+) -> Result<&'b str, LuhnError> {
+    let options = options.unwrap_or_default();
+    let (payload, checksum) = prepare_generate(value, &options)?;
+
+    let mut output = BumpString::with_capacity_in(payload.len() * 2, bump);
+    write_generated(&mut output, &payload, checksum, &options)
+        .map_err(|e| LuhnError::ParseError(e.to_string()))?;
+    Ok(output.into_bump_str())
+}
+
+/// Generates Luhn numbers or checksums for many values, allocating every output
+/// string in `bump`.
+///
+/// Each value is handled independently; one malformed value does not stop the batch.
+///
+/// # Examples
+/// ```
+/// use bumpalo::Bump;
+/// use luhn_tools::generate_batch_in;
+///
+/// let bump = Bump::new();
+/// let results = generate_batch_in(&bump, ["1", "12", "x"], None);
+/// assert_eq!(results[0], Ok("18"));
+/// assert_eq!(results[1], Ok("125"));
+/// assert!(results[2].is_err());
+/// ```
+pub fn generate_batch_in<'b, 'a, I>(
+    bump: &'b Bump,
+    values: I,
+    options: Option<GenerateOptions>,
+) -> Vec<Result<&'b str, LuhnError>>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    generate_batch_in_until(bump, values, options, &Limit::default()).into_inner()
+}
+
+/// Generates Luhn numbers or checksums for many values in `bump`, stopping early
+/// when `limit` is reached.
+///
+/// The same as [`generate_batch_in`], except that an interrupted run returns
+/// results for only the values handled before the interruption.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+/// use bumpalo::Bump;
+/// use luhn_tools::{generate_batch_in_until, Limit};
+///
+/// let bump = Bump::new();
+/// let limit = Limit::timeout(Duration::from_secs(5));
+/// let outcome = generate_batch_in_until(&bump, ["1", "12"], None, &limit);
+/// assert_eq!(outcome.into_inner(), [Ok("18"), Ok("125")]);
+/// ```
+pub fn generate_batch_in_until<'b, 'a, I>(
+    bump: &'b Bump,
+    values: I,
+    options: Option<GenerateOptions>,
+    limit: &Limit,
+) -> BatchOutcome<Vec<Result<&'b str, LuhnError>>>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut checker = limit.checker();
+    let mut results = Vec::new();
+    for value in values {
+        if let Some(reason) = checker.poll() {
+            let completed = results.len();
+            return BatchOutcome::new(results, completed, Some(reason));
+        }
+        results.push(generate_in(bump, value, options));
+    }
+    BatchOutcome::Complete(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate;
+
+    #[test]
+    fn test_matches_generate() {
+        let bump = Bump::new();
+        let grouped = Some(GenerateOptions {
+            group_size: Some(4),
+            pad_to: Some(12),
+            ..Default::default()
+        });
+        for options in [None, grouped] {
+            for value in ["1", "7992739871", "00123"] {
+                assert_eq!(
+                    generate_in(&bump, value, options).unwrap(),
+                    generate(value, options).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_batch_shares_arena() {
+        let bump = Bump::new();
+        let values: Vec<String> = (0..100).map(|n| n.to_string()).collect();
+        let results = generate_batch_in(&bump, values.iter().map(String::as_str), None);
+        assert_eq!(results.len(), 100);
+        assert_eq!(results[42], Ok("422"));
+        assert!(bump.allocated_bytes() > 0);
+    }
+
+    #[test]
+    fn test_batch_until() {
+        use crate::{CancelToken, Interruption};
+
+        let bump = Bump::new();
+        let token = CancelToken::new();
+        token.cancel();
+        let limit = Limit {
+            cancel: Some(token),
+            ..Limit::default()
+        };
+        assert_eq!(
+            generate_batch_in_until(&bump, ["1", "12"], None, &limit),
+            BatchOutcome::Interrupted {
+                partial: vec![],
+                completed: 0,
+                reason: Interruption::Cancelled
+            }
+        );
+        assert!(generate_batch_in_until(&bump, ["1"], None, &Limit::default()).is_complete());
+    }
+
+    #[test]
+    fn test_error_cases() {
+        let bump = Bump::new();
+        assert_eq!(
+            generate_in(&bump, "", None).unwrap_err(),
+            LuhnError::EmptyString
+        );
+        assert_eq!(
+            generate_in(&bump, "1a", None).unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 1,
+                character: 'a'
+            }
+        );
+    }
+}
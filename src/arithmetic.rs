@@ -0,0 +1,176 @@
+//! Arithmetic on the payload of a Luhn number.
+//!
+//! Incrementing a Luhn number as a plain string corrupts its check digit. The helpers
+//! in this module operate on the payload (everything but the trailing check digit),
+//! carry across digits, and recompute the check digit afterwards. The width of the
+//! number is always preserved, including leading zeros.
+
+use crate::repair::split_check_digit;
+use crate::{generate, LuhnError};
+
+/// Adds one to the payload of a Luhn number and recomputes its check digit.
+///
+/// The existing check digit is discarded, so the input does not need to be valid.
+///
+/// # Arguments
+/// * `value` - A number including its trailing check digit
+///
+/// # Returns
+/// * `Ok(String)` - The next payload with a valid check digit, same width as the input
+/// * `Err(LuhnError)` - Error if the input is malformed or the payload would overflow
+///
+/// # Examples
+/// ```
+/// use luhn_tools::increment_payload;
+///
+/// assert_eq!(increment_payload("79927398713").unwrap(), "79927398721");
+/// assert_eq!(increment_payload("00190").unwrap(), "00208");
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * The input fails the usual input checks (empty, spaces, non-numeric, ...)
+/// * The input is only one character long
+/// * The payload is all nines and incrementing it would add a digit
+pub fn increment_payload(value: &str) -> Result<String, LuhnError> {
+    let mut payload = split_payload(value)?;
+
+    if increment_digits(&mut payload) {
+        return recompute(&payload);
+    }
+    Err(LuhnError::InvalidLength(format!(
+        "incremented payload must not exceed {} digits",
+        payload.len()
+    )))
+}
+
+/// Subtracts one from the payload of a Luhn number and recomputes its check digit.
+///
+/// The existing check digit is discarded, so the input does not need to be valid.
+///
+/// # Arguments
+/// * `value` - A number including its trailing check digit
+///
+/// # Returns
+/// * `Ok(String)` - The previous payload with a valid check digit, same width as the input
+/// * `Err(LuhnError)` - Error if the input is malformed or the payload is zero
+///
+/// # Examples
+/// ```
+/// use luhn_tools::decrement_payload;
+///
+/// assert_eq!(decrement_payload("79927398721").unwrap(), "79927398713");
+/// assert_eq!(decrement_payload("00208").unwrap(), "00190");
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * The input fails the usual input checks (empty, spaces, non-numeric, ...)
+/// * The input is only one character long
+/// * The payload is all zeros and decrementing it would go negative
+pub fn decrement_payload(value: &str) -> Result<String, LuhnError> {
+    let mut payload = split_payload(value)?;
+
+    if decrement_digits(&mut payload) {
+        return recompute(&payload);
+    }
+    Err(LuhnError::NegativeNumber)
+}
+
+/// Adds one to ASCII `digits` in place, returning false if they wrapped to zeros.
+pub(crate) fn increment_digits(digits: &mut [u8]) -> bool {
+    for digit in digits.iter_mut().rev() {
+        if *digit == b'9' {
+            *digit = b'0';
+        } else {
+            *digit += 1;
+            return true;
+        }
+    }
+    false
+}
+
+/// Subtracts one from ASCII `digits` in place, returning false if they were all
+/// zeros.
+pub(crate) fn decrement_digits(digits: &mut [u8]) -> bool {
+    for digit in digits.iter_mut().rev() {
+        if *digit == b'0' {
+            *digit = b'9';
+        } else {
+            *digit -= 1;
+            return true;
+        }
+    }
+    false
+}
+
+/// Validates the input and returns its payload digits without the check digit.
+fn split_payload(value: &str) -> Result<Vec<u8>, LuhnError> {
+    split_check_digit(value).map(|(payload, _)| payload.as_bytes().to_vec())
+}
+
+/// Appends a fresh check digit to an ASCII digit payload.
+fn recompute(payload: &[u8]) -> Result<String, LuhnError> {
+    let payload = std::str::from_utf8(payload).map_err(|e| LuhnError::ParseError(e.to_string()))?;
+    generate(payload, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate;
+
+    #[test]
+    fn test_increment() {
+        assert_eq!(increment_payload("18").unwrap(), "26");
+        assert_eq!(increment_payload("79927398713").unwrap(), "79927398721");
+        // Carries propagate and the width is preserved
+        assert_eq!(increment_payload("01990").unwrap(), "02006");
+        assert_eq!(increment_payload("0000").unwrap(), "0018");
+    }
+
+    #[test]
+    fn test_decrement() {
+        assert_eq!(decrement_payload("26").unwrap(), "18");
+        assert_eq!(decrement_payload("02006").unwrap(), "01990");
+        assert_eq!(decrement_payload("0018").unwrap(), "0000");
+    }
+
+    #[test]
+    fn test_round_trip_stays_valid() {
+        let mut value = "00000".to_string();
+        for _ in 0..200 {
+            value = increment_payload(&value).unwrap();
+            assert!(validate(&value).unwrap());
+            assert_eq!(value.len(), 5);
+        }
+        for _ in 0..200 {
+            value = decrement_payload(&value).unwrap();
+        }
+        assert_eq!(value, "00000");
+    }
+
+    #[test]
+    fn test_error_cases() {
+        assert_eq!(increment_payload("").unwrap_err(), LuhnError::EmptyString);
+        assert_eq!(
+            increment_payload("1a").unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 1,
+                character: 'a'
+            }
+        );
+        assert_eq!(
+            increment_payload("1").unwrap_err(),
+            LuhnError::InvalidLength("string must be longer than 1 character".to_string())
+        );
+        assert_eq!(
+            increment_payload("999").unwrap_err(),
+            LuhnError::InvalidLength("incremented payload must not exceed 2 digits".to_string())
+        );
+        assert_eq!(
+            decrement_payload("000").unwrap_err(),
+            LuhnError::NegativeNumber
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Bit-packed validation results for columnar workloads.
+//!
+//! Validating hundreds of millions of values into a `Vec<bool>` spends a byte per
+//! result. [`validate_bitmap`] packs the results into 64-bit words instead, using the
+//! same LSB-first layout as Arrow validity buffers.
+
+use crate::{validate, BatchOutcome, Limit};
+
+/// Packed per-value validity: bit `i` is set if value `i` is a valid Luhn number.
+///
+/// Bits are stored least-significant first within each word, so the words can be
+/// handed to Arrow-style consumers as a validity buffer. Bits past `len()` in the
+/// last word are always zero.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ValidityBitmap {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl ValidityBitmap {
+    /// Returns the number of values represented.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if no values are represented.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the validity of value `index`, or `None` if out of range.
+    pub fn get(&self, index: usize) -> Option<bool> {
+        if index >= self.len {
+            return None;
+        }
+        self.words
+            .get(index / 64)
+            .map(|word| word & (1 << (index % 64)) != 0)
+    }
+
+    /// Returns the number of valid values.
+    pub fn count_valid(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Returns the packed words.
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// Consumes the bitmap, returning the packed words.
+    pub fn into_words(self) -> Vec<u64> {
+        self.words
+    }
+
+    /// Iterates over the validity of every value, in order.
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(move |index| self.get(index).unwrap_or(false))
+    }
+
+    /// Iterates over the indices of valid values, in order.
+    pub fn valid_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(|(word_index, &word)| SetBits(word).map(move |bit| word_index * 64 + bit))
+    }
+
+    /// Appends the validity of one more value.
+    fn push(&mut self, valid: bool) {
+        let bit = self.len % 64;
+        if bit == 0 {
+            self.words.push(0);
+        }
+        if valid {
+            if let Some(word) = self.words.last_mut() {
+                *word |= 1 << bit;
+            }
+        }
+        self.len += 1;
+    }
+}
+
+/// Iterator over the positions of set bits in a word, lowest first.
+struct SetBits(u64);
+
+impl Iterator for SetBits {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.0 == 0 {
+            return None;
+        }
+        let bit = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        Some(bit)
+    }
+}
+
+/// Validates many values, returning bit-packed results.
+///
+/// Malformed values (empty, non-numeric, ...) are reported as invalid.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::validate_bitmap;
+///
+/// let bitmap = validate_bitmap(["79927398713", "79927398714", "x", "18"]);
+/// assert_eq!(bitmap.len(), 4);
+/// assert_eq!(bitmap.words(), &[0b1001]);
+/// assert_eq!(bitmap.valid_indices().collect::<Vec<_>>(), vec![0, 3]);
+/// ```
+pub fn validate_bitmap<'a, I>(values: I) -> ValidityBitmap
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    validate_bitmap_until(values, &Limit::default()).into_inner()
+}
+
+/// Validates many values into a bitmap, stopping early when `limit` is reached.
+///
+/// The same as [`validate_bitmap`], except that an interrupted run returns a
+/// bitmap covering only the values validated before the interruption.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+/// use luhn_tools::{validate_bitmap_until, Limit};
+///
+/// let outcome = validate_bitmap_until(["79927398713", "18"], &Limit::timeout(Duration::from_secs(5)));
+/// assert!(outcome.is_complete());
+/// assert_eq!(outcome.value().count_valid(), 2);
+/// ```
+pub fn validate_bitmap_until<'a, I>(values: I, limit: &Limit) -> BatchOutcome<ValidityBitmap>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let values = values.into_iter();
+    let mut bitmap = ValidityBitmap {
+        words: Vec::with_capacity((values.size_hint().0 + 63) / 64),
+        len: 0,
+    };
+    let mut checker = limit.checker();
+    for value in values {
+        if let Some(reason) = checker.poll() {
+            let completed = bitmap.len;
+            return BatchOutcome::new(bitmap, completed, Some(reason));
+        }
+        bitmap.push(validate(value).unwrap_or(false));
+    }
+    BatchOutcome::Complete(bitmap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let bitmap = validate_bitmap(Vec::new());
+        assert!(bitmap.is_empty());
+        assert_eq!(bitmap.words(), &[] as &[u64]);
+        assert_eq!(bitmap.get(0), None);
+    }
+
+    #[test]
+    fn test_spans_words() {
+        let values: Vec<String> = (0..130)
+            .map(|n| if n % 3 == 0 { "18" } else { "10" }.to_string())
+            .collect();
+        let bitmap = validate_bitmap(values.iter().map(String::as_str));
+
+        assert_eq!(bitmap.len(), 130);
+        assert_eq!(bitmap.words().len(), 3);
+        assert_eq!(bitmap.count_valid(), 44);
+        assert_eq!(bitmap.get(129), Some(true));
+        assert_eq!(bitmap.get(128), Some(false));
+        assert_eq!(bitmap.get(130), None);
+        // Bits past the end stay clear
+        assert_eq!(bitmap.words()[2] >> 2, 0);
+
+        let expected: Vec<bool> = (0..130).map(|n| n % 3 == 0).collect();
+        assert_eq!(bitmap.iter().collect::<Vec<_>>(), expected);
+        assert_eq!(
+            bitmap.valid_indices().collect::<Vec<_>>(),
+            (0..130).filter(|n| n % 3 == 0).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_malformed_values_are_invalid() {
+        let bitmap = validate_bitmap(["", "1", "1a", "18"]);
+        assert_eq!(bitmap.into_words(), vec![0b1000]);
+    }
+
+    #[test]
+    fn test_interrupted() {
+        use crate::{CancelToken, Interruption};
+
+        let token = CancelToken::new();
+        let limit = Limit {
+            cancel: Some(token.clone()),
+            ..Limit::default()
+        };
+        // Cancel while the third value is being read
+        let values = ["18", "10", "18", "18"]
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| {
+                if i == 2 {
+                    token.cancel();
+                }
+                value
+            });
+        match validate_bitmap_until(values, &limit) {
+            BatchOutcome::Interrupted {
+                partial,
+                completed,
+                reason,
+            } => {
+                assert_eq!(completed, 2);
+                assert_eq!(partial.len(), 2);
+                assert_eq!(partial.words(), &[0b01]);
+                assert_eq!(reason, Interruption::Cancelled);
+            }
+            BatchOutcome::Complete(_) => panic!("expected an interruption"),
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! Per-block check digits.
+//!
+//! Some structured labels carry a check digit after every block of N digits rather
+//! than one for the whole number, so a misread can be pinned to a single block. Each
+//! block is checked on its own with plain Luhn.
+
+use crate::{generate, handle_errors, validate, LuhnError};
+
+/// Inserts a Luhn check digit after every `block_len` digits of `value`.
+///
+/// A trailing partial block gets its own check digit as well.
+///
+/// # Arguments
+/// * `value` - The payload, without check digits
+/// * `block_len` - Number of payload digits per block
+///
+/// # Returns
+/// * `Ok(String)` - The payload with a check digit after each block
+/// * `Err(LuhnError)` - Error if the input or block length is invalid
+///
+/// # Examples
+/// ```
+/// use luhn_tools::generate_blocks;
+///
+/// assert_eq!(generate_blocks("12345678", 4).unwrap(), "1234456788");
+/// assert_eq!(generate_blocks("123456", 4).unwrap(), "12344562");
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * The input fails the usual input checks (empty, spaces, non-numeric, ...)
+/// * `block_len` is zero
+pub fn generate_blocks(value: &str, block_len: usize) -> Result<String, LuhnError> {
+    handle_errors(value)?;
+    check_block_len(block_len)?;
+
+    let mut result = String::with_capacity(value.len() + value.len() / block_len + 1);
+    for block in value.as_bytes().chunks(block_len) {
+        let block = core::str::from_utf8(block).unwrap_or_default();
+        result.push_str(&generate(block, None)?);
+    }
+    Ok(result)
+}
+
+/// Validates a number produced by [`generate_blocks`].
+///
+/// The input is read in chunks of `block_len + 1` digits (the last chunk may be
+/// shorter), each ending in the check digit for that block.
+///
+/// # Arguments
+/// * `value` - The full number, including every block's check digit
+/// * `block_len` - Number of payload digits per block
+///
+/// # Returns
+/// * `Ok(true)` - If every block's check digit is correct
+/// * `Ok(false)` - If any block's check digit is wrong
+/// * `Err(LuhnError)` - Error if the input or block length is invalid
+///
+/// # Examples
+/// ```
+/// use luhn_tools::validate_blocks;
+///
+/// assert!(validate_blocks("1234456788", 4).unwrap());
+/// assert!(!validate_blocks("1234456780", 4).unwrap());
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * The input fails the usual input checks (empty, spaces, non-numeric, ...)
+/// * `block_len` is zero
+/// * The last chunk is a lone check digit with no payload
+pub fn validate_blocks(value: &str, block_len: usize) -> Result<bool, LuhnError> {
+    handle_errors(value)?;
+    check_block_len(block_len)?;
+    // A block longer than any string reads the whole value as one chunk
+    let chunk_len = block_len.saturating_add(1);
+    if value.len() % chunk_len == 1 {
+        return Err(LuhnError::InvalidLength(
+            "last block must contain at least one digit and a check digit".to_string(),
+        ));
+    }
+
+    for chunk in value.as_bytes().chunks(chunk_len) {
+        let chunk = core::str::from_utf8(chunk).unwrap_or_default();
+        if !validate(chunk)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+fn check_block_len(block_len: usize) -> Result<(), LuhnError> {
+    if block_len == 0 {
+        return Err(LuhnError::InvalidLength(
+            "block length must be greater than 0".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for block_len in 1..=6 {
+            let number = generate_blocks("79927398710", block_len).unwrap();
+            assert!(validate_blocks(&number, block_len).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_single_block_matches_generate() {
+        assert_eq!(
+            generate_blocks("7992739871", 10).unwrap(),
+            generate("7992739871", None).unwrap()
+        );
+        assert_eq!(generate_blocks("7992739871", 20).unwrap(), "79927398713");
+        assert_eq!(
+            generate_blocks("7992739871", usize::MAX).unwrap(),
+            "79927398713"
+        );
+        assert!(validate_blocks("79927398713", usize::MAX).unwrap());
+    }
+
+    #[test]
+    fn test_error_is_localized_to_block() {
+        assert!(validate_blocks("1234456788", 4).unwrap());
+        assert!(!validate_blocks("1234556788", 4).unwrap());
+        assert!(!validate_blocks("1234456798", 4).unwrap());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            generate_blocks("1234", 0).unwrap_err(),
+            LuhnError::InvalidLength("block length must be greater than 0".to_string())
+        );
+        assert_eq!(
+            validate_blocks("123441", 4).unwrap_err(),
+            LuhnError::InvalidLength(
+                "last block must contain at least one digit and a check digit".to_string()
+            )
+        );
+        assert_eq!(generate_blocks("", 4).unwrap_err(), LuhnError::EmptyString);
+        assert_eq!(
+            validate_blocks("12 34", 4).unwrap_err(),
+            LuhnError::ContainsSpaces {
+                index: 2,
+                character: ' '
+            }
+        );
+    }
+}
@@ -0,0 +1,511 @@
+//! Card-network shaped numbers.
+//!
+//! Payment sandboxes reject numbers that do not look like a real network's cards.
+//! [`Brand`] records each network's prefix ranges and card length, and
+//! [`random_card`] draws a number inside them with a valid check digit, and
+//! [`CardNumber`] holds a number that passed both Luhn and the length rules of a
+//! payment card.
+
+use core::borrow::Borrow;
+use core::str::FromStr;
+use std::fmt;
+
+use crate::{
+    check_length_bounds, handle_errors, locate_in_input, normalize_input, strict_validate,
+    LuhnError, Scratch, ValidateOptions,
+};
+#[cfg(feature = "random")]
+use crate::{random_from_rng, RandomOptions};
+
+/// Shortest payment card number, in digits.
+const MIN_CARD_LEN: usize = 12;
+/// Longest payment card number, in digits.
+const MAX_CARD_LEN: usize = 19;
+/// Digits in an issuer identification number.
+const IIN_LEN: usize = 6;
+
+/// A card network.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Brand {
+    /// Visa: prefix 4, 16 digits
+    Visa,
+    /// Mastercard: prefixes 51–55 and 2221–2720, 16 digits
+    Mastercard,
+    /// American Express: prefixes 34 and 37, 15 digits
+    Amex,
+    /// Discover: prefixes 6011, 644–649 and 65, 16 digits
+    Discover,
+}
+
+impl Brand {
+    /// Every supported brand.
+    pub const ALL: [Brand; 4] = [Brand::Visa, Brand::Mastercard, Brand::Amex, Brand::Discover];
+
+    /// Returns the brand's display name, e.g. "American Express".
+    pub fn name(&self) -> &'static str {
+        match self {
+            Brand::Visa => "Visa",
+            Brand::Mastercard => "Mastercard",
+            Brand::Amex => "American Express",
+            Brand::Discover => "Discover",
+        }
+    }
+
+    /// Returns the brand's prefix ranges, as `(low, high)` pairs of equal width.
+    pub fn prefix_ranges(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Brand::Visa => &[("4", "4")],
+            Brand::Mastercard => &[("51", "55"), ("2221", "2720")],
+            Brand::Amex => &[("34", "34"), ("37", "37")],
+            Brand::Discover => &[("6011", "6011"), ("644", "649"), ("65", "65")],
+        }
+    }
+
+    /// Returns the number of digits in the brand's cards, check digit included.
+    pub fn card_len(&self) -> usize {
+        match self {
+            Brand::Amex => 15,
+            Brand::Visa | Brand::Mastercard | Brand::Discover => 16,
+        }
+    }
+
+    /// Returns true if `value` has this brand's length and one of its prefixes.
+    /// The check digit is not verified.
+    pub fn matches(&self, value: &str) -> bool {
+        value.len() == self.card_len()
+            && self.prefix_ranges().iter().any(|(low, high)| {
+                value
+                    .get(..low.len())
+                    .map_or(false, |prefix| *low <= prefix && prefix <= *high)
+            })
+    }
+}
+
+/// A validated payment card number, stored as its canonical digits.
+///
+/// Spaces and dashes in the input are stripped, surrounding whitespace is
+/// trimmed, and the remaining digits must be 12 to 19 long and pass Luhn.
+///
+/// `Debug` output shows only the first six and last four digits, so a card that
+/// ends up in a log line is not exposed in full. `Display` prints every digit.
+/// The `zeroize` feature wipes the digits on drop, along with any copy made while
+/// stripping separators.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{Brand, CardNumber};
+///
+/// let card: CardNumber = "4111 1111 1111 1111".parse().unwrap();
+/// assert_eq!(card.as_str(), "4111111111111111");
+/// assert_eq!(card.iin(), "411111");
+/// assert_eq!(card.last4(), "1111");
+/// assert_eq!(card.brand(), Some(Brand::Visa));
+///
+/// assert_eq!(format!("{:?}", card), "CardNumber(411111******1111)");
+///
+/// assert!(CardNumber::new("4111-1111-1111-1112").is_err());
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CardNumber(String);
+
+impl CardNumber {
+    /// Normalizes and validates a card number.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The digits left after stripping separators fail the usual input checks
+    /// * There are fewer than 12 or more than 19 digits
+    /// * The check digit is wrong (`InvalidChecksum`)
+    pub fn new(value: &str) -> Result<Self, LuhnError> {
+        let options = ValidateOptions {
+            allow_separators: true,
+            trim_whitespace: true,
+            ..ValidateOptions::STRICT
+        };
+        let digits = Scratch(normalize_input(value, &options));
+        handle_errors(&digits).map_err(|error| locate_in_input(error, value, &options))?;
+        check_length_bounds(digits.len(), Some(MIN_CARD_LEN), Some(MAX_CARD_LEN))?;
+        strict_validate(&digits)?;
+        Ok(CardNumber(digits.into_owned()))
+    }
+
+    /// Returns the canonical digits, check digit included.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the issuer identification number, the first six digits.
+    pub fn iin(&self) -> &str {
+        self.0.get(..IIN_LEN).unwrap_or_default()
+    }
+
+    /// Returns the last four digits, as printed on receipts.
+    pub fn last4(&self) -> &str {
+        self.0
+            .get(self.0.len().saturating_sub(4)..)
+            .unwrap_or_default()
+    }
+
+    /// Returns the card network whose prefix and length the number matches, if any.
+    pub fn brand(&self) -> Option<Brand> {
+        Brand::ALL.into_iter().find(|brand| brand.matches(&self.0))
+    }
+
+    /// Unwraps the card number into its canonical digits.
+    pub fn into_string(mut self) -> String {
+        core::mem::take(&mut self.0)
+    }
+}
+
+impl fmt::Debug for CardNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("CardNumber")
+            .field(&format_args!("{}", mask_pan(&self.0)))
+            .finish()
+    }
+}
+
+impl fmt::Display for CardNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for CardNumber {
+    type Err = LuhnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CardNumber::new(s)
+    }
+}
+
+impl TryFrom<&str> for CardNumber {
+    type Error = LuhnError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        CardNumber::new(value)
+    }
+}
+
+impl AsRef<str> for CardNumber {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for CardNumber {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<CardNumber> for String {
+    fn from(value: CardNumber) -> String {
+        value.into_string()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for CardNumber {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for CardNumber {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for CardNumber {}
+
+/// Masks a number for debug output and logs, keeping the first six and last four
+/// characters.
+///
+/// Numbers shorter than a card (12 digits) would give away too much that way,
+/// so they are masked in full.
+pub(crate) fn mask_pan(value: &str) -> String {
+    let len = value.chars().count();
+    value
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            if len >= MIN_CARD_LEN && (i < IIN_LEN || i >= len - 4) {
+                c
+            } else {
+                '*'
+            }
+        })
+        .collect()
+}
+
+/// Generates a random card number of the given brand with a valid Luhn checksum.
+///
+/// A prefix is drawn uniformly from one of the brand's ranges (each range equally
+/// likely), the remaining digits at random, and the check digit is appended.
+///
+/// # Arguments
+/// * `brand` - The card network whose prefixes and length to use
+///
+/// # Returns
+/// * `Ok(String)` - A random, Luhn-valid number shaped like a card of `brand`
+/// * `Err(LuhnError)` - Error if such numbers may not be generated
+///
+/// # Examples
+/// ```
+/// # #[cfg(not(feature = "forbid-real-iins"))]
+/// # {
+/// use luhn_tools::{random_card, validate, Brand};
+///
+/// let card = random_card(Brand::Amex).unwrap();
+/// assert_eq!(card.len(), 15);
+/// assert!(card.starts_with("34") || card.starts_with("37"));
+/// assert!(validate(&card).unwrap());
+/// # }
+/// ```
+///
+/// # Errors
+/// With the `forbid-real-iins` feature, always returns `RealIssuerPrefix`: every
+/// brand range is allocated to a real issuer.
+#[cfg(feature = "random")]
+pub fn random_card(brand: Brand) -> Result<String, LuhnError> {
+    random_card_from_rng(brand, &mut rand::thread_rng())
+}
+
+/// Draws a card number of the given brand from `rng`.
+#[cfg(feature = "random")]
+pub(crate) fn random_card_from_rng<R: rand::Rng + ?Sized>(
+    brand: Brand,
+    rng: &mut R,
+) -> Result<String, LuhnError> {
+    use rand::seq::SliceRandom;
+
+    let (low, high) = brand
+        .prefix_ranges()
+        .choose(rng)
+        .copied()
+        .unwrap_or(("", ""));
+    let low_value: u32 = low.parse().unwrap_or(0);
+    let high_value: u32 = high.parse().unwrap_or(0);
+    let prefix = format!(
+        "{:0width$}",
+        rng.gen_range(low_value..=high_value),
+        width = low.len()
+    );
+
+    random_from_rng(brand.card_len(), &prefix, "", RandomOptions::default(), rng)
+}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for CardNumber {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for CardNumber {
+    /// Deserializes a string and validates it as [`CardNumber::new`] does.
+    ///
+    /// The error message names the problem but never echoes the input.
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Scratch(<String as ::serde::Deserialize>::deserialize(deserializer)?.into());
+        CardNumber::new(&value)
+            .map_err(|e| ::serde::de::Error::custom(format_args!("invalid card number: {}", e)))
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for CardNumber {
+    fn schema_name() -> String {
+        "CardNumber".to_string()
+    }
+
+    fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        crate::schema::digit_string(
+            "^[0-9]{12,19}$",
+            "A payment card number of 12 to 19 digits whose last digit is a valid Luhn check digit.",
+        )
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+impl quickcheck::Arbitrary for CardNumber {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        let extra =
+            <usize as quickcheck::Arbitrary>::arbitrary(g) % (MAX_CARD_LEN - MIN_CARD_LEN + 1);
+        CardNumber(crate::property::valid_digits(g, MIN_CARD_LEN - 1 + extra))
+    }
+
+    /// Shrinks toward shorter valid numbers, down to the 12-digit minimum.
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let payload = self
+            .0
+            .get(..self.0.len().saturating_sub(1))
+            .unwrap_or_default();
+        Box::new(
+            crate::property::shrink_payload(payload, MIN_CARD_LEN - 1)
+                .into_iter()
+                .map(CardNumber),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        assert!(Brand::Visa.matches("4111111111111111"));
+        assert!(Brand::Mastercard.matches("5500000000000004"));
+        assert!(Brand::Mastercard.matches("2221000000000009"));
+        assert!(!Brand::Mastercard.matches("2721000000000000"));
+        assert!(Brand::Amex.matches("378282246310005"));
+        assert!(!Brand::Amex.matches("3782822463100050"));
+        assert!(Brand::Discover.matches("6011111111111117"));
+        assert!(Brand::Discover.matches("6445644564456445"));
+        assert!(!Brand::Discover.matches("6431111111111111"));
+    }
+
+    #[test]
+    fn test_ranges_have_equal_width() {
+        for brand in Brand::ALL {
+            for (low, high) in brand.prefix_ranges() {
+                assert_eq!(low.len(), high.len());
+                assert!(low <= high);
+            }
+        }
+    }
+
+    #[test]
+    fn test_card_number() {
+        let card = CardNumber::new("  3782-822463-10005 ").unwrap();
+        assert_eq!(card.as_str(), "378282246310005");
+        assert_eq!(card.to_string(), "378282246310005");
+        assert_eq!(card.iin(), "378282");
+        assert_eq!(card.last4(), "0005");
+        assert_eq!(card.brand(), Some(Brand::Amex));
+        assert_eq!(card.clone().into_string(), "378282246310005");
+        assert_eq!(CardNumber::try_from("378282246310005").unwrap(), card);
+
+        // Valid numbers outside the known networks have no brand
+        let card: CardNumber = "800000000003".parse().unwrap();
+        assert_eq!(card.brand(), None);
+        assert_eq!(card.last4(), "0003");
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroize() {
+        use zeroize::Zeroize;
+
+        let mut value = CardNumber::new("4111111111111111").unwrap();
+        value.zeroize();
+        assert_eq!(value.as_str(), "");
+        // Accessors stay panic-free on a wiped value
+        assert_eq!(value.iin(), "");
+        assert_eq!(value.last4(), "");
+        assert_eq!(value.brand(), None);
+    }
+
+    #[test]
+    fn test_debug_is_masked() {
+        let card = CardNumber::new("4111 1111 1111 1111").unwrap();
+        assert_eq!(format!("{:?}", card), "CardNumber(411111******1111)");
+        assert_eq!(
+            format!("{:#?}", card),
+            "CardNumber(\n    411111******1111,\n)"
+        );
+        assert_eq!(
+            format!("{:?}", Some(card)),
+            "Some(CardNumber(411111******1111))"
+        );
+
+        assert_eq!(mask_pan("4000000000000000006"), "400000*********0006");
+        assert_eq!(mask_pan("79927398713"), "***********");
+        assert_eq!(mask_pan(""), "");
+        assert_eq!(mask_pan("٧٩٩٢٧٣٩٨٧١٣٤"), "٧٩٩٢٧٣**٧١٣٤");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        // Formatted input is accepted and serialized back as canonical digits
+        let card: CardNumber = serde_json::from_str("\"4111 1111 1111 1111\"").unwrap();
+        assert_eq!(
+            serde_json::to_string(&card).unwrap(),
+            "\"4111111111111111\""
+        );
+
+        let err = serde_json::from_str::<CardNumber>("\"4111111111111112\"").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("invalid card number: check digit is invalid (expected 1, found 2)"));
+        assert!(!err.to_string().contains("4111111111111112"));
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_json_schema() {
+        let schema = serde_json::to_value(schemars::schema_for!(CardNumber)).unwrap();
+        assert_eq!(schema["type"], "string");
+        assert_eq!(schema["pattern"], "^[0-9]{12,19}$");
+        assert!(schema["description"].as_str().unwrap().contains("Luhn"));
+    }
+
+    #[test]
+    fn test_card_number_errors() {
+        assert_eq!(
+            CardNumber::new("4111 1111 1111 1112").unwrap_err(),
+            LuhnError::InvalidChecksum {
+                expected: 1,
+                found: 2
+            }
+        );
+        assert_eq!(
+            CardNumber::new("79927398713").unwrap_err(),
+            LuhnError::InvalidLength("string must be between 12 and 19 characters".to_string())
+        );
+        assert_eq!(
+            CardNumber::new(&"0".repeat(20)).unwrap_err(),
+            LuhnError::InvalidLength("string must be between 12 and 19 characters".to_string())
+        );
+        assert_eq!(
+            CardNumber::new("4111.1111.1111.1111").unwrap_err(),
+            LuhnError::FloatingPoint
+        );
+        assert_eq!(CardNumber::new(" - ").unwrap_err(), LuhnError::EmptyString);
+        assert_eq!(
+            CardNumber::new(" 4111 1111 x111 1111").unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 11,
+                character: 'x'
+            }
+        );
+    }
+
+    #[cfg(all(feature = "random", not(feature = "forbid-real-iins")))]
+    #[test]
+    fn test_random_card() {
+        for brand in Brand::ALL {
+            for _ in 0..50 {
+                let card = random_card(brand).unwrap();
+                assert!(brand.matches(&card), "{:?} {}", brand, card);
+                assert!(crate::validate(&card).unwrap());
+            }
+        }
+    }
+
+    #[cfg(all(feature = "random", feature = "forbid-real-iins"))]
+    #[test]
+    fn test_random_card_forbidden() {
+        for brand in Brand::ALL {
+            assert_eq!(random_card(brand), Err(LuhnError::RealIssuerPrefix));
+        }
+    }
+}
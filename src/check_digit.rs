@@ -0,0 +1,234 @@
+//! A common interface for check digit algorithms.
+//!
+//! Luhn is one of several schemes that append a check character to a payload.
+//! [`CheckDigit`] is the interface they share, so code that only needs to compute
+//! or verify check characters can be written once and given any algorithm, either
+//! as a generic parameter or as a `&dyn CheckDigit`.
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+
+use crate::LuhnError;
+
+/// An algorithm that computes a check value for a payload.
+///
+/// Most schemes use a single check character; some, such as ISO 7064 MOD 97-10,
+/// use two. [`CheckDigit::check_len`] says how many, and defaults to one.
+///
+/// Only [`CheckDigit::compute`] is required. The default
+/// [`CheckDigit::validate`] treats the last [`CheckDigit::check_len`] characters as
+/// the check value and compares them with the value computed for the rest; the
+/// default [`CheckDigit::append`] adds the computed value to the payload.
+///
+/// The trait is object safe, so algorithms can be chosen at runtime.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{CheckDigit, WeightedMod10};
+///
+/// fn stamp(algorithm: &dyn CheckDigit, payload: &str) -> String {
+///     algorithm.append(payload).unwrap()
+/// }
+///
+/// assert_eq!(WeightedMod10::LUHN.compute("7992739871").unwrap(), "3");
+/// assert_eq!(stamp(&WeightedMod10::LUHN, "7992739871"), "79927398713");
+/// assert_eq!(stamp(&WeightedMod10::UPC, "03600029145"), "036000291452");
+/// assert!(CheckDigit::validate(&WeightedMod10::LUHN, "79927398713").unwrap());
+/// ```
+pub trait CheckDigit {
+    /// Computes the check value for `payload`, [`CheckDigit::check_len`]
+    /// characters long.
+    ///
+    /// # Errors
+    /// Returns an error if the payload is not valid input for the algorithm.
+    fn compute(&self, payload: &str) -> Result<String, LuhnError>;
+
+    /// Returns how many characters the check value has.
+    fn check_len(&self) -> usize {
+        1
+    }
+
+    /// Validates a value that ends with its check value.
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If the check value matches the payload
+    /// * `Ok(false)` - If it does not
+    /// * `Err(LuhnError)` - The errors of [`CheckDigit::compute`], or
+    ///   `InvalidLength` if the value has no characters before its check value
+    fn validate(&self, value: &str) -> Result<bool, LuhnError> {
+        if value.is_empty() {
+            return Err(LuhnError::EmptyString);
+        }
+        let check_len = self.check_len();
+        let split = value
+            .char_indices()
+            .rev()
+            .nth(check_len.saturating_sub(1))
+            .map(|(index, _)| index)
+            .filter(|&index| index > 0);
+        let Some((payload, check)) =
+            split.and_then(|index| Some((value.get(..index)?, value.get(index..)?)))
+        else {
+            return Err(LuhnError::InvalidLength(format!(
+                "string must be longer than {} character{}",
+                check_len,
+                if check_len == 1 { "" } else { "s" }
+            )));
+        };
+        Ok(self.compute(payload)? == check)
+    }
+
+    /// Returns `payload` followed by its check value.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`CheckDigit::compute`].
+    fn append(&self, payload: &str) -> Result<String, LuhnError> {
+        let check = self.compute(payload)?;
+        let mut value = String::with_capacity(payload.len() + check.len());
+        value.push_str(payload);
+        value.push_str(&check);
+        Ok(value)
+    }
+}
+
+impl<T: CheckDigit + ?Sized> CheckDigit for &T {
+    fn compute(&self, payload: &str) -> Result<String, LuhnError> {
+        (**self).compute(payload)
+    }
+
+    fn check_len(&self) -> usize {
+        (**self).check_len()
+    }
+
+    fn validate(&self, value: &str) -> Result<bool, LuhnError> {
+        (**self).validate(value)
+    }
+
+    fn append(&self, payload: &str) -> Result<String, LuhnError> {
+        (**self).append(payload)
+    }
+}
+
+impl<T: CheckDigit + ?Sized> CheckDigit for Box<T> {
+    fn compute(&self, payload: &str) -> Result<String, LuhnError> {
+        (**self).compute(payload)
+    }
+
+    fn check_len(&self) -> usize {
+        (**self).check_len()
+    }
+
+    fn validate(&self, value: &str) -> Result<bool, LuhnError> {
+        (**self).validate(value)
+    }
+
+    fn append(&self, payload: &str) -> Result<String, LuhnError> {
+        (**self).append(payload)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::WeightedMod10;
+
+    /// A toy algorithm that only implements `compute`: the payload length mod 10.
+    struct LengthDigit;
+
+    impl CheckDigit for LengthDigit {
+        fn compute(&self, payload: &str) -> Result<String, LuhnError> {
+            if payload.is_empty() {
+                return Err(LuhnError::EmptyString);
+            }
+            Ok((payload.chars().count() % 10).to_string())
+        }
+    }
+
+    /// A toy two-character scheme: the payload length mod 100, zero-padded.
+    struct LengthPair;
+
+    impl CheckDigit for LengthPair {
+        fn compute(&self, payload: &str) -> Result<String, LuhnError> {
+            Ok(format!("{:02}", payload.chars().count() % 100))
+        }
+
+        fn check_len(&self) -> usize {
+            2
+        }
+    }
+
+    fn round_trip<A: CheckDigit>(algorithm: A, payload: &str) -> bool {
+        let value = algorithm.append(payload).unwrap();
+        algorithm.validate(&value).unwrap()
+    }
+
+    #[test]
+    fn test_default_methods() {
+        assert_eq!(LengthDigit.append("abc").unwrap(), "abc3");
+        assert!(LengthDigit.validate("abc3").unwrap());
+        assert!(!LengthDigit.validate("abc4").unwrap());
+        assert!(LengthDigit.validate("ééé3").unwrap());
+        assert_eq!(
+            LengthDigit.validate("3").unwrap_err(),
+            LuhnError::InvalidLength("string must be longer than 1 character".to_string())
+        );
+        assert_eq!(
+            LengthDigit.validate("").unwrap_err(),
+            LuhnError::EmptyString
+        );
+    }
+
+    #[test]
+    fn test_multi_character_check() {
+        assert_eq!(LengthPair.append("abc").unwrap(), "abc03");
+        assert!(LengthPair.validate("abc03").unwrap());
+        assert!(LengthPair.validate("é01").unwrap());
+        assert!(!LengthPair.validate("abc3").unwrap());
+        assert!(round_trip(&LengthPair as &dyn CheckDigit, "12345"));
+        for short in ["03", "3"] {
+            assert_eq!(
+                LengthPair.validate(short).unwrap_err(),
+                LuhnError::InvalidLength("string must be longer than 2 characters".to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn test_generic_and_dynamic_use() {
+        assert!(round_trip(WeightedMod10::LUHN, "7992739871"));
+        assert!(round_trip(
+            &WeightedMod10::UPC as &dyn CheckDigit,
+            "03600029145"
+        ));
+        assert!(round_trip(LengthDigit, "12345"));
+
+        let algorithms: [Box<dyn CheckDigit>; 3] = [
+            Box::new(WeightedMod10::LUHN),
+            Box::new(WeightedMod10::UPC),
+            Box::new(LengthDigit),
+        ];
+        for algorithm in &algorithms {
+            assert!(round_trip(algorithm, "03600029145"));
+        }
+        assert_eq!(algorithms[0].compute("7992739871").unwrap(), "3");
+    }
+
+    #[test]
+    fn test_weighted_matches_inherent_methods() {
+        let scheme = WeightedMod10::LUHN;
+        assert_eq!(
+            CheckDigit::append(&scheme, "7992739871").unwrap(),
+            scheme.generate("7992739871").unwrap()
+        );
+        // Validation keeps the inherent method's error precedence
+        assert_eq!(
+            CheckDigit::validate(&scheme, "7992 7398713").unwrap_err(),
+            scheme.validate("7992 7398713").unwrap_err()
+        );
+        assert_eq!(
+            CheckDigit::compute(&scheme, "").unwrap_err(),
+            LuhnError::EmptyString
+        );
+    }
+}
@@ -0,0 +1,135 @@
+//! Error plumbing for I/O-heavy callers.
+//!
+//! File-processing code usually deals in [`std::io::Error`] and wants to know which
+//! input failed. [`LuhnError`] converts into an `io::Error` of kind `InvalidData`, and
+//! [`ResultExt::with_input`] attaches the offending input, masked so that logs never
+//! carry a full account number.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use crate::card::mask_pan;
+use crate::LuhnError;
+
+/// A [`LuhnError`] together with the (masked) input that caused it.
+///
+/// Implements [`std::error::Error`] with the `LuhnError` as its source, so it works
+/// with `?` in functions returning `Box<dyn Error>`, `anyhow::Result` and the like.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InputError {
+    /// The input masked like a card number in debug output: the first six and last
+    /// four characters are kept, and inputs shorter than a card are masked in full.
+    pub masked_input: String,
+    /// The underlying error.
+    pub error: LuhnError,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (input: {})", self.error, self.masked_input)
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Attaches the offending input to a `Result<T, LuhnError>`.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{strict_validate, ResultExt};
+///
+/// let error = strict_validate("79927398714").with_input("79927398714").unwrap_err();
+/// assert_eq!(error.masked_input, "***********");
+/// assert_eq!(
+///     error.to_string(),
+///     "check digit is invalid (expected 3, found 4) (input: ***********)"
+/// );
+///
+/// let error = strict_validate("4111111111111112").with_input("4111111111111112").unwrap_err();
+/// assert_eq!(error.masked_input, "411111******1112");
+///
+/// // Converts into io::Error for file-processing code
+/// let io_error: std::io::Error = error.into();
+/// assert_eq!(io_error.kind(), std::io::ErrorKind::InvalidData);
+/// ```
+pub trait ResultExt<T> {
+    /// Wraps the error, if any, in an [`InputError`] carrying `input` masked.
+    fn with_input(self, input: &str) -> Result<T, InputError>;
+}
+
+impl<T> ResultExt<T> for Result<T, LuhnError> {
+    fn with_input(self, input: &str) -> Result<T, InputError> {
+        self.map_err(|error| InputError {
+            masked_input: mask_pan(input),
+            error,
+        })
+    }
+}
+
+impl From<LuhnError> for io::Error {
+    fn from(error: LuhnError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
+
+impl From<InputError> for io::Error {
+    fn from(error: InputError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate;
+
+    #[test]
+    fn test_with_input() {
+        assert_eq!(validate("79927398713").with_input("79927398713"), Ok(true));
+
+        let error = validate("7992 7398 713")
+            .with_input("7992 7398 713")
+            .unwrap_err();
+        assert_eq!(error.masked_input, "7992 7*** 713");
+
+        let error = validate("4111 1111 1111 1111")
+            .with_input("4111 1111 1111 1111")
+            .unwrap_err();
+        assert_eq!(error.masked_input, "4111 1*********1111");
+        assert_eq!(
+            error.error,
+            LuhnError::ContainsSpaces {
+                index: 4,
+                character: ' '
+            }
+        );
+        assert_eq!(error.source().unwrap().to_string(), error.error.to_string());
+    }
+
+    #[test]
+    fn test_io_error_conversion() {
+        let io_error = io::Error::from(LuhnError::EmptyString);
+        assert_eq!(io_error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(io_error.to_string(), "string cannot be empty");
+        assert_eq!(
+            io_error
+                .into_inner()
+                .unwrap()
+                .downcast::<LuhnError>()
+                .unwrap(),
+            Box::new(LuhnError::EmptyString)
+        );
+
+        fn process(line: &str) -> io::Result<bool> {
+            Ok(validate(line).with_input(line)?)
+        }
+        let error = process("12a4").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().ends_with("(input: ****)"));
+    }
+}
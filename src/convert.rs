@@ -0,0 +1,342 @@
+//! Re-keying identifiers from one check digit scheme to another.
+//!
+//! Migrations between numbering schemes, such as moving legacy MOD 11-2 account
+//! numbers to a Luhn-based format, have to strip each old check character and
+//! prefix, apply the new prefix and append a new check character.
+//! [`convert_scheme_batch`] does this in bulk with algorithms looked up in an
+//! [`AlgorithmRegistry`], and records what happened to every item.
+
+use crate::{AlgorithmRegistry, BatchOutcome, CheckDigit, Limit, LuhnError};
+
+/// The source and target schemes of a conversion.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SchemeConversion {
+    /// Registry name of the algorithm the inputs are checked with, e.g. `"mod11-2"`
+    pub from: String,
+    /// Prefix every input must start with; removed before re-keying
+    pub from_prefix: String,
+    /// Registry name of the algorithm for the outputs, e.g. `"luhn"`
+    pub to: String,
+    /// Prefix placed in front of every payload
+    pub to_prefix: String,
+}
+
+impl SchemeConversion {
+    /// Returns a conversion from `from` to `to` without prefixes.
+    pub fn new(from: &str, to: &str) -> Self {
+        SchemeConversion {
+            from: from.to_string(),
+            to: to.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// The audit record for one input of [`convert_scheme_batch`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConversionRecord {
+    /// Position of the input in the batch
+    pub index: usize,
+    /// The input as given
+    pub input: String,
+    /// What the input was converted to, or why it was not
+    pub result: Result<Conversion, LuhnError>,
+}
+
+/// A successfully re-keyed identifier.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Conversion {
+    /// The payload carried over, without either scheme's prefix or check character
+    pub payload: String,
+    /// The check value of the input under the source scheme
+    pub old_check: String,
+    /// The check value of the output under the target scheme
+    pub new_check: String,
+    /// The new identifier: target prefix, payload and new check value
+    pub value: String,
+}
+
+/// Converts identifiers from one registered scheme to another.
+///
+/// Each input is checked with the source algorithm, stripped of its check
+/// character and source prefix, given the target prefix, and completed with the
+/// target algorithm's check character. Inputs are handled independently; one bad
+/// input does not stop the batch.
+///
+/// # Arguments
+/// * `registry` - Where the algorithms named in `conversion` are looked up
+/// * `conversion` - The source and target schemes and prefixes
+/// * `values` - The identifiers to convert, each with its check character
+///
+/// # Returns
+/// * `Ok(Vec<ConversionRecord>)` - One record per input, in input order
+/// * `Err(LuhnError)` - Error if either algorithm is not registered
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{convert_scheme_batch, AlgorithmRegistry, SchemeConversion};
+///
+/// let registry = AlgorithmRegistry::new();
+/// let conversion = SchemeConversion {
+///     from_prefix: "0000000".to_string(),
+///     to_prefix: "9".to_string(),
+///     ..SchemeConversion::new("mod11-2", "luhn")
+/// };
+///
+/// let records =
+///     convert_scheme_batch(&registry, &conversion, ["0000000218250097", "0000000218250098"])
+///         .unwrap();
+/// let converted = records[0].result.as_ref().unwrap();
+/// assert_eq!(converted.payload, "21825009");
+/// assert_eq!(converted.old_check, "7");
+/// assert_eq!(converted.value, "9218250091");
+/// assert!(records[1].result.is_err());
+/// ```
+///
+/// # Errors
+/// Returns `InvalidConfiguration` if `conversion.from` or `conversion.to` is not
+/// registered. Per-input failures are reported in each record instead:
+/// * The errors of the source or target algorithm
+/// * `ParseError` if the check character does not match the source scheme
+/// * `ParseError` if the input does not start with the source prefix
+pub fn convert_scheme_batch<'a, I>(
+    registry: &AlgorithmRegistry,
+    conversion: &SchemeConversion,
+    values: I,
+) -> Result<Vec<ConversionRecord>, LuhnError>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    convert_scheme_batch_until(registry, conversion, values, &Limit::default())
+        .map(BatchOutcome::into_inner)
+}
+
+/// Converts identifiers from one registered scheme to another, stopping early
+/// when `limit` is reached.
+///
+/// The same as [`convert_scheme_batch`], except that an interrupted run returns
+/// records for only the inputs handled before the interruption.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+/// use luhn_tools::{convert_scheme_batch_until, AlgorithmRegistry, Limit, SchemeConversion};
+///
+/// let registry = AlgorithmRegistry::new();
+/// let conversion = SchemeConversion::new("luhn", "verhoeff");
+/// let limit = Limit::timeout(Duration::from_secs(5));
+///
+/// let outcome = convert_scheme_batch_until(&registry, &conversion, ["79927398713"], &limit)
+///     .unwrap();
+/// assert!(outcome.is_complete());
+/// ```
+///
+/// # Errors
+/// Returns the same errors as [`convert_scheme_batch`].
+pub fn convert_scheme_batch_until<'a, I>(
+    registry: &AlgorithmRegistry,
+    conversion: &SchemeConversion,
+    values: I,
+    limit: &Limit,
+) -> Result<BatchOutcome<Vec<ConversionRecord>>, LuhnError>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let from = lookup(registry, &conversion.from)?;
+    let to = lookup(registry, &conversion.to)?;
+
+    let mut checker = limit.checker();
+    let mut records = Vec::new();
+    for (index, input) in values.into_iter().enumerate() {
+        if let Some(reason) = checker.poll() {
+            return Ok(BatchOutcome::new(records, index, Some(reason)));
+        }
+        records.push(ConversionRecord {
+            index,
+            input: input.to_string(),
+            result: convert_one(from, to, conversion, input),
+        });
+    }
+    Ok(BatchOutcome::Complete(records))
+}
+
+fn lookup<'r>(
+    registry: &'r AlgorithmRegistry,
+    name: &str,
+) -> Result<&'r (dyn CheckDigit + Send + Sync), LuhnError> {
+    registry.get(name).ok_or_else(|| {
+        LuhnError::InvalidConfiguration(format!("no algorithm registered as \"{}\"", name))
+    })
+}
+
+fn convert_one(
+    from: &dyn CheckDigit,
+    to: &dyn CheckDigit,
+    conversion: &SchemeConversion,
+    input: &str,
+) -> Result<Conversion, LuhnError> {
+    if !from.validate(input)? {
+        return Err(LuhnError::ParseError(format!(
+            "check character does not match {}",
+            conversion.from
+        )));
+    }
+    // `validate` succeeded, so the input has more characters than the check value
+    let split = input
+        .char_indices()
+        .rev()
+        .nth(from.check_len().saturating_sub(1))
+        .map_or(0, |(index, _)| index);
+    let (body, old_check) = input.split_at(split);
+    let payload = body
+        .strip_prefix(conversion.from_prefix.as_str())
+        .ok_or_else(|| {
+            LuhnError::ParseError(format!(
+                "missing the \"{}\" prefix of {}",
+                conversion.from_prefix, conversion.from
+            ))
+        })?;
+
+    let mut value = String::with_capacity(conversion.to_prefix.len() + payload.len() + 2);
+    value.push_str(&conversion.to_prefix);
+    value.push_str(payload);
+    let new_check = to.compute(&value)?;
+    value.push_str(&new_check);
+
+    Ok(Conversion {
+        payload: payload.to_string(),
+        old_check: old_check.to_string(),
+        new_check,
+        value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate, mod11_2, validate};
+
+    #[test]
+    fn test_mod11_to_luhn() {
+        let registry = AlgorithmRegistry::new();
+        let conversion = SchemeConversion {
+            from_prefix: "0000000".to_string(),
+            to_prefix: "9".to_string(),
+            ..SchemeConversion::new("mod11-2", "luhn")
+        };
+        let inputs = ["0000000218250097", "000000021694233X"];
+        let records = convert_scheme_batch(&registry, &conversion, inputs).unwrap();
+        assert_eq!(records.len(), 2);
+
+        let first = records[0].result.as_ref().unwrap();
+        assert_eq!(records[0].index, 0);
+        assert_eq!(records[0].input, "0000000218250097");
+        assert_eq!(first.value, generate("921825009", None).unwrap());
+
+        let second = records[1].result.as_ref().unwrap();
+        assert_eq!(second.payload, "21694233");
+        assert_eq!(second.old_check, "X");
+        assert_eq!(second.value, generate("921694233", None).unwrap());
+        assert!(second.value.ends_with(&second.new_check));
+        assert!(validate(&second.value).unwrap());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let registry = AlgorithmRegistry::new();
+        let there = SchemeConversion::new("luhn", "mod11-2");
+        let back = SchemeConversion::new("mod11-2", "luhn");
+        let converted = convert_scheme_batch(&registry, &there, ["79927398713"]).unwrap();
+        let value = &converted[0].result.as_ref().unwrap().value;
+        assert_eq!(value, &mod11_2::generate("7992739871").unwrap());
+
+        let restored = convert_scheme_batch(&registry, &back, [value.as_str()]).unwrap();
+        assert_eq!(restored[0].result.as_ref().unwrap().value, "79927398713");
+    }
+
+    #[test]
+    fn test_item_failures_do_not_stop_batch() {
+        let registry = AlgorithmRegistry::new();
+        let conversion = SchemeConversion {
+            from_prefix: "79".to_string(),
+            ..SchemeConversion::new("luhn", "verhoeff")
+        };
+        let records = convert_scheme_batch(
+            &registry,
+            &conversion,
+            ["79927398714", "0018", "12a", "", "79927398713"],
+        )
+        .unwrap();
+        assert_eq!(
+            records[0].result,
+            Err(LuhnError::ParseError(
+                "check character does not match luhn".to_string()
+            ))
+        );
+        assert_eq!(
+            records[1].result,
+            Err(LuhnError::ParseError(
+                "missing the \"79\" prefix of luhn".to_string()
+            ))
+        );
+        assert!(matches!(
+            records[2].result,
+            Err(LuhnError::NonNumeric { .. })
+        ));
+        assert_eq!(records[3].result, Err(LuhnError::EmptyString));
+        assert_eq!(
+            records[4].result.as_ref().unwrap().value,
+            registry
+                .get("verhoeff")
+                .unwrap()
+                .append("92739871")
+                .unwrap()
+        );
+        assert_eq!(records[4].index, 4);
+    }
+
+    #[test]
+    fn test_batch_until() {
+        use crate::{CancelToken, Interruption};
+
+        let registry = AlgorithmRegistry::new();
+        let conversion = SchemeConversion::new("luhn", "mod11-2");
+        let token = CancelToken::new();
+        let limit = Limit {
+            cancel: Some(token.clone()),
+            ..Limit::default()
+        };
+        let values = ["79927398713", "0018", "18"].into_iter().inspect(|value| {
+            if *value == "18" {
+                token.cancel();
+            }
+        });
+        let outcome = convert_scheme_batch_until(&registry, &conversion, values, &limit).unwrap();
+        assert_eq!(outcome.interruption(), Some(Interruption::Cancelled));
+        let records = outcome.into_inner();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].input, "0018");
+
+        // Configuration errors come before the limit is checked
+        assert!(convert_scheme_batch_until(
+            &registry,
+            &SchemeConversion::new("x", "luhn"),
+            [],
+            &limit
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_unknown_algorithm() {
+        let registry = AlgorithmRegistry::new();
+        assert_eq!(
+            convert_scheme_batch(&registry, &SchemeConversion::new("mod11", "luhn"), [])
+                .unwrap_err(),
+            LuhnError::InvalidConfiguration("no algorithm registered as \"mod11\"".to_string())
+        );
+        assert!(
+            convert_scheme_batch(&registry, &SchemeConversion::new("luhn", "crc32"), []).is_err()
+        );
+    }
+}
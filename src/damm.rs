@@ -0,0 +1,151 @@
+//! The Damm algorithm.
+//!
+//! Damm runs the digits through a quasigroup table instead of summing them. Like
+//! Luhn it appends a single decimal check digit, but it detects every single-digit
+//! error and every adjacent transposition, including the `09`/`90` swap that Luhn
+//! misses.
+//!
+//! # Examples
+//! ```
+//! use luhn_tools::damm;
+//!
+//! assert_eq!(damm::generate("572").unwrap(), "5724");
+//! assert!(damm::validate("5724").unwrap());
+//! assert!(!damm::validate("7524").unwrap());
+//! ```
+
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use crate::{handle_errors, CheckDigit, LuhnError};
+
+/// The weakly totally anti-symmetric quasigroup of order 10 from Damm's thesis.
+const TABLE: [[u8; 10]; 10] = [
+    [0, 3, 1, 7, 5, 9, 8, 6, 4, 2],
+    [7, 0, 9, 2, 1, 5, 4, 8, 6, 3],
+    [4, 2, 0, 6, 8, 7, 1, 3, 5, 9],
+    [1, 7, 5, 0, 9, 8, 3, 4, 2, 6],
+    [6, 1, 2, 3, 0, 4, 5, 9, 7, 8],
+    [3, 6, 7, 4, 2, 0, 9, 5, 8, 1],
+    [5, 8, 6, 9, 7, 2, 0, 1, 3, 4],
+    [8, 9, 4, 5, 3, 6, 2, 0, 1, 7],
+    [9, 4, 3, 8, 6, 1, 7, 2, 0, 5],
+    [2, 5, 8, 1, 4, 3, 6, 7, 9, 0],
+];
+
+/// The Damm algorithm as a [`CheckDigit`] implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Damm;
+
+impl CheckDigit for Damm {
+    fn compute(&self, payload: &str) -> Result<String, LuhnError> {
+        Ok(checksum(payload)?.to_string())
+    }
+
+    fn validate(&self, value: &str) -> Result<bool, LuhnError> {
+        validate(value)
+    }
+}
+
+/// Computes the Damm check digit for a payload.
+///
+/// # Errors
+/// Returns an error if the payload fails the usual input checks (empty, spaces,
+/// non-numeric, ...).
+pub fn checksum(payload: &str) -> Result<u8, LuhnError> {
+    handle_errors(payload)?;
+    Ok(interim(payload))
+}
+
+/// Appends the Damm check digit to a payload.
+///
+/// # Errors
+/// Returns the same errors as [`checksum`].
+pub fn generate(payload: &str) -> Result<String, LuhnError> {
+    Ok(format!("{}{}", payload, checksum(payload)?))
+}
+
+/// Validates a number whose last digit is its Damm check digit.
+///
+/// # Errors
+/// Returns the same errors as [`checksum`], plus `InvalidLength` if the input is
+/// only one character long.
+pub fn validate(value: &str) -> Result<bool, LuhnError> {
+    handle_errors(value)?;
+    if value.len() < 2 {
+        return Err(LuhnError::InvalidLength(
+            "string must be longer than 1 character".to_string(),
+        ));
+    }
+    Ok(interim(value) == 0)
+}
+
+/// Runs ASCII digits through the table. A valid number ends on 0.
+fn interim(digits: &str) -> u8 {
+    digits.bytes().fold(0, |state, byte| {
+        TABLE
+            .get(usize::from(state))
+            .and_then(|row| row.get(usize::from(byte.wrapping_sub(b'0'))))
+            .copied()
+            .unwrap_or(state)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_values() {
+        assert_eq!(checksum("572").unwrap(), 4);
+        assert_eq!(generate("12345").unwrap(), "123459");
+        assert!(validate("5724").unwrap());
+        assert!(validate("00").unwrap());
+        assert!(!validate("5727").unwrap());
+    }
+
+    #[test]
+    fn test_detects_what_luhn_misses() {
+        // 09 and 90 swapped: Luhn accepts both, Damm rejects the swap
+        let number = generate("1090").unwrap();
+        assert!(validate(&number).unwrap());
+        let swapped = format!("1900{}", &number[4..]);
+        assert!(!validate(&swapped).unwrap());
+    }
+
+    #[test]
+    fn test_detects_single_digit_errors() {
+        let number = generate("8675309").unwrap().into_bytes();
+        for i in 0..number.len() {
+            for shift in 1..10 {
+                let mut changed = number.clone();
+                changed[i] = b'0' + (changed[i] - b'0' + shift) % 10;
+                let changed = String::from_utf8(changed).unwrap();
+                assert!(!validate(&changed).unwrap(), "{changed}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_check_digit_trait() {
+        assert_eq!(Damm.compute("572").unwrap(), "4");
+        assert_eq!(Damm.append("572").unwrap(), "5724");
+        assert!(CheckDigit::validate(&Damm, "5724").unwrap());
+    }
+
+    #[test]
+    fn test_error_cases() {
+        assert_eq!(checksum("").unwrap_err(), LuhnError::EmptyString);
+        assert_eq!(
+            validate("7").unwrap_err(),
+            LuhnError::InvalidLength("string must be longer than 1 character".to_string())
+        );
+        assert_eq!(
+            validate("57a4").unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 2,
+                character: 'a'
+            }
+        );
+    }
+}
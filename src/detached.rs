@@ -0,0 +1,341 @@
+//! Check digits transmitted separately from their payload.
+//!
+//! Some partners send the check digit out-of-band, e.g. in a separate column or
+//! header. [`DetachedChecksum`] computes such a digit for a payload, round-trips it
+//! through its string form, and verifies it against the payload later.
+
+use core::str::FromStr;
+use std::fmt;
+
+use crate::{checksum_from_sum, luhn_sum, BatchOutcome, Limit, LuhnError};
+
+/// A Luhn check digit kept apart from its payload.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::DetachedChecksum;
+///
+/// let checksum = DetachedChecksum::compute("7992739871").unwrap();
+/// let column = checksum.to_string();
+/// assert_eq!(column, "3");
+///
+/// let received: DetachedChecksum = column.parse().unwrap();
+/// assert!(received.verify("7992739871").unwrap());
+/// assert!(!received.verify("7992739872").unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DetachedChecksum(u8);
+
+impl DetachedChecksum {
+    /// Computes the check digit for a payload.
+    ///
+    /// # Errors
+    /// Returns an error if the payload fails the usual input checks (empty, spaces,
+    /// non-numeric, ...).
+    pub fn compute(payload: &str) -> Result<Self, LuhnError> {
+        Ok(DetachedChecksum(checksum_from_sum(luhn_sum(
+            payload, true,
+        )?)))
+    }
+
+    /// Creates a detached checksum from a digit value.
+    ///
+    /// # Errors
+    /// Returns `ParseError` if `digit` is greater than 9.
+    pub fn from_digit(digit: u8) -> Result<Self, LuhnError> {
+        if digit > 9 {
+            return Err(LuhnError::ParseError(
+                "check digit must be between 0 and 9".to_string(),
+            ));
+        }
+        Ok(DetachedChecksum(digit))
+    }
+
+    /// Returns the check digit value (0–9).
+    pub fn digit(&self) -> u8 {
+        self.0
+    }
+
+    /// Verifies that this check digit belongs to `payload`.
+    ///
+    /// # Errors
+    /// Returns an error if the payload fails the usual input checks (empty, spaces,
+    /// non-numeric, ...).
+    pub fn verify(&self, payload: &str) -> Result<bool, LuhnError> {
+        Ok(DetachedChecksum::compute(payload)? == *self)
+    }
+}
+
+impl fmt::Display for DetachedChecksum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for DetachedChecksum {
+    type Err = LuhnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.as_bytes() {
+            [] => Err(LuhnError::EmptyString),
+            [digit] if digit.is_ascii_digit() => Ok(DetachedChecksum(digit - b'0')),
+            [byte] => Err(LuhnError::NonNumeric {
+                index: 0,
+                character: char::from(*byte),
+            }),
+            _ => Err(LuhnError::InvalidLength(
+                "check digit must be a single character".to_string(),
+            )),
+        }
+    }
+}
+
+/// Verifies a payload against a check digit received as a string.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::verify_detached;
+///
+/// assert!(verify_detached("7992739871", "3").unwrap());
+/// ```
+///
+/// # Errors
+/// Returns an error if the payload fails the usual input checks, or the check
+/// digit is not a single ASCII digit.
+pub fn verify_detached(payload: &str, check_digit: &str) -> Result<bool, LuhnError> {
+    check_digit.parse::<DetachedChecksum>()?.verify(payload)
+}
+
+/// Computes detached check digits for many payloads.
+///
+/// Each payload is handled independently; one malformed payload does not stop
+/// the batch.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::compute_detached_batch;
+///
+/// let results = compute_detached_batch(["7992739871", "1a"]);
+/// assert_eq!(results[0].as_ref().unwrap().digit(), 3);
+/// assert!(results[1].is_err());
+/// ```
+pub fn compute_detached_batch<'a, I>(payloads: I) -> Vec<Result<DetachedChecksum, LuhnError>>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    compute_detached_batch_until(payloads, &Limit::default()).into_inner()
+}
+
+/// Computes detached check digits for many payloads, stopping early when `limit`
+/// is reached.
+///
+/// The same as [`compute_detached_batch`], except that an interrupted run returns
+/// results for only the payloads handled before the interruption.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+/// use luhn_tools::{compute_detached_batch_until, Limit};
+///
+/// let outcome = compute_detached_batch_until(["7992739871"], &Limit::timeout(Duration::from_secs(5)));
+/// assert!(outcome.is_complete());
+/// assert_eq!(outcome.value()[0].as_ref().unwrap().digit(), 3);
+/// ```
+pub fn compute_detached_batch_until<'a, I>(
+    payloads: I,
+    limit: &Limit,
+) -> BatchOutcome<Vec<Result<DetachedChecksum, LuhnError>>>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    run_until(payloads, limit, DetachedChecksum::compute)
+}
+
+/// Verifies many `(payload, check digit)` pairs.
+///
+/// Each pair is handled independently; one malformed pair does not stop the batch.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::verify_detached_batch;
+///
+/// let results = verify_detached_batch([("7992739871", "3"), ("7992739871", "4")]);
+/// assert_eq!(results, vec![Ok(true), Ok(false)]);
+/// ```
+pub fn verify_detached_batch<'a, I>(pairs: I) -> Vec<Result<bool, LuhnError>>
+where
+    I: IntoIterator<Item = (&'a str, &'a str)>,
+{
+    verify_detached_batch_until(pairs, &Limit::default()).into_inner()
+}
+
+/// Verifies many `(payload, check digit)` pairs, stopping early when `limit` is
+/// reached.
+///
+/// The same as [`verify_detached_batch`], except that an interrupted run returns
+/// results for only the pairs handled before the interruption.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{verify_detached_batch_until, CancelToken, Interruption, Limit};
+///
+/// let token = CancelToken::new();
+/// token.cancel();
+/// let limit = Limit { cancel: Some(token), ..Limit::default() };
+///
+/// let outcome = verify_detached_batch_until([("7992739871", "3")], &limit);
+/// assert_eq!(outcome.interruption(), Some(Interruption::Cancelled));
+/// assert!(outcome.value().is_empty());
+/// ```
+pub fn verify_detached_batch_until<'a, I>(
+    pairs: I,
+    limit: &Limit,
+) -> BatchOutcome<Vec<Result<bool, LuhnError>>>
+where
+    I: IntoIterator<Item = (&'a str, &'a str)>,
+{
+    run_until(pairs, limit, |(payload, check_digit)| {
+        verify_detached(payload, check_digit)
+    })
+}
+
+/// Applies `f` to each item until `limit` is reached.
+fn run_until<I, T, F>(items: I, limit: &Limit, mut f: F) -> BatchOutcome<Vec<T>>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> T,
+{
+    let mut checker = limit.checker();
+    let mut results = Vec::new();
+    for item in items {
+        if let Some(reason) = checker.poll() {
+            let completed = results.len();
+            return BatchOutcome::new(results, completed, Some(reason));
+        }
+        results.push(f(item));
+    }
+    BatchOutcome::Complete(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate;
+
+    #[test]
+    fn test_matches_generate() {
+        for payload in ["1", "12", "123", "7992739871", "00123"] {
+            let checksum = DetachedChecksum::compute(payload).unwrap();
+            assert_eq!(
+                format!("{}{}", payload, checksum),
+                generate(payload, None).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for digit in 0..10 {
+            let checksum = DetachedChecksum::from_digit(digit).unwrap();
+            assert_eq!(
+                checksum.to_string().parse::<DetachedChecksum>(),
+                Ok(checksum)
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!("".parse::<DetachedChecksum>(), Err(LuhnError::EmptyString));
+        assert_eq!(
+            "x".parse::<DetachedChecksum>(),
+            Err(LuhnError::NonNumeric {
+                index: 0,
+                character: 'x'
+            })
+        );
+        assert_eq!(
+            "12".parse::<DetachedChecksum>(),
+            Err(LuhnError::InvalidLength(
+                "check digit must be a single character".to_string()
+            ))
+        );
+        assert_eq!(
+            DetachedChecksum::from_digit(10),
+            Err(LuhnError::ParseError(
+                "check digit must be between 0 and 9".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_verify() {
+        assert!(verify_detached("7992739871", "3").unwrap());
+        assert!(!verify_detached("7992739871", "0").unwrap());
+        assert_eq!(
+            verify_detached("", "3").unwrap_err(),
+            LuhnError::EmptyString
+        );
+        assert_eq!(
+            verify_detached("7992739871", "").unwrap_err(),
+            LuhnError::EmptyString
+        );
+    }
+
+    #[test]
+    fn test_batches() {
+        let computed = compute_detached_batch(vec!["1", "12", ""]);
+        assert_eq!(
+            computed,
+            vec![
+                Ok(DetachedChecksum(8)),
+                Ok(DetachedChecksum(5)),
+                Err(LuhnError::EmptyString)
+            ]
+        );
+
+        let verified = verify_detached_batch(vec![("1", "8"), ("12", "4"), ("12", "x")]);
+        assert_eq!(
+            verified,
+            vec![
+                Ok(true),
+                Ok(false),
+                Err(LuhnError::NonNumeric {
+                    index: 0,
+                    character: 'x'
+                })
+            ]
+        );
+    }
+
+    #[test]
+    fn test_batches_until() {
+        use crate::{CancelToken, Interruption};
+
+        let token = CancelToken::new();
+        let limit = Limit {
+            cancel: Some(token.clone()),
+            ..Limit::default()
+        };
+        let payloads = ["1", "12", "123"].into_iter().inspect(|payload| {
+            if *payload == "123" {
+                token.cancel();
+            }
+        });
+        assert_eq!(
+            compute_detached_batch_until(payloads, &limit),
+            BatchOutcome::Interrupted {
+                partial: vec![
+                    DetachedChecksum::from_digit(8),
+                    DetachedChecksum::from_digit(5)
+                ],
+                completed: 2,
+                reason: Interruption::Cancelled
+            }
+        );
+        assert_eq!(
+            verify_detached_batch_until([("7992739871", "3")], &Limit::default()),
+            BatchOutcome::Complete(vec![Ok(true)])
+        );
+    }
+}
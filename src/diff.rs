@@ -0,0 +1,244 @@
+//! Describing repairs as digit edits.
+//!
+//! The repair helpers ([`fix_check_digit`](crate::fix_check_digit),
+//! [`suggest_single_digit_fixes`](crate::suggest_single_digit_fixes),
+//! [`verify_or_fix`](crate::verify_or_fix), ...) return repaired numbers.
+//! [`digit_edits`] turns a repaired number back into the list of digits that
+//! changed, and [`render_diff`] shows them for CLI and CI output.
+
+use std::fmt::Write;
+
+use crate::{handle_errors, LuhnError};
+
+/// One digit changed by a repair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DigitEdit {
+    /// Zero-based position of the digit, from the left.
+    pub position: usize,
+    /// The digit in the original number.
+    pub old: u8,
+    /// The digit in the repaired number.
+    pub new: u8,
+}
+
+/// How [`render_diff`] shows a repair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DiffStyle {
+    /// Unified-diff style lines: `-` with the original number, `+` with the
+    /// repaired one, and a line of carets under the changed digits.
+    #[default]
+    Unified,
+    /// The repaired number on one line, with each changed digit shown as the old
+    /// digit struck through in red followed by the new digit in green.
+    Ansi,
+}
+
+/// Lists the digits that differ between a number and its repaired form.
+///
+/// # Arguments
+/// * `original` - The number before the repair
+/// * `repaired` - The same number after the repair, e.g. from
+///   [`fix_check_digit`](crate::fix_check_digit)
+///
+/// # Returns
+/// * `Ok(Vec<DigitEdit>)` - The changed digits, left to right; empty if none changed
+/// * `Err(LuhnError)` - Error if either number is malformed or they differ in length
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{digit_edits, fix_check_digit, DigitEdit};
+///
+/// let fixed = fix_check_digit("79927398710").unwrap();
+/// assert_eq!(
+///     digit_edits("79927398710", &fixed).unwrap(),
+///     [DigitEdit { position: 10, old: 0, new: 3 }]
+/// );
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * Either number fails the usual input checks (empty, spaces, non-numeric, ...)
+/// * The numbers differ in length
+pub fn digit_edits(original: &str, repaired: &str) -> Result<Vec<DigitEdit>, LuhnError> {
+    handle_errors(original)?;
+    handle_errors(repaired)?;
+    if original.len() != repaired.len() {
+        return Err(LuhnError::InvalidLength(format!(
+            "original and repaired numbers must have the same length ({} and {})",
+            original.len(),
+            repaired.len()
+        )));
+    }
+
+    Ok(original
+        .bytes()
+        .zip(repaired.bytes())
+        .enumerate()
+        .filter(|(_, (old, new))| old != new)
+        .map(|(position, (old, new))| DigitEdit {
+            position,
+            old: old - b'0',
+            new: new - b'0',
+        })
+        .collect())
+}
+
+/// Renders the digits that differ between a number and its repaired form.
+///
+/// # Arguments
+/// * `original` - The number before the repair
+/// * `repaired` - The same number after the repair
+/// * `style` - Plain unified-diff lines, or ANSI colors for a terminal
+///
+/// # Returns
+/// * `Ok(String)` - The rendered diff, without a trailing newline
+/// * `Err(LuhnError)` - Error if either number is malformed or they differ in length
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{render_diff, DiffStyle};
+///
+/// assert_eq!(
+///     render_diff("79927389713", "79927398713", DiffStyle::Unified).unwrap(),
+///     "-79927389713\n+79927398713\n       ^^"
+/// );
+/// assert_eq!(
+///     render_diff("18", "15", DiffStyle::Ansi).unwrap(),
+///     "1\x1b[9;31m8\x1b[0m\x1b[32m5\x1b[0m"
+/// );
+/// ```
+///
+/// # Errors
+/// Returns the same errors as [`digit_edits`].
+pub fn render_diff(original: &str, repaired: &str, style: DiffStyle) -> Result<String, LuhnError> {
+    let edits = digit_edits(original, repaired)?;
+    let mut out = String::new();
+    // Writing to a String cannot fail
+    let _ = match style {
+        DiffStyle::Unified => write_unified(&mut out, original, repaired, &edits),
+        DiffStyle::Ansi => write_ansi(&mut out, original, &edits),
+    };
+    Ok(out)
+}
+
+fn write_unified(
+    out: &mut String,
+    original: &str,
+    repaired: &str,
+    edits: &[DigitEdit],
+) -> std::fmt::Result {
+    write!(out, "-{}\n+{}\n", original, repaired)?;
+    // Carets line up under the digits, past the one-character line prefix
+    // Padded by hand: format widths cannot exceed u16::MAX
+    let mut column = 0;
+    for edit in edits {
+        out.extend(core::iter::repeat(' ').take(edit.position + 1 - column));
+        out.push('^');
+        column = edit.position + 2;
+    }
+    if edits.is_empty() {
+        out.pop();
+    }
+    Ok(())
+}
+
+fn write_ansi(out: &mut String, original: &str, edits: &[DigitEdit]) -> std::fmt::Result {
+    let mut edits = edits.iter().peekable();
+    for (position, digit) in original.chars().enumerate() {
+        match edits.next_if(|edit| edit.position == position) {
+            Some(edit) => write!(
+                out,
+                "\x1b[9;31m{}\x1b[0m\x1b[32m{}\x1b[0m",
+                edit.old, edit.new
+            )?,
+            None => out.push(digit),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{suggest_single_digit_fixes, verify_or_fix, LuhnOutcome};
+
+    #[test]
+    fn test_edits_of_repairs() {
+        for fix in suggest_single_digit_fixes("79927398714").unwrap() {
+            let edits = digit_edits("79927398714", &fix).unwrap();
+            assert_eq!(edits.len(), 1);
+        }
+        let LuhnOutcome::Fixed(fixed) = verify_or_fix("79927389713").unwrap() else {
+            panic!("expected a unique transposition fix");
+        };
+        assert_eq!(
+            digit_edits("79927389713", &fixed).unwrap(),
+            [
+                DigitEdit {
+                    position: 6,
+                    old: 8,
+                    new: 9
+                },
+                DigitEdit {
+                    position: 7,
+                    old: 9,
+                    new: 8
+                }
+            ]
+        );
+        assert!(digit_edits("18", "18").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_render_unified() {
+        assert_eq!(
+            render_diff("79927398710", "79927398713", DiffStyle::Unified).unwrap(),
+            "-79927398710\n+79927398713\n           ^"
+        );
+        assert_eq!(
+            render_diff("1000", "2002", DiffStyle::Unified).unwrap(),
+            "-1000\n+2002\n ^  ^"
+        );
+        // Nothing changed: no caret line
+        assert_eq!(
+            render_diff("18", "18", DiffStyle::default()).unwrap(),
+            "-18\n+18"
+        );
+
+        // Caret columns past the format width limit
+        let original = "0".repeat(70_000);
+        let mut repaired = original.clone();
+        repaired.replace_range(69_999.., "1");
+        let rendered = render_diff(&original, &repaired, DiffStyle::Unified).unwrap();
+        let carets = rendered.rsplit('\n').next().unwrap();
+        assert_eq!(carets.len(), 70_001);
+        assert!(carets.ends_with(" ^"));
+    }
+
+    #[test]
+    fn test_render_ansi() {
+        assert_eq!(
+            render_diff("1000", "2002", DiffStyle::Ansi).unwrap(),
+            "\x1b[9;31m1\x1b[0m\x1b[32m2\x1b[0m00\x1b[9;31m0\x1b[0m\x1b[32m2\x1b[0m"
+        );
+        assert_eq!(render_diff("18", "18", DiffStyle::Ansi).unwrap(), "18");
+    }
+
+    #[test]
+    fn test_error_cases() {
+        assert_eq!(
+            digit_edits("79927398710", "7992739871").unwrap_err(),
+            LuhnError::InvalidLength(
+                "original and repaired numbers must have the same length (11 and 10)".to_string()
+            )
+        );
+        assert_eq!(
+            render_diff("12a", "123", DiffStyle::Unified).unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 2,
+                character: 'a'
+            }
+        );
+        assert_eq!(digit_edits("", "").unwrap_err(), LuhnError::EmptyString);
+    }
+}
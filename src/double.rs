@@ -0,0 +1,100 @@
+//! Double check-digit scheme.
+//!
+//! Some legacy voucher systems append two check digits: Luhn is applied to the
+//! payload, the result is appended, and Luhn is applied again to the extended
+//! number. The second digit guards the first, catching some errors a single
+//! check digit lets through.
+
+use crate::{generate, handle_errors, validate, LuhnError};
+
+/// Appends two Luhn check digits to `value`.
+///
+/// # Arguments
+/// * `value` - The payload, without check digits
+///
+/// # Returns
+/// * `Ok(String)` - The payload followed by both check digits
+/// * `Err(LuhnError)` - Error if the input is invalid
+///
+/// # Examples
+/// ```
+/// use luhn_tools::generate_double;
+///
+/// assert_eq!(generate_double("7992739871").unwrap(), "799273987138");
+/// ```
+///
+/// # Errors
+/// Returns an error if the input fails the usual input checks (empty, spaces,
+/// non-numeric, ...).
+pub fn generate_double(value: &str) -> Result<String, LuhnError> {
+    generate(&generate(value, None)?, None)
+}
+
+/// Validates a number carrying two trailing Luhn check digits.
+///
+/// # Arguments
+/// * `value` - The full number, including both check digits
+///
+/// # Returns
+/// * `Ok(true)` - If both check digits are correct
+/// * `Ok(false)` - If either check digit is wrong
+/// * `Err(LuhnError)` - Error if the input is invalid
+///
+/// # Examples
+/// ```
+/// use luhn_tools::validate_double;
+///
+/// assert!(validate_double("799273987138").unwrap());
+/// assert!(!validate_double("799273987139").unwrap());
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * The input fails the usual input checks (empty, spaces, non-numeric, ...)
+/// * The input is shorter than three characters
+pub fn validate_double(value: &str) -> Result<bool, LuhnError> {
+    handle_errors(value)?;
+    if value.len() < 3 {
+        return Err(LuhnError::InvalidLength(
+            "string must be at least 3 characters".to_string(),
+        ));
+    }
+
+    let inner = value.get(..value.len() - 1).unwrap_or_default();
+    Ok(validate(inner)? && validate(value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for payload in ["0", "1", "7992739871", "79927398710000"] {
+            let number = generate_double(payload).unwrap();
+            assert_eq!(number.len(), payload.len() + 2);
+            assert!(validate_double(&number).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_single_check_digit_is_not_enough() {
+        // Valid under plain Luhn, but the last digit is not a second check digit.
+        assert!(validate("79927398713").unwrap());
+        assert!(!validate_double("79927398713").unwrap());
+        assert!(!validate_double("799273987030").unwrap());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(generate_double("").unwrap_err(), LuhnError::EmptyString);
+        assert_eq!(
+            validate_double("00").unwrap_err(),
+            LuhnError::InvalidLength("string must be at least 3 characters".to_string())
+        );
+        assert_eq!(
+            validate_double("12-3").unwrap_err(),
+            LuhnError::NegativeNumber
+        );
+    }
+}
@@ -0,0 +1,342 @@
+//! Bulk export of generated test data.
+//!
+//! Fixture files with millions of rows are easiest to produce by streaming straight
+//! to a file. [`export_test_data`] draws numbers from a [`Synthetic`] generator and
+//! writes them, with optional brand and masked columns, as CSV or NDJSON to any
+//! [`io::Write`].
+
+use std::io::{self, Write};
+
+use rand::Rng;
+
+use crate::card::mask_pan;
+use crate::limit::Checker;
+use crate::{BatchOutcome, Interruption, Limit, Synthetic};
+
+/// The output format of [`export_test_data`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ExportFormat {
+    /// Comma-separated values with a header row
+    #[default]
+    Csv,
+    /// One JSON object per line
+    Ndjson,
+}
+
+/// What [`export_test_data`] writes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExportSpec {
+    /// Number of rows to write
+    pub count: usize,
+    /// Where the numbers come from
+    pub source: Synthetic,
+    /// The output format
+    pub format: ExportFormat,
+    /// If true, adds a `brand` column with the matching [`Brand`](crate::Brand)
+    /// name, empty (or `null`) if none matches
+    pub brand_column: bool,
+    /// If true, adds a `masked` column with the number masked like a card number in
+    /// debug output: the first six and last four digits are kept, and numbers shorter
+    /// than 12 digits are masked in full
+    pub masked_column: bool,
+}
+
+impl ExportSpec {
+    /// Returns a spec for `count` uniformly random CSV rows of `len` digits, with
+    /// no extra columns.
+    pub fn new(count: usize, len: usize) -> Self {
+        ExportSpec {
+            count,
+            source: Synthetic::new().bin("", &[(len, 1)], 1),
+            format: ExportFormat::Csv,
+            brand_column: false,
+            masked_column: false,
+        }
+    }
+}
+
+/// Streams generated valid numbers to `writer` as described by `spec`.
+///
+/// Rows are written as they are generated, so memory use does not grow with
+/// `spec.count`. Wrap files in an [`io::BufWriter`] for throughput.
+///
+/// # Arguments
+/// * `writer` - Where to write the rows
+/// * `spec` - The number of rows, their source, format and columns
+///
+/// # Returns
+/// * `Ok(())` - If every row was written
+/// * `Err(io::Error)` - The first write error, or an `InvalidData` error wrapping
+///   the [`LuhnError`](crate::LuhnError) if the source cannot generate numbers
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{export_test_data, ExportFormat, ExportSpec};
+///
+/// let spec = ExportSpec {
+///     format: ExportFormat::Ndjson,
+///     masked_column: true,
+///     ..ExportSpec::new(2, 11)
+/// };
+/// let mut out = Vec::new();
+/// export_test_data(&mut out, &spec).unwrap();
+///
+/// let text = String::from_utf8(out).unwrap();
+/// assert_eq!(text.lines().count(), 2);
+/// assert!(text.starts_with("{\"number\":\""));
+/// assert!(text.contains("\"masked\":\"*******"));
+/// ```
+///
+/// # Errors
+/// Returns an error if writing fails or the source is misconfigured (see
+/// [`Synthetic::dataset`]).
+pub fn export_test_data<W: Write>(writer: W, spec: &ExportSpec) -> io::Result<()> {
+    export_test_data_with_rng(writer, spec, &mut rand::thread_rng())
+}
+
+/// Streams generated valid numbers to `writer`, drawing them from `rng`.
+///
+/// The same as [`export_test_data`], with caller-supplied randomness so that
+/// exports can be reproduced from a seed.
+///
+/// # Errors
+/// Returns the same errors as [`export_test_data`].
+pub fn export_test_data_with_rng<W: Write, R: Rng + ?Sized>(
+    writer: W,
+    spec: &ExportSpec,
+    rng: &mut R,
+) -> io::Result<()> {
+    export_rows(writer, spec, rng, &mut Limit::default().checker()).map(|_| ())
+}
+
+/// Streams generated valid numbers to `writer`, stopping early when `limit` is
+/// reached.
+///
+/// The same as [`export_test_data`], except that an interrupted run stops after
+/// the last complete row and reports how many rows were written.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{export_test_data_until, BatchOutcome, CancelToken, ExportSpec, Limit};
+///
+/// let token = CancelToken::new();
+/// token.cancel();
+/// let limit = Limit { cancel: Some(token), ..Limit::default() };
+///
+/// let mut out = Vec::new();
+/// let outcome = export_test_data_until(&mut out, &ExportSpec::new(1000, 16), &limit).unwrap();
+/// assert!(matches!(outcome, BatchOutcome::Interrupted { completed: 0, .. }));
+/// assert_eq!(out, b"number\n");
+/// ```
+///
+/// # Errors
+/// Returns the same errors as [`export_test_data`].
+pub fn export_test_data_until<W: Write>(
+    writer: W,
+    spec: &ExportSpec,
+    limit: &Limit,
+) -> io::Result<BatchOutcome<()>> {
+    let (completed, interruption) =
+        export_rows(writer, spec, &mut rand::thread_rng(), &mut limit.checker())?;
+    Ok(BatchOutcome::new((), completed, interruption))
+}
+
+/// Writes the header and rows of an export, polling `checker` before each row.
+/// Returns how many rows were written and why it stopped early, if it did.
+fn export_rows<W: Write, R: Rng + ?Sized>(
+    mut writer: W,
+    spec: &ExportSpec,
+    rng: &mut R,
+    checker: &mut Checker,
+) -> io::Result<(usize, Option<Interruption>)> {
+    // Checked before the header so a bad source leaves the output untouched
+    let sampler = spec.source.sampler()?;
+    if spec.format == ExportFormat::Csv {
+        writer.write_all(b"number")?;
+        if spec.brand_column {
+            writer.write_all(b",brand")?;
+        }
+        if spec.masked_column {
+            writer.write_all(b",masked")?;
+        }
+        writer.write_all(b"\n")?;
+    }
+
+    for row in 0..spec.count {
+        if let Some(reason) = checker.poll() {
+            writer.flush()?;
+            return Ok((row, Some(reason)));
+        }
+        let card = sampler.sample(rng)?;
+        let brand = card.brand.map(|brand| brand.name());
+        match spec.format {
+            ExportFormat::Csv => {
+                writer.write_all(card.number.as_bytes())?;
+                if spec.brand_column {
+                    write!(writer, ",{}", brand.unwrap_or(""))?;
+                }
+                if spec.masked_column {
+                    write!(writer, ",{}", mask_pan(&card.number))?;
+                }
+            }
+            ExportFormat::Ndjson => {
+                write!(writer, "{{\"number\":\"{}\"", card.number)?;
+                if spec.brand_column {
+                    match brand {
+                        Some(name) => write!(writer, ",\"brand\":\"{}\"", name)?,
+                        None => writer.write_all(b",\"brand\":null")?,
+                    }
+                }
+                if spec.masked_column {
+                    write!(writer, ",\"masked\":\"{}\"", mask_pan(&card.number))?;
+                }
+                writer.write_all(b"}")?;
+            }
+        }
+        writer.write_all(b"\n")?;
+    }
+
+    writer.flush()?;
+    Ok((spec.count, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{validate, LuhnError};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn export(spec: &ExportSpec) -> String {
+        let mut out = Vec::new();
+        export_test_data_with_rng(&mut out, spec, &mut StdRng::seed_from_u64(1)).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_csv() {
+        let spec = ExportSpec {
+            brand_column: true,
+            masked_column: true,
+            ..ExportSpec::new(100, 16)
+        };
+        let text = export(&spec);
+        let mut lines = text.lines();
+        assert_eq!(lines.next(), Some("number,brand,masked"));
+
+        let mut rows = 0;
+        for line in lines {
+            let fields: Vec<&str> = line.split(',').collect();
+            assert_eq!(fields.len(), 3);
+            assert!(validate(fields[0]).unwrap());
+            assert_eq!(fields[2], mask_pan(fields[0]));
+            rows += 1;
+        }
+        assert_eq!(rows, 100);
+
+        assert_eq!(export(&ExportSpec::new(0, 16)), "number\n");
+    }
+
+    #[test]
+    fn test_ndjson() {
+        let spec = ExportSpec {
+            source: Synthetic::new().bin("799273", &[(16, 1)], 1),
+            format: ExportFormat::Ndjson,
+            brand_column: true,
+            ..ExportSpec::new(3, 16)
+        };
+        let text = export(&spec);
+        assert_eq!(text.lines().count(), 3);
+        for line in text.lines() {
+            let number = &line[11..27];
+            assert_eq!(
+                line,
+                format!("{{\"number\":\"{}\",\"brand\":null}}", number)
+            );
+            assert!(number.starts_with("799273"));
+            assert!(validate(number).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let spec = ExportSpec::new(1, 1);
+        let mut buffer = Vec::new();
+        let error = export_test_data(&mut buffer, &spec).unwrap_err();
+        // Nothing, not even the header, is written for a bad source
+        assert!(buffer.is_empty());
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            error
+                .get_ref()
+                .and_then(|e| e.downcast_ref::<LuhnError>())
+                .unwrap(),
+            &LuhnError::InvalidLength("string must be greater than 1".to_string())
+        );
+
+        struct Failing;
+        impl Write for Failing {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::Other, "disk full"))
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+        let error = export_test_data(Failing, &ExportSpec::new(1, 16)).unwrap_err();
+        assert_eq!(error.to_string(), "disk full");
+    }
+
+    #[test]
+    fn test_until() {
+        use crate::CancelToken;
+
+        /// Cancels `token` once `rows` lines after the header have been written.
+        struct CancelAfter {
+            out: Vec<u8>,
+            rows: usize,
+            token: CancelToken,
+        }
+        impl Write for CancelAfter {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.out.extend_from_slice(buf);
+                if self.out.iter().filter(|&&b| b == b'\n').count() > self.rows {
+                    self.token.cancel();
+                }
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let token = CancelToken::new();
+        let limit = Limit {
+            cancel: Some(token.clone()),
+            ..Limit::default()
+        };
+        let mut writer = CancelAfter {
+            out: Vec::new(),
+            rows: 3,
+            token,
+        };
+        let outcome =
+            export_test_data_until(&mut writer, &ExportSpec::new(100, 16), &limit).unwrap();
+        assert_eq!(
+            outcome,
+            BatchOutcome::Interrupted {
+                partial: (),
+                completed: 3,
+                reason: Interruption::Cancelled
+            }
+        );
+        let text = String::from_utf8(writer.out).unwrap();
+        assert_eq!(text.lines().count(), 4);
+        assert!(text.lines().skip(1).all(|line| validate(line).unwrap()));
+
+        let mut out = Vec::new();
+        let outcome = export_test_data_until(&mut out, &ExportSpec::new(5, 16), &Limit::default());
+        assert_eq!(outcome.unwrap(), BatchOutcome::Complete(()));
+        assert_eq!(out.iter().filter(|&&b| b == b'\n').count(), 6);
+    }
+}
@@ -0,0 +1,217 @@
+//! Integers that remember their zero-padded width.
+//!
+//! An identifier such as `001230` stored as a `u64` loses its leading zeros, and
+//! with them its length. [`FixedWidth`] keeps the width in the type so the digits
+//! round-trip exactly, and check digits are computed over the padded form.
+
+use core::str::FromStr;
+use std::fmt::{self, Write};
+
+use crate::{payload_checksum, validate, LuhnError, Parity};
+
+/// A `u64` rendered as exactly `W` digits, with leading zeros.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::FixedWidth;
+///
+/// let id: FixedWidth<6> = "001230".parse().unwrap();
+/// assert_eq!(id.value(), 1230);
+/// assert_eq!(id.to_string(), "001230");
+/// assert!(id.is_valid());
+///
+/// let payload = FixedWidth::<5>::new(123).unwrap();
+/// assert_eq!(payload.with_check_digit(), "001230");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FixedWidth<const W: usize>(u64);
+
+impl<const W: usize> FixedWidth<W> {
+    /// Wraps a value, checking that it fits in `W` digits.
+    ///
+    /// # Errors
+    /// Returns `InvalidLength` if `W` is zero or the value has more than `W` digits.
+    pub fn new(value: u64) -> Result<Self, LuhnError> {
+        if W == 0 {
+            return Err(LuhnError::InvalidLength(
+                "width must be at least 1".to_string(),
+            ));
+        }
+        if digit_count(value) > W {
+            return Err(LuhnError::InvalidLength(format!(
+                "value must fit in {} digits",
+                W
+            )));
+        }
+        Ok(FixedWidth(value))
+    }
+
+    /// Returns the wrapped integer.
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns the width in digits.
+    pub const fn width(&self) -> usize {
+        W
+    }
+
+    /// Returns the Luhn check digit for the padded digits, treated as a payload.
+    ///
+    /// Like the other arithmetic on existing numbers, this applies no issuer policy:
+    /// with the `forbid-real-iins` feature it still answers for payloads in real
+    /// card issuer ranges.
+    pub fn check_digit(&self) -> u8 {
+        // The padded digits are never empty and always ASCII digits
+        payload_checksum(&self.to_string(), Parity::FromRight).unwrap_or(0)
+    }
+
+    /// Returns the padded digits with their Luhn check digit appended
+    /// (`W + 1` digits).
+    pub fn with_check_digit(&self) -> String {
+        let mut digits = self.to_string();
+        digits.push(char::from(b'0' + self.check_digit()));
+        digits
+    }
+
+    /// Returns true if the padded digits, read as a complete number with its check
+    /// digit last, are Luhn-valid.
+    pub fn is_valid(&self) -> bool {
+        validate(&self.to_string()).unwrap_or(false)
+    }
+}
+
+impl<const W: usize> fmt::Display for FixedWidth<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Padded by hand: a format width is limited to u16, and W is not
+        for _ in digit_count(self.0)..W {
+            f.write_char('0')?;
+        }
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const W: usize> FromStr for FixedWidth<W> {
+    type Err = LuhnError;
+
+    /// Parses exactly `W` ASCII digits, leading zeros included.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        crate::handle_errors(s)?;
+        if s.len() != W {
+            return Err(LuhnError::InvalidLength(format!(
+                "string must be exactly {} characters",
+                W
+            )));
+        }
+        let value = s
+            .parse()
+            .map_err(|_| LuhnError::ParseError("value does not fit in a u64".to_string()))?;
+        FixedWidth::new(value)
+    }
+}
+
+impl<const W: usize> TryFrom<u64> for FixedWidth<W> {
+    type Error = LuhnError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        FixedWidth::new(value)
+    }
+}
+
+impl<const W: usize> From<FixedWidth<W>> for u64 {
+    fn from(value: FixedWidth<W>) -> u64 {
+        value.0
+    }
+}
+
+/// Returns the number of decimal digits in `value` (1 for zero).
+fn digit_count(mut value: u64) -> usize {
+    let mut count = 1;
+    while value >= 10 {
+        value /= 10;
+        count += 1;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_keeps_leading_zeros() {
+        let id: FixedWidth<6> = "001230".parse().unwrap();
+        assert_eq!(id.value(), 1230);
+        assert_eq!(id.to_string(), "001230");
+        assert_eq!(id.width(), 6);
+        assert_eq!(u64::from(id), 1230);
+        assert_eq!(FixedWidth::<6>::try_from(1230).unwrap(), id);
+    }
+
+    #[test]
+    fn test_check_digits() {
+        let payload = FixedWidth::<5>::new(123).unwrap();
+        assert_eq!(payload.with_check_digit(), "001230");
+        assert_eq!(payload.check_digit(), 0);
+        assert!(FixedWidth::<6>::new(1230).unwrap().is_valid());
+        assert!(!FixedWidth::<6>::new(1231).unwrap().is_valid());
+        assert_eq!(FixedWidth::<1>::new(0).unwrap().with_check_digit(), "00");
+    }
+
+    #[test]
+    fn test_real_issuer_ranges() {
+        // No issuer policy applies, with or without forbid-real-iins
+        let visa = FixedWidth::<15>::new(411111111111111).unwrap();
+        assert_eq!(visa.check_digit(), 1);
+        assert_eq!(visa.with_check_digit(), "4111111111111111");
+    }
+
+    #[test]
+    fn test_wide_display() {
+        let wide = FixedWidth::<70_000>::new(42).unwrap();
+        let digits = wide.to_string();
+        assert_eq!(digits.len(), 70_000);
+        assert!(digits.ends_with("00042"));
+        assert_eq!(wide.with_check_digit().len(), 70_001);
+        assert_eq!(
+            wide.check_digit(),
+            FixedWidth::<2>::new(42).unwrap().check_digit()
+        );
+    }
+
+    #[test]
+    fn test_max_value() {
+        let max = FixedWidth::<20>::new(u64::MAX).unwrap();
+        assert_eq!(max.to_string(), u64::MAX.to_string());
+        assert_eq!(max.with_check_digit().len(), 21);
+    }
+
+    #[test]
+    fn test_error_cases() {
+        assert_eq!(
+            FixedWidth::<3>::new(1000).unwrap_err(),
+            LuhnError::InvalidLength("value must fit in 3 digits".to_string())
+        );
+        assert_eq!(
+            FixedWidth::<0>::new(0).unwrap_err(),
+            LuhnError::InvalidLength("width must be at least 1".to_string())
+        );
+        assert_eq!(
+            "1230".parse::<FixedWidth<6>>().unwrap_err(),
+            LuhnError::InvalidLength("string must be exactly 6 characters".to_string())
+        );
+        assert_eq!(
+            "12a0".parse::<FixedWidth<4>>().unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 2,
+                character: 'a'
+            }
+        );
+        assert_eq!(
+            "99999999999999999999"
+                .parse::<FixedWidth<20>>()
+                .unwrap_err(),
+            LuhnError::ParseError("value does not fit in a u64".to_string())
+        );
+    }
+}
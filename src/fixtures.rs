@@ -0,0 +1,214 @@
+//! Deterministic test fixtures generated at compile time.
+//!
+//! Test suites often want a handful of valid numbers without pulling in `rand` or
+//! paying for generation at runtime. [`Fixtures`] is built by a `const fn` from a seed
+//! and a spec, and the [`fixtures!`](crate::fixtures!) macro forces that evaluation
+//! to happen at compile time, so test binaries embed the numbers as static data.
+
+/// A fixed set of `N` valid Luhn numbers, each `L` digits long.
+///
+/// Numbers are derived from the seed with a small deterministic generator, so the
+/// same seed and spec always yield the same numbers. Duplicates are possible for
+/// short lengths.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{fixtures, Fixtures};
+///
+/// static CARDS: Fixtures<8, 16> = fixtures!(seed = 42, count = 8, length = 16, prefix = "4");
+///
+/// assert_eq!(CARDS.len(), 8);
+/// for card in CARDS.iter() {
+///     assert!(card.starts_with('4'));
+///     # #[cfg(feature = "std")]
+///     assert!(luhn_tools::validate(card).unwrap());
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fixtures<const N: usize, const L: usize> {
+    numbers: [[u8; L]; N],
+}
+
+impl<const N: usize, const L: usize> Fixtures<N, L> {
+    /// Generates the fixtures at compile time (or at runtime, if called outside a
+    /// const context).
+    ///
+    /// # Returns
+    /// * `Some(Fixtures)` - `N` valid numbers of `L` digits beginning with `prefix`
+    /// * `None` - If `L` is less than 2, or `prefix` is not ASCII digits or leaves
+    ///   no room for the check digit
+    pub const fn generate(seed: u64, prefix: &str) -> Option<Self> {
+        let prefix = prefix.as_bytes();
+        if L < 2 || prefix.len() >= L {
+            return None;
+        }
+        let mut i = 0;
+        while i < prefix.len() {
+            if !prefix[i].is_ascii_digit() {
+                return None;
+            }
+            i += 1;
+        }
+
+        let mut numbers = [[b'0'; L]; N];
+        let mut state = seed;
+        let mut n = 0;
+        while n < N {
+            let mut j = 0;
+            while j < L - 1 {
+                numbers[n][j] = if j < prefix.len() {
+                    prefix[j]
+                } else {
+                    let (next, output) = splitmix64(state);
+                    state = next;
+                    b'0' + (output % 10) as u8
+                };
+                j += 1;
+            }
+            numbers[n][L - 1] = check_digit(&numbers[n]);
+            n += 1;
+        }
+
+        Some(Fixtures { numbers })
+    }
+
+    /// Returns the number at `index`.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.numbers
+            .get(index)
+            .and_then(|n| core::str::from_utf8(n).ok())
+    }
+
+    /// Iterates over the numbers in order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.numbers
+            .iter()
+            .filter_map(|n| core::str::from_utf8(n).ok())
+    }
+
+    /// Returns the number of fixtures, `N`.
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns true if `N` is zero.
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Returns the raw ASCII digits.
+    pub const fn as_bytes(&self) -> &[[u8; L]; N] {
+        &self.numbers
+    }
+}
+
+/// Expands to a [`Fixtures`] value computed at compile time.
+///
+/// Takes `seed`, `count` and `length` (all constants) and an optional `prefix`.
+/// An invalid spec (length below 2, or a prefix that is not digits or is too
+/// long) is a compile error.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::fixtures;
+///
+/// let ids = fixtures!(seed = 7, count = 3, length = 11);
+/// # #[cfg(feature = "std")]
+/// assert!(ids.iter().all(|id| luhn_tools::validate(id).unwrap()));
+/// assert_eq!(ids, fixtures!(seed = 7, count = 3, length = 11));
+/// ```
+#[macro_export]
+macro_rules! fixtures {
+    (seed = $seed:expr, count = $count:expr, length = $length:expr $(, prefix = $prefix:expr)? $(,)?) => {{
+        const FIXTURES: $crate::Fixtures<{ $count }, { $length }> =
+            match $crate::Fixtures::generate($seed, $crate::fixtures!(@prefix $($prefix)?)) {
+                ::core::option::Option::Some(fixtures) => fixtures,
+                ::core::option::Option::None => {
+                    ::core::panic!("invalid fixtures spec: check the length and prefix")
+                }
+            };
+        FIXTURES
+    }};
+    (@prefix) => {
+        ""
+    };
+    (@prefix $prefix:expr) => {
+        $prefix
+    };
+}
+
+/// One step of the SplitMix64 generator: returns the next state and its output.
+pub(crate) const fn splitmix64(state: u64) -> (u64, u64) {
+    let state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    (state, z ^ (z >> 31))
+}
+
+/// Computes the Luhn check digit for all but the last byte of `digits`.
+const fn check_digit(digits: &[u8]) -> u8 {
+    let mut sum = 0u32;
+    let mut i = digits.len() - 1;
+    let mut double = true;
+    while i > 0 {
+        i -= 1;
+        let mut d = (digits[i] - b'0') as u32;
+        if double {
+            d *= 2;
+            if d > 9 {
+                d -= 9;
+            }
+        }
+        sum += d;
+        double = !double;
+    }
+    b'0' + ((10 - sum % 10) % 10) as u8
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::validate;
+
+    static NUMBERS: Fixtures<20, 16> =
+        fixtures!(seed = 1, count = 20, length = 16, prefix = "799273");
+
+    #[test]
+    fn test_fixtures_are_valid() {
+        assert_eq!(NUMBERS.len(), 20);
+        assert_eq!(NUMBERS.iter().count(), 20);
+        for number in NUMBERS.iter() {
+            assert_eq!(number.len(), 16);
+            assert!(number.starts_with("799273"));
+            assert!(validate(number).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let again = fixtures!(seed = 1, count = 20, length = 16, prefix = "799273");
+        assert_eq!(NUMBERS, again);
+        let other = fixtures!(seed = 2, count = 20, length = 16, prefix = "799273");
+        assert_ne!(NUMBERS, other);
+        assert_eq!(Fixtures::<20, 16>::generate(1, "799273").unwrap(), NUMBERS);
+    }
+
+    #[test]
+    fn test_minimal_spec() {
+        let pairs = fixtures!(seed = 0, count = 5, length = 2);
+        assert!(pairs.iter().all(|n| validate(n).unwrap()));
+        assert_eq!(pairs.get(5), None);
+        let whole = fixtures!(seed = 0, count = 1, length = 11, prefix = "7992739871");
+        assert_eq!(whole.get(0), Some("79927398713"));
+        let none = fixtures!(seed = 0, count = 0, length = 4);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_spec() {
+        assert_eq!(Fixtures::<1, 1>::generate(0, ""), None);
+        assert_eq!(Fixtures::<1, 4>::generate(0, "1234"), None);
+        assert_eq!(Fixtures::<1, 4>::generate(0, "1a"), None);
+    }
+}
@@ -0,0 +1,201 @@
+//! Structured fuzzing inputs built with [`arbitrary`].
+//!
+//! Uniformly random bytes almost never form a valid Luhn number, so a fuzzer fed
+//! plain strings spends its time on the first input check. The [`Arbitrary`]
+//! implementations here steer it instead: [`LuhnNumber`] is always valid, and
+//! [`RawInput`] mixes valid numbers with near misses (one digit changed, two
+//! digits swapped, stray separators or whitespace) and free-form text.
+
+use arbitrary::{Arbitrary, Unstructured};
+
+use crate::{payload_checksum, GenerateOptions, LuhnNumber, Parity};
+
+/// Longest payload generated, so inputs stay cheap to process.
+const MAX_PAYLOAD_LEN: usize = 32;
+
+/// Raw, unvalidated input for fuzzing code that parses Luhn numbers.
+///
+/// # Examples
+/// ```
+/// use arbitrary::{Arbitrary, Unstructured};
+/// use luhn_tools::{validate_with, RawInput};
+///
+/// let mut u = Unstructured::new(&[7, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+/// let input = RawInput::arbitrary(&mut u).unwrap();
+/// // Whatever the input, validation reports a result instead of panicking
+/// let _ = validate_with(input.as_str(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RawInput(String);
+
+impl RawInput {
+    /// Returns the input.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Unwraps the input into its string.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl AsRef<str> for RawInput {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<'a> Arbitrary<'a> for RawInput {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut digits = valid_digits(u)?.into_bytes();
+        let len = digits.len();
+
+        match u.int_in_range(0..=5)? {
+            0 => {}
+            1 => {
+                // One digit changed: the error Luhn is designed to catch
+                let i = u.choose_index(len)?;
+                let shift = u.int_in_range(1..=9)?;
+                if let Some(digit) = digits.get_mut(i) {
+                    *digit = b'0' + (*digit - b'0' + shift) % 10;
+                }
+            }
+            2 => {
+                // Two neighbouring digits swapped
+                let i = u.choose_index(len - 1)?;
+                digits.swap(i, i + 1);
+            }
+            3 => {
+                let separator = *u.choose(b" -_.")?;
+                let i = u.choose_index(len + 1)?;
+                digits.insert(i, separator);
+            }
+            4 => {
+                digits.insert(0, b' ');
+                digits.push(b'\n');
+            }
+            _ => return Ok(RawInput(u.arbitrary()?)),
+        }
+
+        let value = String::from_utf8(digits).map_err(|_| arbitrary::Error::IncorrectFormat)?;
+        Ok(RawInput(value))
+    }
+}
+
+impl<'a> Arbitrary<'a> for LuhnNumber {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        LuhnNumber::new(valid_digits(u)?).map_err(|_| arbitrary::Error::IncorrectFormat)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Parity {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(if u.arbitrary()? {
+            Parity::FromLeft
+        } else {
+            Parity::FromRight
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for GenerateOptions {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(GenerateOptions {
+            checksum_only: u.arbitrary()?,
+            allow_underscores: u.arbitrary()?,
+            reject_degenerate: u.arbitrary()?,
+            group_size: optional(u, 0..=8)?,
+            group_separator: u.arbitrary()?,
+            pad_to: optional(u, 0..=MAX_PAYLOAD_LEN + 1)?,
+            parity: u.arbitrary()?,
+        })
+    }
+}
+
+/// Draws a valid number of 2 to `MAX_PAYLOAD_LEN + 1` digits.
+fn valid_digits(u: &mut Unstructured) -> arbitrary::Result<String> {
+    let len = u.int_in_range(1..=MAX_PAYLOAD_LEN)?;
+    let mut digits = String::with_capacity(len + 1);
+    for _ in 0..len {
+        digits.push(char::from(b'0' + u.int_in_range(0..=9)?));
+    }
+    let check = payload_checksum(&digits, Parity::FromRight)
+        .map_err(|_| arbitrary::Error::IncorrectFormat)?;
+    digits.push(char::from(b'0' + check));
+    Ok(digits)
+}
+
+/// Draws an optional size from `range`, so that huge values cannot exhaust memory.
+fn optional(
+    u: &mut Unstructured,
+    range: core::ops::RangeInclusive<usize>,
+) -> arbitrary::Result<Option<usize>> {
+    Ok(if u.arbitrary()? {
+        Some(u.int_in_range(range)?)
+    } else {
+        None
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate, validate, validate_with};
+
+    /// Deterministic pseudo-random bytes for driving `Unstructured`.
+    fn bytes(seed: u64) -> Vec<u8> {
+        let mut state = seed;
+        (0..256)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                (state >> 56) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_luhn_number_always_valid() {
+        for seed in 0..500 {
+            let data = bytes(seed);
+            let number = LuhnNumber::arbitrary(&mut Unstructured::new(&data)).unwrap();
+            assert!(validate(number.as_str()).unwrap());
+            assert!(number.as_str().len() <= MAX_PAYLOAD_LEN + 1);
+        }
+    }
+
+    #[test]
+    fn test_raw_input_mixes_valid_and_invalid() {
+        let (mut valid, mut invalid) = (0, 0);
+        for seed in 0..500 {
+            let data = bytes(seed);
+            let input = RawInput::arbitrary(&mut Unstructured::new(&data)).unwrap();
+            match validate_with(input.as_str(), None) {
+                Ok(true) => valid += 1,
+                _ => invalid += 1,
+            }
+        }
+        assert!(valid > 50, "{valid} valid");
+        assert!(invalid > 50, "{invalid} invalid");
+    }
+
+    #[test]
+    fn test_generate_options_do_not_panic() {
+        for seed in 0..500 {
+            let data = bytes(seed);
+            let mut u = Unstructured::new(&data);
+            let options = GenerateOptions::arbitrary(&mut u).unwrap();
+            let input = RawInput::arbitrary(&mut u).unwrap();
+            let _ = generate(input.as_str(), Some(options));
+        }
+    }
+
+    #[test]
+    fn test_empty_data() {
+        let mut u = Unstructured::new(&[]);
+        assert!(validate(LuhnNumber::arbitrary(&mut u).unwrap().as_str()).unwrap());
+        assert!(RawInput::arbitrary(&mut u).is_ok());
+    }
+}
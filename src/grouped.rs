@@ -0,0 +1,138 @@
+//! Fixed-size digit grouping for display.
+//!
+//! Stored identifiers are canonical digits; UIs usually show them in blocks, e.g.
+//! `7992 7398 713`. [`format_grouped`] does the chunking so frontends do not have to,
+//! using the same logic as grouped output from [`generate`](crate::generate).
+
+use core::fmt;
+
+use crate::{strict_validate, LuhnError};
+
+/// Formats `value` in groups of `group_size` characters separated by `separator`.
+///
+/// Groups are counted from the left, so only the last group can be short. A
+/// `group_size` of zero returns the value unchanged. The input is not checked; see
+/// [`format_grouped_checked`] to validate first.
+///
+/// # Arguments
+/// * `value` - The canonical digits to display
+/// * `group_size` - Number of digits per group
+/// * `separator` - The character placed between groups
+///
+/// # Returns
+/// * `String` - The grouped value
+///
+/// # Examples
+/// ```
+/// use luhn_tools::format_grouped;
+///
+/// assert_eq!(format_grouped("79927398713", 4, ' '), "7992 7398 713");
+/// assert_eq!(format_grouped("4111111111111111", 4, '-'), "4111-1111-1111-1111");
+/// ```
+pub fn format_grouped(value: &str, group_size: usize, separator: char) -> String {
+    let mut result = String::with_capacity(value.len() + value.len() / group_size.max(1));
+    // Writing to a String cannot fail.
+    let _ = write_grouped(&mut result, value.chars(), group_size, separator);
+    result
+}
+
+/// Validates `value` as a Luhn number, then formats it like [`format_grouped`].
+///
+/// # Arguments
+/// * `value` - The canonical digits to display, including the check digit
+/// * `group_size` - Number of digits per group
+/// * `separator` - The character placed between groups
+///
+/// # Returns
+/// * `Ok(String)` - The grouped value, if it is a valid Luhn number
+/// * `Err(LuhnError)` - Error if the input is malformed or its check digit is wrong
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{format_grouped_checked, LuhnError};
+///
+/// assert_eq!(format_grouped_checked("79927398713", 4, ' ').unwrap(), "7992 7398 713");
+/// assert_eq!(
+///     format_grouped_checked("79927398714", 4, ' ').unwrap_err(),
+///     LuhnError::InvalidChecksum { expected: 3, found: 4 }
+/// );
+/// ```
+///
+/// # Errors
+/// Returns the same errors as [`strict_validate`].
+pub fn format_grouped_checked(
+    value: &str,
+    group_size: usize,
+    separator: char,
+) -> Result<String, LuhnError> {
+    strict_validate(value)?;
+    Ok(format_grouped(value, group_size, separator))
+}
+
+/// Writes `chars` to `out`, inserting `separator` before every `group_size`-th
+/// character. A `group_size` of zero writes the characters ungrouped.
+pub(crate) fn write_grouped<W, I>(
+    out: &mut W,
+    chars: I,
+    group_size: usize,
+    separator: char,
+) -> fmt::Result
+where
+    W: fmt::Write,
+    I: Iterator<Item = char>,
+{
+    for (index, c) in chars.enumerate() {
+        if group_size > 0 && index > 0 && index % group_size == 0 {
+            out.write_char(separator)?;
+        }
+        out.write_char(c)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate, GenerateOptions};
+
+    #[test]
+    fn test_format_grouped() {
+        assert_eq!(format_grouped("12345678", 4, ' '), "1234 5678");
+        assert_eq!(format_grouped("123", 4, ' '), "123");
+        assert_eq!(format_grouped("123", 1, '.'), "1.2.3");
+        assert_eq!(format_grouped("12345", 0, ' '), "12345");
+        assert_eq!(format_grouped("", 4, ' '), "");
+    }
+
+    #[test]
+    fn test_matches_grouped_generate() {
+        let options = Some(GenerateOptions {
+            group_size: Some(3),
+            group_separator: '-',
+            ..Default::default()
+        });
+        assert_eq!(
+            format_grouped("79927398713", 3, '-'),
+            generate("7992739871", options).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_format_grouped_checked() {
+        assert_eq!(
+            format_grouped_checked("79927398713", 3, ' ').unwrap(),
+            "799 273 987 13"
+        );
+        assert_eq!(
+            format_grouped_checked("7992 7398 713", 4, ' ').unwrap_err(),
+            LuhnError::ContainsSpaces {
+                index: 4,
+                character: ' '
+            }
+        );
+        assert_eq!(
+            format_grouped_checked("", 4, ' ').unwrap_err(),
+            LuhnError::EmptyString
+        );
+    }
+}
@@ -0,0 +1,134 @@
+//! Luhn over hexadecimal strings.
+//!
+//! MEIDs and some hardware serial numbers carry a base-16 Luhn check character.
+//! This is Luhn mod N with N = 16, run on the same engine as
+//! [`generate_mod_n`](crate::generate_mod_n), but it accepts either letter case as
+//! hex strings usually do.
+
+use alloc::format;
+use alloc::string::String;
+
+use crate::mod_n::{check_character, check_symbols, validate_in};
+use crate::LuhnError;
+
+/// The alphabet the engine runs on; input is upper-cased first.
+const HEX: &str = "0123456789ABCDEF";
+
+/// Hex digits accepted in input.
+const HEX_EITHER_CASE: &str = "0123456789ABCDEFabcdef";
+
+/// Appends a base-16 Luhn check character to a hex string.
+///
+/// The check character is lower case if the payload contains lower case letters,
+/// and upper case otherwise.
+///
+/// # Arguments
+/// * `value` - The payload, hex digits of either case
+///
+/// # Returns
+/// * `Ok(String)` - The payload followed by its check character
+/// * `Err(LuhnError)` - If the input is invalid
+///
+/// # Errors
+/// * `EmptyString` - If `value` is empty
+/// * `ContainsSpaces` - If `value` contains whitespace
+/// * `NonNumeric` - If `value` contains a character that is not a hex digit
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{generate_hex, validate_hex};
+///
+/// // MEID AF 01 23 45 0A BC DE has check character C
+/// assert_eq!(generate_hex("AF0123450ABCDE").unwrap(), "AF0123450ABCDEC");
+/// assert_eq!(generate_hex("af0123450abcde").unwrap(), "af0123450abcdec");
+/// assert!(validate_hex("AF0123450ABCDEC").unwrap());
+/// ```
+pub fn generate_hex(value: &str) -> Result<String, LuhnError> {
+    check_symbols(value, HEX_EITHER_CASE)?;
+    let check = check_character(&value.to_ascii_uppercase(), HEX)?;
+    let check = if value.bytes().any(|b| b.is_ascii_lowercase()) {
+        check.to_ascii_lowercase()
+    } else {
+        check
+    };
+    Ok(format!("{}{}", value, check))
+}
+
+/// Validates a hex string whose last character is its base-16 Luhn check
+/// character. Letter case is ignored.
+///
+/// # Arguments
+/// * `value` - The value to validate, check character included
+///
+/// # Returns
+/// * `Ok(true)` - If the check character is correct
+/// * `Ok(false)` - If it is not
+/// * `Err(LuhnError)` - If the input is invalid
+///
+/// # Errors
+/// The same errors as [`generate_hex`], plus `InvalidLength` if `value` is only
+/// one character long.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::validate_hex;
+///
+/// assert!(validate_hex("AF0123450ABCDEC").unwrap());
+/// assert!(validate_hex("af0123450abcdec").unwrap());
+/// assert!(!validate_hex("AF0123450ABCDED").unwrap());
+/// assert!(validate_hex("AF0123450ABCDEG").is_err());
+/// ```
+pub fn validate_hex(value: &str) -> Result<bool, LuhnError> {
+    check_symbols(value, HEX_EITHER_CASE)?;
+    validate_in(&value.to_ascii_uppercase(), HEX)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{generate_mod_n, validate_mod_n};
+
+    #[test]
+    fn test_matches_mod_n() {
+        for payload in ["0", "F", "AF0123450ABCDE", "DEADBEEF", "0123456789ABCDEF"] {
+            let value = generate_hex(payload).unwrap();
+            assert_eq!(value, generate_mod_n(payload, 16).unwrap());
+            assert!(validate_mod_n(&value, 16).unwrap());
+            assert!(validate_hex(&value).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_case() {
+        assert_eq!(generate_hex("deadbeef").unwrap(), "deadbeefc");
+        assert_eq!(generate_hex("DEADBEEF").unwrap(), "DEADBEEFC");
+        // Without letters in the payload, a letter check character is upper case
+        assert_eq!(generate_hex("12").unwrap(), "12B");
+        assert!(validate_hex("12b").unwrap());
+        assert!(validate_hex("DeadBeefc").unwrap());
+    }
+
+    #[test]
+    fn test_error_cases() {
+        assert_eq!(generate_hex("").unwrap_err(), LuhnError::EmptyString);
+        assert_eq!(
+            validate_hex("A").unwrap_err(),
+            LuhnError::InvalidLength("string must be longer than 1 character".to_string())
+        );
+        // The reported character is the one in the input, not an upper-cased copy
+        assert_eq!(
+            generate_hex("abcg").unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 3,
+                character: 'g'
+            }
+        );
+        assert_eq!(
+            validate_hex("AB CD").unwrap_err(),
+            LuhnError::ContainsSpaces {
+                index: 2,
+                character: ' '
+            }
+        );
+    }
+}
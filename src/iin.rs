@@ -0,0 +1,156 @@
+//! Issuer identification number (IIN) ranges of the major card networks.
+//!
+//! Used by the `forbid-real-iins` safety interlock, and by random generation with
+//! [`RandomOptions::avoid_real_iins`](crate::RandomOptions::avoid_real_iins), to keep
+//! generated values out of ranges allocated to real card issuers.
+
+/// Prefix ranges allocated to card networks, as `(low, high)` pairs of equal width.
+///
+/// A number falls in a range if its leading digits, read as an integer of the same
+/// width, lie between `low` and `high` inclusive.
+const ISSUER_RANGES: &[(&str, &str)] = &[
+    ("1", "1"),       // UATP
+    ("2200", "2204"), // Mir
+    ("2221", "2720"), // Mastercard 2-series
+    ("300", "305"),   // Diners Club
+    ("34", "34"),     // American Express
+    ("3528", "3589"), // JCB
+    ("36", "36"),     // Diners Club
+    ("37", "37"),     // American Express
+    ("38", "39"),     // Diners Club
+    ("4", "4"),       // Visa
+    ("50", "50"),     // Maestro, Verve
+    ("51", "55"),     // Mastercard
+    ("56", "58"),     // Maestro
+    ("60", "67"),     // Discover, RuPay, UnionPay, Maestro, Troy
+    ("81", "82"),     // RuPay
+    ("9792", "9792"), // Troy
+];
+
+/// Lengths of primary account numbers issued on the ranges above.
+const CARD_LENGTHS: core::ops::RangeInclusive<usize> = 12..=19;
+
+/// Returns true if `value` has a card-number length and begins with a prefix
+/// allocated to a real card network.
+pub(crate) fn is_real_iin(value: &str) -> bool {
+    if !CARD_LENGTHS.contains(&value.len()) {
+        return false;
+    }
+
+    ISSUER_RANGES.iter().any(|(low, high)| {
+        value
+            .get(..low.len())
+            .map_or(false, |prefix| *low <= prefix && prefix <= *high)
+    })
+}
+
+/// Returns true if every number of `len` digits made of `prefix`, free digits and
+/// `suffix` (followed by a check digit) has a real issuer prefix, so that no random
+/// completion could ever be accepted. The first free digit is drawn from `leading`
+/// and the others from `rest`.
+#[cfg(all(feature = "random", feature = "std"))]
+pub(crate) fn always_real(
+    prefix: &str,
+    suffix: &str,
+    len: usize,
+    leading: &[u8],
+    rest: &[u8],
+) -> bool {
+    if !CARD_LENGTHS.contains(&len) {
+        return false;
+    }
+
+    // The widest range prefix has four digits; later digits never matter.
+    const WIDTH: usize = 4;
+    let free = len.saturating_sub(1 + prefix.len() + suffix.len());
+    // Each leading position is either a fixed digit or the set it is drawn from
+    let lead: Vec<Result<u8, &[u8]>> = prefix
+        .bytes()
+        .map(Ok)
+        .chain((0..free).map(|i| Err(if i == 0 { leading } else { rest })))
+        .chain(suffix.bytes().map(Ok))
+        .take(WIDTH)
+        .collect();
+    let combinations = lead
+        .iter()
+        .map(|position| position.map_or_else(|set| set.len(), |_| 1))
+        .product::<usize>();
+
+    let mut candidate = String::with_capacity(len);
+    (0..combinations).all(|mut fill| {
+        candidate.clear();
+        for position in &lead {
+            let digit = match position {
+                Ok(digit) => *digit,
+                Err(set) => {
+                    let digit = set.get(fill % set.len()).map_or(b'0', |d| b'0' + d);
+                    fill /= set.len();
+                    digit
+                }
+            };
+            candidate.push(char::from(digit));
+        }
+        while candidate.len() < len {
+            candidate.push('0');
+        }
+        is_real_iin(&candidate)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_real_ranges() {
+        assert!(is_real_iin("4111111111111111"));
+        assert!(is_real_iin("5500000000000004"));
+        assert!(is_real_iin("2221000000000009"));
+        assert!(is_real_iin("378282246310005"));
+        assert!(is_real_iin("6011111111111117"));
+        assert!(is_real_iin("3530111333300000"));
+    }
+
+    #[test]
+    fn test_unallocated_ranges() {
+        assert!(!is_real_iin("0000000000000000"));
+        assert!(!is_real_iin("7992739871300000"));
+        assert!(!is_real_iin("2100000000000000"));
+        assert!(!is_real_iin("3300000000000000"));
+        assert!(!is_real_iin("9000000000000000"));
+    }
+
+    #[cfg(all(feature = "random", feature = "std"))]
+    const DIGITS: &[u8] = &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+
+    #[cfg(all(feature = "random", feature = "std"))]
+    #[test]
+    fn test_always_real() {
+        assert!(always_real("4", "", 16, DIGITS, DIGITS));
+        assert!(always_real("411111", "", 16, DIGITS, DIGITS));
+        assert!(always_real("34", "", 15, DIGITS, DIGITS));
+        assert!(!always_real("2", "", 16, DIGITS, DIGITS));
+        assert!(!always_real("", "", 16, DIGITS, DIGITS));
+        assert!(!always_real("799273", "", 16, DIGITS, DIGITS));
+        assert!(!always_real("4", "", 11, DIGITS, DIGITS));
+
+        // Suffix digits count when they reach into the leading four
+        assert!(always_real("", "411111111111111", 16, DIGITS, DIGITS));
+        assert!(always_real("3", "4111111111111", 15, DIGITS, DIGITS));
+        assert!(!always_real("", "4111111111111", 16, DIGITS, DIGITS));
+        assert!(!always_real("", "0042", 16, DIGITS, DIGITS));
+
+        // Only the digits that can be drawn count
+        assert!(always_real("", "", 16, &[4], &[4]));
+        assert!(always_real("", "", 16, &[4, 5], &[1]));
+        assert!(!always_real("", "", 16, &[4, 7], DIGITS));
+        assert!(always_real("3", "", 16, &[4, 7], &[0, 1]));
+        assert!(!always_real("3", "", 16, &[0], &[9]));
+    }
+
+    #[test]
+    fn test_non_card_lengths() {
+        assert!(!is_real_iin("41111111111"));
+        assert!(!is_real_iin("41111111111111111111"));
+    }
+}
@@ -0,0 +1,267 @@
+//! Interning for workloads that hold many copies of the same numbers.
+//!
+//! Fraud graphs and aggregation jobs can keep millions of [`LuhnNumber`]s in
+//! memory, most of them duplicates. A [`LuhnInterner`] stores one shared
+//! allocation per distinct number and hands out [`InternedLuhnNumber`] handles,
+//! which are as cheap to clone as an `Arc` and compare by pointer.
+
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::card::mask_pan;
+use crate::{strict_validate, LuhnError, LuhnNumber};
+
+/// A pool of validated Luhn numbers, one allocation per distinct value.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{LuhnInterner, LuhnNumber};
+///
+/// let mut interner = LuhnInterner::new();
+/// let a = interner.intern("79927398713").unwrap();
+/// let b = interner.intern_number(&LuhnNumber::new("79927398713").unwrap());
+///
+/// assert!(a.ptr_eq(&b));
+/// assert_eq!(interner.len(), 1);
+/// assert!(interner.intern("79927398714").is_err());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct LuhnInterner {
+    values: HashSet<Arc<str>>,
+}
+
+impl LuhnInterner {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the shared handle for `value`, validating and storing it first if
+    /// it has not been seen before.
+    ///
+    /// Values already in the pool are known to be valid, so they are not
+    /// validated again.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`LuhnNumber::new`].
+    pub fn intern(&mut self, value: &str) -> Result<InternedLuhnNumber, LuhnError> {
+        if let Some(existing) = self.get(value) {
+            return Ok(existing);
+        }
+        strict_validate(value)?;
+        Ok(self.insert(value))
+    }
+
+    /// Returns the shared handle for an already validated number.
+    pub fn intern_number(&mut self, number: &LuhnNumber) -> InternedLuhnNumber {
+        match self.get(number.as_str()) {
+            Some(existing) => existing,
+            None => self.insert(number.as_str()),
+        }
+    }
+
+    /// Returns the shared handle for `value` if it is in the pool.
+    pub fn get(&self, value: &str) -> Option<InternedLuhnNumber> {
+        self.values.get(value).cloned().map(InternedLuhnNumber)
+    }
+
+    /// Returns the number of distinct values in the pool.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns true if the pool holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Drops the values no handle refers to any more, returning how many were
+    /// removed.
+    pub fn purge(&mut self) -> usize {
+        let before = self.values.len();
+        self.values.retain(|value| Arc::strong_count(value) > 1);
+        before - self.values.len()
+    }
+
+    fn insert(&mut self, value: &str) -> InternedLuhnNumber {
+        let value: Arc<str> = Arc::from(value);
+        self.values.insert(Arc::clone(&value));
+        InternedLuhnNumber(value)
+    }
+}
+
+/// A validated Luhn number shared through a [`LuhnInterner`].
+///
+/// It offers the same accessors as [`LuhnNumber`] and converts to one with
+/// `From`. Equality first compares pointers, so two handles from the same
+/// interner compare in constant time; handles from different interners still
+/// compare by value. Hashing and ordering match `str`. `Debug` output is masked
+/// like [`LuhnNumber`]'s. Interned digits are shared and are not wiped on drop,
+/// even with the `zeroize` feature.
+#[derive(Clone)]
+pub struct InternedLuhnNumber(Arc<str>);
+
+impl InternedLuhnNumber {
+    /// Returns the full number, check digit included.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the payload, everything but the trailing check digit.
+    pub fn body(&self) -> &str {
+        self.0
+            .get(..self.0.len().saturating_sub(1))
+            .unwrap_or_default()
+    }
+
+    /// Returns the trailing check digit (0–9).
+    pub fn check_digit(&self) -> u8 {
+        self.0.bytes().last().map_or(0, |b| b - b'0')
+    }
+
+    /// Returns true if both handles share the same allocation.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl PartialEq for InternedLuhnNumber {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || self.0 == other.0
+    }
+}
+
+impl Eq for InternedLuhnNumber {}
+
+impl Hash for InternedLuhnNumber {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl PartialOrd for InternedLuhnNumber {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for InternedLuhnNumber {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl fmt::Debug for InternedLuhnNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("InternedLuhnNumber")
+            .field(&format_args!("{}", mask_pan(&self.0)))
+            .finish()
+    }
+}
+
+impl fmt::Display for InternedLuhnNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for InternedLuhnNumber {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for InternedLuhnNumber {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<InternedLuhnNumber> for LuhnNumber {
+    fn from(value: InternedLuhnNumber) -> LuhnNumber {
+        LuhnNumber::from_validated(value.as_str().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_allocation_per_value() {
+        let mut interner = LuhnInterner::new();
+        let handles: Vec<_> = ["79927398713", "0018", "79927398713", "0018", "79927398713"]
+            .iter()
+            .map(|value| interner.intern(value).unwrap())
+            .collect();
+        assert_eq!(interner.len(), 2);
+        assert!(handles[0].ptr_eq(&handles[2]));
+        assert!(handles[0].ptr_eq(&handles[4]));
+        assert!(handles[1].ptr_eq(&handles[3]));
+        assert!(!handles[0].ptr_eq(&handles[1]));
+
+        let number = LuhnNumber::new("0018").unwrap();
+        assert!(interner.intern_number(&number).ptr_eq(&handles[1]));
+        assert!(interner.get("0018").unwrap().ptr_eq(&handles[1]));
+        assert_eq!(interner.get("18"), None);
+    }
+
+    #[test]
+    fn test_matches_luhn_number() {
+        let mut interner = LuhnInterner::new();
+        let interned = interner.intern("4111111111111111").unwrap();
+        let number = LuhnNumber::new("4111111111111111").unwrap();
+        assert_eq!(interned.as_str(), number.as_str());
+        assert_eq!(interned.body(), number.body());
+        assert_eq!(interned.check_digit(), number.check_digit());
+        assert_eq!(interned.to_string(), number.to_string());
+        assert_eq!(
+            format!("{:?}", interned),
+            "InternedLuhnNumber(411111******1111)"
+        );
+        assert_eq!(LuhnNumber::from(interned), number);
+    }
+
+    #[test]
+    fn test_equality_across_interners() {
+        let a = LuhnInterner::new().intern("79927398713").unwrap();
+        let b = LuhnInterner::new().intern("79927398713").unwrap();
+        assert!(!a.ptr_eq(&b));
+        assert_eq!(a, b);
+
+        let set: HashSet<InternedLuhnNumber> = [a, b].into_iter().collect();
+        assert_eq!(set.len(), 1);
+        assert!(set.contains("79927398713"));
+    }
+
+    #[test]
+    fn test_purge() {
+        let mut interner = LuhnInterner::new();
+        let kept = interner.intern("79927398713").unwrap();
+        interner.intern("0018").unwrap();
+        assert_eq!(interner.purge(), 1);
+        assert_eq!(interner.len(), 1);
+        assert!(interner.get("79927398713").unwrap().ptr_eq(&kept));
+        drop(kept);
+        assert_eq!(interner.purge(), 1);
+        assert!(interner.is_empty());
+    }
+
+    #[test]
+    fn test_error_cases() {
+        let mut interner = LuhnInterner::new();
+        assert_eq!(
+            interner.intern("79927398714").unwrap_err(),
+            LuhnError::InvalidChecksum {
+                expected: 3,
+                found: 4
+            }
+        );
+        assert_eq!(interner.intern("").unwrap_err(), LuhnError::EmptyString);
+        assert!(interner.is_empty());
+    }
+}
@@ -0,0 +1,303 @@
+//! ISBN-10 and ISBN-13 check digits.
+//!
+//! ISBN-10 weights its nine payload digits 10 down to 2 and works modulo 11, so its
+//! check character is a digit or `X`. ISBN-13 is an EAN-13 code and uses the same
+//! 1-3 weighting as [`WeightedMod10::UPC`]. ISBNs are usually printed with hyphens
+//! or spaces between their parts; every function here ignores them.
+//!
+//! # Examples
+//! ```
+//! use luhn_tools::isbn;
+//!
+//! assert!(isbn::validate("0-306-40615-2").unwrap());
+//! assert!(isbn::validate("978-0-306-40615-7").unwrap());
+//! assert_eq!(isbn::to_isbn13("0-306-40615-2").unwrap(), "9780306406157");
+//! ```
+
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use crate::{CheckDigit, LuhnError, WeightedMod10};
+
+/// ISBN-10 check digits as a [`CheckDigit`] implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Isbn10;
+
+impl CheckDigit for Isbn10 {
+    fn compute(&self, payload: &str) -> Result<String, LuhnError> {
+        checksum10(payload).map(String::from)
+    }
+
+    fn validate(&self, value: &str) -> Result<bool, LuhnError> {
+        validate10(value)
+    }
+}
+
+/// ISBN-13 check digits as a [`CheckDigit`] implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Isbn13;
+
+impl CheckDigit for Isbn13 {
+    fn compute(&self, payload: &str) -> Result<String, LuhnError> {
+        checksum13(payload).map(String::from)
+    }
+
+    fn validate(&self, value: &str) -> Result<bool, LuhnError> {
+        validate13(value)
+    }
+}
+
+/// Computes the ISBN-10 check character for the first nine digits: `'0'`–`'9'` or
+/// `'X'`.
+///
+/// # Errors
+/// * `EmptyString` - If `payload` is empty
+/// * `NonNumeric` - If `payload` contains anything but digits, hyphens and spaces
+/// * `InvalidLength` - If `payload` does not have exactly nine digits
+pub fn checksum10(payload: &str) -> Result<char, LuhnError> {
+    let digits = digits(payload, 9, false, "ISBN-10 payload")?;
+    Ok(isbn10_check(&digits))
+}
+
+/// Computes the ISBN-13 check digit for the first twelve digits.
+///
+/// # Errors
+/// The same errors as [`checksum10`], for twelve digits.
+pub fn checksum13(payload: &str) -> Result<char, LuhnError> {
+    let digits = digits(payload, 12, false, "ISBN-13 payload")?;
+    let check = WeightedMod10::UPC.checksum(&digits)?;
+    Ok(char::from(b'0' + check))
+}
+
+/// Validates an ISBN-10. The check character may be `X` or `x`.
+///
+/// # Returns
+/// * `Ok(true)` - If the check character is correct
+/// * `Ok(false)` - If it is not
+/// * `Err(LuhnError)` - If the input is not shaped like an ISBN-10
+///
+/// # Errors
+/// The same errors as [`checksum10`], for ten characters.
+pub fn validate10(value: &str) -> Result<bool, LuhnError> {
+    let digits = digits(value, 10, true, "ISBN-10")?;
+    let (payload, check) = digits.split_at(9);
+    Ok(check.starts_with(isbn10_check(payload)))
+}
+
+/// Validates an ISBN-13.
+///
+/// # Returns
+/// * `Ok(true)` - If the check digit is correct
+/// * `Ok(false)` - If it is not
+/// * `Err(LuhnError)` - If the input is not shaped like an ISBN-13
+///
+/// # Errors
+/// The same errors as [`checksum10`], for thirteen digits.
+pub fn validate13(value: &str) -> Result<bool, LuhnError> {
+    let digits = digits(value, 13, false, "ISBN-13")?;
+    WeightedMod10::UPC.validate(&digits)
+}
+
+/// Validates an ISBN of either format, chosen by its number of digits.
+///
+/// # Errors
+/// The errors of [`validate10`] or [`validate13`], or `InvalidLength` if the input
+/// has neither 10 nor 13 digits.
+pub fn validate(value: &str) -> Result<bool, LuhnError> {
+    match value.chars().filter(|&c| c != '-' && c != ' ').count() {
+        10 => validate10(value),
+        13 => validate13(value),
+        _ => {
+            // Report bad characters before the length, as for a single format
+            strip(value, Some(9))?;
+            Err(LuhnError::InvalidLength(
+                "ISBN must have 10 or 13 digits".to_string(),
+            ))
+        }
+    }
+}
+
+/// Converts an ISBN-10 to its ISBN-13 form, without separators.
+///
+/// # Errors
+/// The errors of [`validate10`], or `InvalidChecksum` if the ISBN-10's check
+/// character is wrong (with `X` reported as 10).
+///
+/// # Examples
+/// ```
+/// use luhn_tools::isbn;
+///
+/// assert_eq!(isbn::to_isbn13("080442957X").unwrap(), "9780804429573");
+/// assert!(isbn::to_isbn13("0-306-40615-3").is_err());
+/// ```
+pub fn to_isbn13(isbn10: &str) -> Result<String, LuhnError> {
+    let digits = digits(isbn10, 10, true, "ISBN-10")?;
+    let (payload, check) = digits.split_at(9);
+    let expected = isbn10_check(payload);
+    if !check.starts_with(expected) {
+        return Err(LuhnError::InvalidChecksum {
+            expected: check_value(expected),
+            found: check.chars().next().map_or(0, check_value),
+        });
+    }
+    let payload = format!("978{}", payload);
+    WeightedMod10::UPC.generate(&payload)
+}
+
+/// Strips hyphens and spaces from `value`, checking that exactly `len` digits
+/// remain. With `allow_x`, the last of them may be `X` or `x`, returned as `X`.
+/// `name` labels the length error.
+fn digits(value: &str, len: usize, allow_x: bool, name: &str) -> Result<String, LuhnError> {
+    let digits = strip(value, if allow_x { Some(len - 1) } else { None })?;
+    if digits.len() != len {
+        return Err(LuhnError::InvalidLength(format!(
+            "{} must have {} digits",
+            name, len
+        )));
+    }
+    Ok(digits)
+}
+
+/// Strips hyphens and spaces, rejecting any other non-digit except an `X` or `x`
+/// at digit position `x_at`.
+fn strip(value: &str, x_at: Option<usize>) -> Result<String, LuhnError> {
+    if value.is_empty() {
+        return Err(LuhnError::EmptyString);
+    }
+    let mut digits = String::with_capacity(value.len());
+    for (index, character) in value.char_indices() {
+        match character {
+            '-' | ' ' => {}
+            '0'..='9' => digits.push(character),
+            'X' | 'x' if x_at == Some(digits.len()) => digits.push('X'),
+            _ => return Err(LuhnError::NonNumeric { index, character }),
+        }
+    }
+    Ok(digits)
+}
+
+/// Computes the ISBN-10 check character for nine ASCII digits.
+fn isbn10_check(payload: &str) -> char {
+    let sum: u32 = payload
+        .bytes()
+        .zip((2..=10).rev())
+        .map(|(byte, weight)| u32::from(byte.wrapping_sub(b'0')) * weight)
+        .sum();
+    match (11 - sum % 11) % 11 {
+        10 => 'X',
+        check => char::from(b'0' + check as u8),
+    }
+}
+
+/// Returns the value of an ISBN-10 check character: 0–9, or 10 for `X`.
+fn check_value(check: char) -> u8 {
+    match check {
+        'X' => 10,
+        _ => (check as u8).wrapping_sub(b'0'),
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_isbn10() {
+        assert_eq!(checksum10("030640615").unwrap(), '2');
+        assert_eq!(checksum10("0-8044-2957").unwrap(), 'X');
+        assert!(validate10("0-306-40615-2").unwrap());
+        assert!(validate10("0 8044 2957 X").unwrap());
+        assert!(validate10("080442957x").unwrap());
+        assert!(!validate10("0306406153").unwrap());
+    }
+
+    #[test]
+    fn test_isbn13() {
+        assert_eq!(checksum13("978-0-306-40615").unwrap(), '7');
+        assert!(validate13("978-0-306-40615-7").unwrap());
+        assert!(validate13("9791234567896").unwrap());
+        assert!(!validate13("9780306406158").unwrap());
+    }
+
+    #[test]
+    fn test_either_format() {
+        assert!(validate("0306406152").unwrap());
+        assert!(validate("9780306406157").unwrap());
+        assert!(!validate("9780306406158").unwrap());
+        assert_eq!(
+            validate("978030640615").unwrap_err(),
+            LuhnError::InvalidLength("ISBN must have 10 or 13 digits".to_string())
+        );
+    }
+
+    #[test]
+    fn test_to_isbn13() {
+        for (isbn10, isbn13) in [
+            ("0-306-40615-2", "9780306406157"),
+            ("080442957X", "9780804429573"),
+            ("0 19 852663 6", "9780198526636"),
+        ] {
+            let converted = to_isbn13(isbn10).unwrap();
+            assert_eq!(converted, isbn13);
+            assert!(validate13(&converted).unwrap());
+        }
+        assert_eq!(
+            to_isbn13("0306406153").unwrap_err(),
+            LuhnError::InvalidChecksum {
+                expected: 2,
+                found: 3
+            }
+        );
+        assert_eq!(
+            to_isbn13("0306406150").unwrap_err(),
+            LuhnError::InvalidChecksum {
+                expected: 2,
+                found: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_check_digit_trait() {
+        assert_eq!(Isbn10.append("080442957").unwrap(), "080442957X");
+        assert!(CheckDigit::validate(&Isbn10, "080442957X").unwrap());
+        assert_eq!(Isbn13.compute("978030640615").unwrap(), "7");
+        assert!(CheckDigit::validate(&Isbn13, "9780306406157").unwrap());
+    }
+
+    #[test]
+    fn test_error_cases() {
+        assert_eq!(validate("").unwrap_err(), LuhnError::EmptyString);
+        assert_eq!(
+            checksum10("03064061").unwrap_err(),
+            LuhnError::InvalidLength("ISBN-10 payload must have 9 digits".to_string())
+        );
+        assert_eq!(
+            validate13("978-0-306-40615").unwrap_err(),
+            LuhnError::InvalidLength("ISBN-13 must have 13 digits".to_string())
+        );
+        // Positions refer to the input as written
+        assert_eq!(
+            validate10("0-306-4O615-2").unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 7,
+                character: 'O'
+            }
+        );
+        // X is only a check character
+        assert_eq!(
+            validate10("08044X9570").unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 5,
+                character: 'X'
+            }
+        );
+        assert_eq!(
+            validate13("978030640615X").unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 12,
+                character: 'X'
+            }
+        );
+    }
+}
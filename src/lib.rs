@@ -7,6 +7,7 @@
 //! # Examples
 //!
 //! ```
+//! # #[cfg(feature = "std")] {
 //! use luhn_tools::{generate, validate, GenerateOptions};
 //!
 //! // Generate a Luhn number
@@ -16,53 +17,614 @@
 //! // Validate a Luhn number
 //! let is_valid = validate("79927398713").unwrap();
 //! assert!(is_valid);
+//! # }
 //! ```
+//!
+//! # Panics
+//!
+//! No public function in this crate panics, whatever the input. Malformed input is
+//! always reported through [`LuhnError`]. The library code is built with
+//! `clippy::unwrap_used`, `clippy::expect_used` and `clippy::panic` forbidden to keep
+//! it that way.
 
 #![cfg_attr(not(feature = "std"), no_std)] // Allow no_std usage
+#![cfg_attr(
+    not(test),
+    forbid(clippy::unwrap_used, clippy::expect_used, clippy::panic)
+)] // Panic-free library code
 
+extern crate alloc;
+// Lets code expanded from the derive macros name `::luhn_tools` inside this crate too.
+#[cfg(feature = "derive")]
+extern crate self as luhn_tools;
+
+#[cfg(feature = "std")]
+use alloc::borrow::Cow;
+#[cfg(feature = "std")]
+use alloc::format;
+use alloc::string::String;
+#[cfg(feature = "std")]
+use alloc::string::ToString;
+use core::fmt;
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt;
 
-/// Configuration options for generating Luhn numbers.
+#[cfg(feature = "std")]
+mod ambiguous;
+#[cfg(all(feature = "bumpalo", feature = "std"))]
+mod arena;
+#[cfg(feature = "std")]
+mod arithmetic;
+#[cfg(feature = "std")]
+mod bitmap;
+#[cfg(feature = "std")]
+mod blocks;
+#[cfg(feature = "std")]
+mod card;
+mod check_digit;
+#[cfg(feature = "std")]
+mod context;
+#[cfg(feature = "std")]
+mod convert;
+pub mod damm;
+#[cfg(feature = "std")]
+mod detached;
+#[cfg(feature = "std")]
+mod diff;
+#[cfg(feature = "std")]
+mod double;
+#[cfg(all(feature = "random", feature = "std"))]
+mod export;
+#[cfg(feature = "std")]
+mod fixed_width;
+mod fixtures;
+#[cfg(all(feature = "arbitrary", feature = "std"))]
+mod fuzz;
+#[cfg(feature = "std")]
+mod grouped;
+mod hex;
+#[cfg(all(feature = "std", any(feature = "forbid-real-iins", feature = "random")))]
+mod iin;
+#[cfg(feature = "std")]
+mod intern;
+pub mod isbn;
+#[cfg(feature = "std")]
+mod limit;
+#[cfg(feature = "std")]
+mod locale;
+#[cfg(feature = "std")]
+mod luhn;
+pub mod mod11_2;
+pub mod mod97_10;
+mod mod_n;
+#[cfg(feature = "std")]
+mod newtype;
+#[cfg(feature = "std")]
+mod number;
+#[cfg(feature = "std")]
+mod pipeline;
+#[cfg(feature = "std")]
+mod pool;
+#[cfg(feature = "std")]
+mod position;
+#[cfg(all(feature = "quickcheck", feature = "std"))]
+mod property;
+#[cfg(feature = "rand_core")]
+mod random_core;
+#[cfg(feature = "std")]
+mod range;
+#[cfg(feature = "std")]
+mod records;
+#[cfg(feature = "std")]
+mod registry;
+#[cfg(feature = "std")]
+mod repair;
+#[cfg(feature = "std")]
+mod report;
+#[cfg(feature = "std")]
+mod resize;
+#[cfg(all(feature = "schemars", feature = "std"))]
+mod schema;
+#[cfg(all(feature = "secrecy", feature = "std"))]
+mod secret;
+#[cfg(feature = "std")]
+mod sequence;
+#[cfg(all(feature = "serde", feature = "std"))]
+pub mod serde;
+#[cfg(all(feature = "random", feature = "std"))]
+mod synthetic;
+#[cfg(all(feature = "bench", feature = "std"))]
+mod throughput;
+mod transliterate;
+#[cfg(feature = "std")]
+mod unicode;
+pub mod verhoeff;
+mod weighted;
+
+#[cfg(feature = "std")]
+pub use ambiguous::resolve_ambiguous;
+#[cfg(all(feature = "bumpalo", feature = "std"))]
+pub use arena::{generate_batch_in, generate_batch_in_until, generate_in};
+#[cfg(feature = "std")]
+pub use arithmetic::{decrement_payload, increment_payload};
+#[cfg(feature = "std")]
+pub use bitmap::{validate_bitmap, validate_bitmap_until, ValidityBitmap};
+#[cfg(feature = "std")]
+pub use blocks::{generate_blocks, validate_blocks};
+#[cfg(all(feature = "random", feature = "std"))]
+pub use card::random_card;
+#[cfg(feature = "std")]
+pub use card::{Brand, CardNumber};
+pub use check_digit::CheckDigit;
+#[cfg(feature = "std")]
+pub use context::{InputError, ResultExt};
+#[cfg(feature = "std")]
+pub use convert::{
+    convert_scheme_batch, convert_scheme_batch_until, Conversion, ConversionRecord,
+    SchemeConversion,
+};
+#[cfg(feature = "std")]
+pub use detached::{
+    compute_detached_batch, compute_detached_batch_until, verify_detached, verify_detached_batch,
+    verify_detached_batch_until, DetachedChecksum,
+};
+#[cfg(feature = "std")]
+pub use diff::{digit_edits, render_diff, DiffStyle, DigitEdit};
+#[cfg(feature = "std")]
+pub use double::{generate_double, validate_double};
+#[cfg(all(feature = "random", feature = "std"))]
+pub use export::{
+    export_test_data, export_test_data_until, export_test_data_with_rng, ExportFormat, ExportSpec,
+};
+#[cfg(feature = "std")]
+pub use fixed_width::FixedWidth;
+pub use fixtures::Fixtures;
+#[cfg(all(feature = "arbitrary", feature = "std"))]
+pub use fuzz::RawInput;
+#[cfg(feature = "std")]
+pub use grouped::{format_grouped, format_grouped_checked};
+pub use hex::{generate_hex, validate_hex};
+#[cfg(feature = "std")]
+pub use intern::{InternedLuhnNumber, LuhnInterner};
+#[cfg(feature = "std")]
+pub use limit::{BatchOutcome, CancelToken, Interruption, Limit};
+#[cfg(feature = "std")]
+pub use locale::{format_grouped_locale, parse_grouped_locale, DigitGrouping, LocaleFormat};
+#[cfg(feature = "std")]
+pub use luhn::{Luhn, LuhnBuilder, Sanitizer};
+#[cfg(all(feature = "derive", feature = "std"))]
+pub use luhn_tools_derive::{LuhnChecked, LuhnValidated};
+pub use mod11_2::validate_orcid;
+pub use mod_n::{
+    generate_mod_n, generate_mod_n_with, validate_mod_n, validate_mod_n_with, Alphabet,
+};
+#[cfg(feature = "std")]
+pub use number::LuhnNumber;
+#[cfg(feature = "std")]
+pub use pipeline::{Pipeline, PipelineError, Stage};
+#[cfg(feature = "std")]
+pub use pool::{validate_pool, validate_pool_into, validate_pool_until, PoolStatus};
+#[cfg(feature = "std")]
+pub use position::{generate_at, validate_at};
+#[cfg(feature = "rand_core")]
+pub use random_core::random_core;
+#[cfg(feature = "std")]
+pub use range::{count_valid_in_range, next_valid, nth_valid_after, prev_valid};
+#[cfg(feature = "std")]
+pub use records::{
+    validate_records, validate_records_until, FieldFailure, FieldSpec, RecordFailure,
+};
+#[cfg(feature = "std")]
+pub use registry::{AlgorithmRegistry, BoxedCheckDigit};
+#[cfg(feature = "std")]
+pub use repair::{
+    fix_check_digit, required_check_digit, suggest_single_digit_fixes, suggest_transposition_fixes,
+    verify_or_fix, LuhnOutcome,
+};
+#[cfg(feature = "std")]
+pub use report::{validate_report, validate_report_with, ValidationReport};
+#[cfg(feature = "std")]
+pub use resize::{extend_to, truncate_to, ResizeReport, ResizeSide};
+#[cfg(all(feature = "secrecy", feature = "std"))]
+pub use secret::{validate_secret, SecretCardNumber};
+#[cfg(feature = "std")]
+pub use sequence::{sequence, valid_with_prefix, Sequence};
+#[cfg(all(feature = "random", feature = "std"))]
+pub use synthetic::{Synthetic, SyntheticCard};
+#[cfg(all(feature = "bench", feature = "std"))]
+pub use throughput::Throughput;
+pub use transliterate::Transliteration;
+pub use weighted::WeightedMod10;
+
+/// Items used by code that this crate's macros expand to. Not public API.
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub mod __private {
+    pub use crate::newtype::{payload_check_digit, push_field, validate_newtype};
+    #[cfg(feature = "serde")]
+    pub use serde;
+}
+
+/// Which digits the Luhn algorithm doubles.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Parity {
+    /// Standard Luhn: doubling starts with the digit immediately left of the check
+    /// digit and alternates leftwards.
+    #[default]
+    FromRight,
+    /// Doubling starts with the leftmost digit and alternates rightwards, as used by
+    /// some national ID schemes. The check digit itself may fall on a doubled position.
+    FromLeft,
+}
+
+/// Configuration options for generating Luhn numbers.
+///
+/// New options may be added in minor releases, so start from
+/// [`GenerateOptions::default`] and set the fields you need.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub struct GenerateOptions {
     /// If true, returns only the checksum digit.
     /// If false, returns the original number with the checksum digit appended.
     pub checksum_only: bool,
+    /// If true, underscores used as visual separators (e.g. `7992_7398_71`) are
+    /// stripped from the input; the output contains only digits.
+    pub allow_underscores: bool,
+    /// If true, degenerate payloads (all zeros, or a single digit repeated) are
+    /// rejected with [`LuhnError::DegeneratePayload`].
+    pub reject_degenerate: bool,
+    /// If set, the output is split into groups of this many digits from the left,
+    /// e.g. "7992 7398 713" for a group size of 4. Ignored when `checksum_only` is
+    /// true or the group size is 0.
+    pub group_size: Option<usize>,
+    /// The character placed between groups when `group_size` is set.
+    pub group_separator: char,
+    /// If set, the input is left-padded with zeros so that the generated number
+    /// (including its check digit) is exactly this many digits long.
+    pub pad_to: Option<usize>,
+    /// Which digits are doubled when computing the check digit.
+    pub parity: Parity,
+}
+
+impl Default for GenerateOptions {
+    fn default() -> Self {
+        GenerateOptions {
+            checksum_only: false,
+            allow_underscores: false,
+            reject_degenerate: false,
+            group_size: None,
+            group_separator: ' ',
+            pad_to: None,
+            parity: Parity::FromRight,
+        }
+    }
+}
+
+/// Configuration options for validating Luhn numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ValidateOptions {
+    /// If true, spaces and dashes are stripped before validation, so inputs like
+    /// "4111 1111 1111 1111" or "79927-39871-3" are accepted.
+    /// If false, they are rejected as usual.
+    pub allow_separators: bool,
+    /// If true, phone-style punctuation is stripped before validation: spaces,
+    /// dashes, dots, parentheses and plus signs, so inputs like
+    /// "+7 (992) 739.871-3" are accepted.
+    pub phone_format: bool,
+    /// If true, leading and trailing whitespace (including newlines) is trimmed
+    /// before validation. Whitespace inside the number is still rejected.
+    pub trim_whitespace: bool,
+    /// If true, control characters (including NUL) and byte order marks anywhere
+    /// in the input are stripped before validation instead of being rejected with
+    /// [`LuhnError::ControlCharacter`].
+    pub strip_control: bool,
+    /// If true, underscores used as visual separators (e.g. `7992_7398_713`) are
+    /// stripped before validation.
+    pub allow_underscores: bool,
+    /// If true, decimal digits from other scripts (e.g. Arabic-Indic `٠١٢` or
+    /// full-width `０１２`) are converted to ASCII digits before validation.
+    pub unicode_digits: bool,
+    /// Minimum number of digits (after normalization), if any.
+    pub min_len: Option<usize>,
+    /// Maximum number of digits (after normalization), if any.
+    pub max_len: Option<usize>,
+    /// If true, numbers whose payload (everything but the check digit) is all
+    /// zeros or a single digit repeated are rejected with
+    /// [`LuhnError::DegeneratePayload`], even though they pass Luhn.
+    pub reject_degenerate: bool,
+    /// If true, numbers made of a single repeated digit, or whose payload is a
+    /// strictly ascending or descending run (e.g. `1234567890123452`), are rejected
+    /// with [`LuhnError::PatternedNumber`], even though they pass Luhn.
+    pub reject_patterned: bool,
+    /// Which digits are doubled when checking the number.
+    pub parity: Parity,
+}
+
+impl ValidateOptions {
+    /// The default options as a constant: no normalization of any kind.
+    pub const STRICT: ValidateOptions = ValidateOptions {
+        allow_separators: false,
+        phone_format: false,
+        trim_whitespace: false,
+        strip_control: false,
+        allow_underscores: false,
+        unicode_digits: false,
+        min_len: None,
+        max_len: None,
+        reject_degenerate: false,
+        reject_patterned: false,
+        parity: Parity::FromRight,
+    };
+}
+
+impl Default for ValidateOptions {
+    fn default() -> Self {
+        ValidateOptions::STRICT
+    }
+}
+
+/// Configuration options for generating random Luhn numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RandomOptions {
+    /// If true, degenerate payloads (all zeros, or a single digit repeated) are
+    /// never produced.
+    pub reject_degenerate: bool,
+    /// Maximum accepted length. `None` applies
+    /// [`RandomOptions::DEFAULT_MAX_LEN`]; use `Some(usize::MAX)` to lift the cap,
+    /// leaving lengths bounded only by the memory that can be reserved for them.
+    pub max_len: Option<usize>,
+    /// If true, card-length numbers (12 to 19 digits) that begin with a prefix
+    /// allocated to a real card issuer are never produced, as with the
+    /// `forbid-real-iins` feature but chosen per call.
+    pub avoid_real_iins: bool,
+    /// If true, numbers never start with 0, so they keep their length when parsed
+    /// as integers.
+    pub no_leading_zero: bool,
+    /// Digits that never appear in the randomly drawn part of the number, e.g. 0 and
+    /// 1 to avoid characters an embossing font renders ambiguously. The check digit
+    /// is computed and cannot be constrained.
+    pub excluded_digits: DigitSet,
+}
+
+impl RandomOptions {
+    /// The length cap applied when [`RandomOptions::max_len`] is `None`.
+    pub const DEFAULT_MAX_LEN: usize = 100;
+}
+
+/// A set of decimal digits, such as [`RandomOptions::excluded_digits`].
+///
+/// Sets are built in code with [`DigitSet::with`], or parsed from a string of
+/// digits read from configuration.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::DigitSet;
+///
+/// let set: DigitSet = "01".parse().unwrap();
+/// assert_eq!(set, DigitSet::EMPTY.with(0).with(1));
+/// assert!(set.contains(1) && !set.contains(2));
+/// assert!("0,1".parse::<DigitSet>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DigitSet(u16);
+
+impl DigitSet {
+    /// The set with no digits.
+    pub const EMPTY: DigitSet = DigitSet(0);
+
+    /// Returns this set with `digit` added. Values above 9 are not digits and leave
+    /// the set unchanged.
+    pub const fn with(self, digit: u8) -> DigitSet {
+        if digit < 10 {
+            DigitSet(self.0 | 1 << digit)
+        } else {
+            self
+        }
+    }
+
+    /// Returns whether `digit` is in the set.
+    pub const fn contains(self, digit: u8) -> bool {
+        digit < 10 && self.0 & 1 << digit != 0
+    }
+
+    /// Returns whether the set has no digits.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl core::str::FromStr for DigitSet {
+    type Err = LuhnError;
+
+    /// Parses a string of ASCII digits, in any order and possibly repeated.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.char_indices()
+            .try_fold(DigitSet::EMPTY, |set, (index, character)| {
+                match character.to_digit(10) {
+                    Some(digit) if character.is_ascii_digit() => Ok(set.with(digit as u8)),
+                    _ => Err(LuhnError::NonNumeric { index, character }),
+                }
+            })
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum LuhnError {
     /// Input string is empty
     EmptyString,
     /// Input contains whitespace
-    ContainsSpaces,
+    ContainsSpaces {
+        /// Byte index of the first space
+        index: usize,
+        /// The offending character
+        character: char,
+    },
+    /// Input contains a control character, NUL or byte order mark
+    ControlCharacter {
+        /// Byte index of the first such character
+        index: usize,
+        /// The offending character
+        character: char,
+    },
     /// Input contains a negative number
     NegativeNumber,
     /// Input contains a floating point number
     FloatingPoint,
     /// Input contains non-numeric characters
-    NonNumeric,
+    NonNumeric {
+        /// Byte index of the first non-numeric character
+        index: usize,
+        /// The offending character
+        character: char,
+    },
     /// Input length is invalid (too short or too long)
     InvalidLength(String),
     /// Error parsing number
     ParseError(String),
+    /// Generated number would begin with a real card issuer prefix
+    /// (only produced with the `forbid-real-iins` feature)
+    RealIssuerPrefix,
+    /// Payload is all zeros or a single digit repeated
+    DegeneratePayload,
+    /// Number is a single repeated digit or a sequential run
+    PatternedNumber,
+    /// Number is well formed but its check digit is wrong
+    InvalidChecksum {
+        /// The check digit the payload requires
+        expected: u8,
+        /// The check digit the number carries
+        found: u8,
+    },
+    /// Algorithm configuration is invalid (e.g. empty weights)
+    InvalidConfiguration(String),
+}
+
+impl LuhnError {
+    /// Returns a fixed message for the error, without formatting any dynamic parts.
+    ///
+    /// Useful where no formatting machinery is available, e.g. `defmt`/`ufmt` logging
+    /// or panic messages in firmware. Variants that carry a message or a position
+    /// return a generic description instead; use `Display` for the full message.
+    ///
+    /// # Examples
+    /// ```
+    /// use luhn_tools::LuhnError;
+    ///
+    /// assert_eq!(LuhnError::EmptyString.as_static_str(), "string cannot be empty");
+    /// # #[cfg(feature = "std")]
+    /// assert_eq!(luhn_tools::validate("1").unwrap_err().as_static_str(), "invalid length");
+    /// ```
+    pub const fn as_static_str(&self) -> &'static str {
+        match self {
+            LuhnError::EmptyString => "string cannot be empty",
+            LuhnError::ContainsSpaces { .. } => "string cannot contain spaces",
+            LuhnError::ControlCharacter { .. } => "string cannot contain control characters",
+            LuhnError::NegativeNumber => "negative numbers are not allowed",
+            LuhnError::FloatingPoint => "floating point numbers are not allowed",
+            LuhnError::NonNumeric { .. } => "string must be convertible to a number",
+            LuhnError::InvalidLength(_) => "invalid length",
+            LuhnError::ParseError(_) => "failed to parse",
+            LuhnError::RealIssuerPrefix => "generated number begins with a real card issuer prefix",
+            LuhnError::DegeneratePayload => {
+                "payload cannot be all zeros or a single repeated digit"
+            }
+            LuhnError::PatternedNumber => "number cannot be a repeated digit or sequential run",
+            LuhnError::InvalidChecksum { .. } => "check digit is invalid",
+            LuhnError::InvalidConfiguration(_) => "invalid configuration",
+        }
+    }
+
+    /// Returns a stable numeric code for the error kind.
+    ///
+    /// Codes never change once assigned and are never reused, so they can be stored
+    /// or passed across an FFI boundary. Dynamic details (messages, positions,
+    /// digits) are not encoded.
+    ///
+    /// | Code | Variant |
+    /// |-----:|---------|
+    /// | 1 | `EmptyString` |
+    /// | 2 | `ContainsSpaces` |
+    /// | 3 | `NegativeNumber` |
+    /// | 4 | `FloatingPoint` |
+    /// | 5 | `NonNumeric` |
+    /// | 6 | `InvalidLength` |
+    /// | 7 | `ParseError` |
+    /// | 8 | `RealIssuerPrefix` |
+    /// | 9 | `DegeneratePayload` |
+    /// | 10 | `InvalidChecksum` |
+    /// | 11 | `InvalidConfiguration` |
+    /// | 12 | `PatternedNumber` |
+    /// | 13 | `ControlCharacter` |
+    ///
+    /// # Examples
+    /// ```
+    /// use luhn_tools::LuhnError;
+    ///
+    /// assert_eq!(LuhnError::EmptyString.error_code(), 1);
+    /// # #[cfg(feature = "std")]
+    /// assert_eq!(luhn_tools::strict_validate("79927398714").unwrap_err().error_code(), 10);
+    /// ```
+    pub const fn error_code(&self) -> u16 {
+        match self {
+            LuhnError::EmptyString => 1,
+            LuhnError::ContainsSpaces { .. } => 2,
+            LuhnError::NegativeNumber => 3,
+            LuhnError::FloatingPoint => 4,
+            LuhnError::NonNumeric { .. } => 5,
+            LuhnError::InvalidLength(_) => 6,
+            LuhnError::ParseError(_) => 7,
+            LuhnError::RealIssuerPrefix => 8,
+            LuhnError::DegeneratePayload => 9,
+            LuhnError::InvalidChecksum { .. } => 10,
+            LuhnError::InvalidConfiguration(_) => 11,
+            LuhnError::PatternedNumber => 12,
+            LuhnError::ControlCharacter { .. } => 13,
+        }
+    }
 }
 
 impl fmt::Display for LuhnError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            LuhnError::EmptyString => write!(f, "string cannot be empty"),
-            LuhnError::ContainsSpaces => write!(f, "string cannot contain spaces"),
-            LuhnError::NegativeNumber => write!(f, "negative numbers are not allowed"),
-            LuhnError::FloatingPoint => write!(f, "floating point numbers are not allowed"),
-            LuhnError::NonNumeric => write!(f, "string must be convertible to a number"),
+            LuhnError::ContainsSpaces { index, .. } => {
+                write!(f, "{} (at byte {})", self.as_static_str(), index)
+            }
+            LuhnError::ControlCharacter { index, character } => {
+                let name = match character {
+                    '\0' => "NUL",
+                    '\u{FEFF}' => "byte order mark",
+                    _ => "control character",
+                };
+                write!(
+                    f,
+                    "string cannot contain control characters (found {} U+{:04X} at byte {})",
+                    name, *character as u32, index
+                )
+            }
+            LuhnError::NonNumeric { index, character } => write!(
+                f,
+                "{} (found {:?} at byte {})",
+                self.as_static_str(),
+                character,
+                index
+            ),
+            LuhnError::InvalidChecksum { expected, found } => write!(
+                f,
+                "{} (expected {}, found {})",
+                self.as_static_str(),
+                expected,
+                found
+            ),
             LuhnError::InvalidLength(msg) => write!(f, "{}", msg),
             LuhnError::ParseError(msg) => write!(f, "{}", msg),
+            LuhnError::InvalidConfiguration(msg) => write!(f, "{}", msg),
+            _ => f.write_str(self.as_static_str()),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for LuhnError {}
 
 /// Validates input string against common error conditions.
@@ -78,8 +640,12 @@ fn handle_errors(value: &str) -> Result<(), LuhnError> {
         return Err(LuhnError::EmptyString);
     }
 
-    if value.contains(' ') {
-        return Err(LuhnError::ContainsSpaces);
+    if let Some((index, character)) = find_char(value, is_control) {
+        return Err(LuhnError::ControlCharacter { index, character });
+    }
+
+    if let Some((index, character)) = find_char(value, |c| c == ' ') {
+        return Err(LuhnError::ContainsSpaces { index, character });
     }
 
     if value.contains('-') {
@@ -90,46 +656,196 @@ fn handle_errors(value: &str) -> Result<(), LuhnError> {
         return Err(LuhnError::FloatingPoint);
     }
 
-    if !value.chars().all(|c| c.is_ascii_digit()) {
-        return Err(LuhnError::NonNumeric);
+    if let Some((index, character)) = find_char(value, |c| !c.is_ascii_digit()) {
+        return Err(LuhnError::NonNumeric { index, character });
     }
 
     Ok(())
 }
 
-/// Calculates the Luhn checksum for a given numeric string.
+/// Returns true for control characters (including NUL) and byte order marks.
+fn is_control(c: char) -> bool {
+    c.is_control() || c == '\u{FEFF}'
+}
+
+/// Returns the byte index and value of the first character matching `predicate`.
+fn find_char(value: &str, predicate: impl Fn(char) -> bool) -> Option<(usize, char)> {
+    value.char_indices().find(|&(_, c)| predicate(c))
+}
+
+/// Checks a candidate Luhn number and reports every problem found, not just the first.
+///
+/// Applies the same rules as [`validate`], but collects all failures so a form can
+/// show them at once. An empty string is reported on its own.
 ///
 /// # Arguments
-/// * `value` - A string slice containing only numeric characters
+/// * `value` - The string to check
 ///
 /// # Returns
-/// * `u8` - The calculated checksum digit
+/// * `Ok(())` - The input is well formed (its checksum is not checked)
+/// * `Err(Vec<LuhnError>)` - Every problem with the input, in a fixed order
 ///
-/// # Panics
-/// * Panics if the input string contains non-numeric characters
-fn generate_checksum(value: &str) -> u8 {
-    let mut double = true;
-    let sum: u32 = value
-        .chars()
-        .rev()
-        .map(|c| c.to_digit(10).unwrap())
-        .fold(0, |acc, digit| {
-            let mut result = acc;
-            if double {
-                let temp = digit * 2;
-                if temp >= 10 {
-                    result += temp / 10 + temp % 10;
-                } else {
-                    result += temp;
-                }
+/// # Examples
+/// ```
+/// use luhn_tools::{check_input_all, LuhnError};
+///
+/// assert!(check_input_all("79927398713").is_ok());
+///
+/// let errors = check_input_all("a ").unwrap_err();
+/// assert_eq!(
+///     errors,
+///     vec![
+///         LuhnError::ContainsSpaces { index: 1, character: ' ' },
+///         LuhnError::NonNumeric { index: 0, character: 'a' },
+///     ]
+/// );
+/// ```
+#[cfg(feature = "std")]
+pub fn check_input_all(value: &str) -> Result<(), Vec<LuhnError>> {
+    if value.is_empty() {
+        return Err(vec![LuhnError::EmptyString]);
+    }
+
+    let mut errors = Vec::new();
+
+    if let Some((index, character)) = find_char(value, is_control) {
+        errors.push(LuhnError::ControlCharacter { index, character });
+    }
+
+    if let Some((index, character)) = find_char(value, |c| c == ' ') {
+        errors.push(LuhnError::ContainsSpaces { index, character });
+    }
+
+    if value.contains('-') {
+        errors.push(LuhnError::NegativeNumber);
+    }
+
+    if value.contains('.') {
+        errors.push(LuhnError::FloatingPoint);
+    }
+
+    if let Some((index, character)) = find_char(value, |c| {
+        !c.is_ascii_digit() && !matches!(c, ' ' | '-' | '.') && !is_control(c)
+    }) {
+        errors.push(LuhnError::NonNumeric { index, character });
+    }
+
+    if value.chars().count() == 1 {
+        errors.push(LuhnError::InvalidLength(
+            "string must be longer than 1 character".to_string(),
+        ));
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Computes the Luhn digit sum of a numeric string, modulo 10, in a single pass.
+///
+/// This is [`WeightedMod10::LUHN`] applied with the weights aligned to the input.
+/// When `double_first` is true the rightmost digit is doubled (the layout used when
+/// computing a new check digit); otherwise doubling starts with the second digit
+/// from the right (the layout used when the check digit is already present).
+///
+/// # Arguments
+/// * `value` - The string to sum
+/// * `double_first` - Whether the rightmost digit is doubled
+///
+/// # Returns
+/// * `Ok(u32)` - The Luhn sum of the digits modulo 10
+/// * `Err(LuhnError)` - The same error `handle_errors` reports for the input
+#[cfg(any(feature = "std", feature = "rand_core"))]
+fn luhn_sum(value: &str, double_first: bool) -> Result<u32, LuhnError> {
+    let weights: &[u32] = if double_first { &[2, 1] } else { &[1, 2] };
+    weighted::weighted_sum(value, weights, 10, true)
+}
+
+/// Converts a Luhn sum over a payload into the check digit that completes it.
+#[cfg(any(feature = "std", feature = "rand_core"))]
+fn checksum_from_sum(sum: u32) -> u8 {
+    ((10 - sum) % 10) as u8
+}
+
+/// Computes the check digit for a payload under the given parity.
+///
+/// # Returns
+/// * `Ok(u8)` - The check digit that completes the payload
+/// * `Err(LuhnError)` - The same error `handle_errors` reports for the input
+#[cfg(any(feature = "std", feature = "rand_core"))]
+fn payload_checksum(payload: &str, parity: Parity) -> Result<u8, LuhnError> {
+    match parity {
+        Parity::FromRight => Ok(checksum_from_sum(luhn_sum(payload, true)?)),
+        Parity::FromLeft => {
+            // Counting from the left, the check digit sits at index `len`; the
+            // rightmost payload digit is doubled when that index is odd.
+            let check_position_doubled = payload.len() % 2 == 0;
+            let checksum = checksum_from_sum(luhn_sum(payload, !check_position_doubled)?);
+            Ok(if check_position_doubled {
+                undouble(checksum)
             } else {
-                result += digit;
-            }
-            double = !double;
-            result
+                checksum
+            })
+        }
+    }
+}
+
+/// Returns the digit whose Luhn-doubled contribution is `contribution`.
+///
+/// Doubling (with digits of two-digit results summed) is a permutation of 0–9, so
+/// every contribution has exactly one preimage.
+#[cfg(any(feature = "std", feature = "rand_core"))]
+fn undouble(contribution: u8) -> u8 {
+    if contribution % 2 == 0 {
+        contribution / 2
+    } else {
+        (contribution + 9) / 2
+    }
+}
+
+/// Returns a digit's contribution to the Luhn sum, doubled or not.
+#[cfg(feature = "std")]
+fn luhn_contribution(digit: u8, doubled: bool) -> u8 {
+    match doubled {
+        true if digit >= 5 => digit * 2 - 9,
+        true => digit * 2,
+        false => digit,
+    }
+}
+
+/// Returns true if `value` is a single digit repeated, or its payload (at least
+/// three digits) steps consistently up or down by one, wrapping 9 to 0.
+#[cfg(feature = "std")]
+fn is_patterned(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    let repeated = bytes.len() > 1 && bytes.windows(2).all(|w| w[0] == w[1]);
+
+    let payload = bytes
+        .get(..bytes.len().saturating_sub(1))
+        .unwrap_or_default();
+    let sequential = payload.len() >= 3
+        && [1, 9].iter().any(|&step| {
+            payload
+                .windows(2)
+                .all(|w| (w[0] - b'0' + step) % 10 == w[1] - b'0')
         });
 
-    ((10 - (sum % 10)) % 10) as u8
+    repeated || sequential
+}
+
+/// Returns true if a payload is all zeros or a single digit repeated.
+///
+/// A lone non-zero digit is not considered degenerate.
+#[cfg(feature = "std")]
+fn is_degenerate(payload: &str) -> bool {
+    match payload.as_bytes().split_first() {
+        Some((&first, rest)) => {
+            rest.iter().all(|&b| b == first) && (first == b'0' || !rest.is_empty())
+        }
+        None => false,
+    }
 }
 
 /// Generates a Luhn number or checksum from the input value.
@@ -144,35 +860,162 @@ fn generate_checksum(value: &str) -> u8 {
 ///
 /// # Examples
 /// ```
-/// use luhn_tools::{generate, GenerateOptions};
+/// use luhn_tools::{generate, GenerateOptions, Parity};
 ///
 /// // Generate full Luhn number
 /// let result = generate("7992739871", None).unwrap();
 /// assert_eq!(result, "79927398713");
 ///
 /// // Generate only checksum
-/// let options = Some(GenerateOptions { checksum_only: true });
-/// let checksum = generate("7992739871", options).unwrap();
+/// let mut options = GenerateOptions::default();
+/// options.checksum_only = true;
+/// let checksum = generate("7992739871", Some(options)).unwrap();
 /// assert_eq!(checksum, "3");
+///
+/// // Accept underscore separators in the input
+/// let mut options = GenerateOptions::default();
+/// options.allow_underscores = true;
+/// assert_eq!(generate("7992_7398_71", Some(options)).unwrap(), "79927398713");
+///
+/// // Group the output for display
+/// let mut options = GenerateOptions::default();
+/// options.group_size = Some(4);
+/// assert_eq!(generate("7992739871", Some(options)).unwrap(), "7992 7398 713");
+///
+/// // Produce a fixed-width number
+/// let mut options = GenerateOptions::default();
+/// options.pad_to = Some(12);
+/// assert_eq!(generate("12345", Some(options)).unwrap(), "000000123455");
+///
+/// // Double from the leftmost digit instead of from the check digit
+/// let mut options = GenerateOptions::default();
+/// options.parity = Parity::FromLeft;
+/// assert_eq!(generate("7992739871", Some(options)).unwrap(), "79927398712");
 /// ```
 ///
 /// # Errors
 /// Returns an error if:
 /// * The input string is empty
+/// * The input contains control characters, NULs or byte order marks
 /// * The input contains spaces
 /// * The input contains negative numbers
 /// * The input contains floating point numbers
 /// * The input contains non-numeric characters
+/// * `reject_degenerate` is set and the input is all zeros or a single repeated digit
+/// * `pad_to` is set and the input is too long to fit, or `pad_to` is less than 2
+///   or too large to allocate
+/// * With the `forbid-real-iins` feature, the resulting number has a card-number
+///   length (12–19 digits) and begins with a real card issuer prefix
 #[cfg(feature = "std")]
 pub fn generate(value: &str, options: Option<GenerateOptions>) -> Result<String, LuhnError> {
-    handle_errors(value)?;
+    let options = options.unwrap_or_default();
+    let (payload, checksum) = prepare_generate(value, &options)?;
+
+    let mut output = String::with_capacity(payload.len() * 2);
+    write_generated(&mut output, &payload, checksum, &options)
+        .map_err(|e| LuhnError::ParseError(e.to_string()))?;
+    Ok(output)
+}
+
+/// Checks and normalizes the input to `generate` and computes its check digit.
+///
+/// # Returns
+/// * `Ok((Cow<str>, u8))` - The (possibly padded) payload and its check digit
+/// * `Err(LuhnError)` - Error if the input is malformed or rejected by `options`
+#[cfg(feature = "std")]
+fn prepare_generate<'a>(
+    value: &'a str,
+    options: &GenerateOptions,
+) -> Result<(Cow<'a, str>, u8), LuhnError> {
+    let normalize = ValidateOptions {
+        allow_underscores: options.allow_underscores,
+        ..ValidateOptions::STRICT
+    };
+    let input = value;
+    let value = normalize_input(input, &normalize);
+    let checksum = payload_checksum(&value, Parity::FromRight)
+        .map_err(|error| locate_in_input(error, input, &normalize))?;
+
+    // Leading zeros never change a standard check digit, so padding can follow the
+    // sum. With left parity they shift every position, so recompute.
+    let value = match options.pad_to {
+        Some(width) => pad_payload(value, width)?,
+        None => value,
+    };
+    let checksum = match options.parity {
+        Parity::FromRight => checksum,
+        Parity::FromLeft => payload_checksum(&value, Parity::FromLeft)?,
+    };
+
+    if options.reject_degenerate && is_degenerate(&value) {
+        return Err(LuhnError::DegeneratePayload);
+    }
+
+    #[cfg(feature = "forbid-real-iins")]
+    if iin::is_real_iin(&format!("{}{}", value, checksum)) {
+        return Err(LuhnError::RealIssuerPrefix);
+    }
+
+    Ok((value, checksum))
+}
+
+/// Writes the output of `generate` for a prepared payload and check digit.
+///
+/// Honors `checksum_only`, `group_size` and `group_separator` from `options`.
+#[cfg(feature = "std")]
+fn write_generated<W: fmt::Write>(
+    out: &mut W,
+    payload: &str,
+    checksum: u8,
+    options: &GenerateOptions,
+) -> fmt::Result {
+    let check_digit = char::from(b'0' + checksum);
+
+    match options.group_size {
+        _ if options.checksum_only => out.write_char(check_digit),
+        Some(size) if size > 0 => grouped::write_grouped(
+            out,
+            payload.chars().chain(core::iter::once(check_digit)),
+            size,
+            options.group_separator,
+        ),
+        _ => {
+            out.write_str(payload)?;
+            out.write_char(check_digit)
+        }
+    }
+}
+
+/// Left-pads a payload with zeros so that, once its check digit is appended, the
+/// number is exactly `width` digits long.
+#[cfg(feature = "std")]
+fn pad_payload(value: Cow<'_, str>, width: usize) -> Result<Cow<'_, str>, LuhnError> {
+    if width < 2 {
+        return Err(LuhnError::InvalidLength(
+            "padded width must be greater than 1".to_string(),
+        ));
+    }
 
-    let checksum = generate_checksum(value);
+    let payload_width = width - 1;
+    if value.len() > payload_width {
+        return Err(LuhnError::InvalidLength(format!(
+            "string must be at most {} characters to pad to {}",
+            payload_width, width
+        )));
+    }
 
-    Ok(match options {
-        Some(opts) if opts.checksum_only => checksum.to_string(),
-        _ => format!("{}{}", value, checksum),
-    })
+    if value.len() == payload_width {
+        Ok(value)
+    } else {
+        // Built by hand: a runtime format width is limited to u16
+        let mut padded = String::new();
+        padded.try_reserve(payload_width).map_err(|_| {
+            LuhnError::InvalidLength(format!("cannot allocate a number padded to {}", width))
+        })?;
+        padded.extend(core::iter::repeat('0').take(payload_width - value.len()));
+        padded.push_str(&value);
+        Ok(Cow::Owned(padded))
+    }
 }
 
 /// Validates whether a number satisfies the Luhn algorithm.
@@ -195,6 +1038,7 @@ pub fn generate(value: &str, options: Option<GenerateOptions>) -> Result<String,
 /// # Errors
 /// Returns an error if:
 /// * The input string is empty
+/// * The input contains control characters, NULs or byte order marks
 /// * The input contains spaces
 /// * The input contains negative numbers
 /// * The input contains floating point numbers
@@ -202,7 +1046,18 @@ pub fn generate(value: &str, options: Option<GenerateOptions>) -> Result<String,
 /// * The input is only one character long
 #[cfg(feature = "std")]
 pub fn validate(value: &str) -> Result<bool, LuhnError> {
-    handle_errors(value)?;
+    validate_parity(value, Parity::FromRight)
+}
+
+/// Validates a number under the given parity.
+#[cfg(feature = "std")]
+fn validate_parity(value: &str, parity: Parity) -> Result<bool, LuhnError> {
+    let double_first = match parity {
+        Parity::FromRight => false,
+        // The rightmost digit has an even index from the left when the length is odd.
+        Parity::FromLeft => value.len() % 2 == 1,
+    };
+    let sum = luhn_sum(value, double_first)?;
 
     if value.len() == 1 {
         return Err(LuhnError::InvalidLength(
@@ -210,210 +1065,2363 @@ pub fn validate(value: &str) -> Result<bool, LuhnError> {
         ));
     }
 
-    let (value_without_checksum, _) = value.split_at(value.len() - 1);
-    Ok(value == generate(value_without_checksum, None)?)
+    Ok(sum == 0)
 }
 
-/// Generates a random number of specified length with a valid Luhn checksum.
+/// Validates a number, treating a checksum mismatch as an error.
+///
+/// Unlike [`validate`], an invalid check digit is reported as
+/// [`LuhnError::InvalidChecksum`] rather than `Ok(false)`, so it cannot be missed
+/// when errors are propagated with `?`.
 ///
 /// # Arguments
-/// * `length` - A string slice containing the desired length of the number
+/// * `value` - A string slice that holds the number to validate
 ///
 /// # Returns
-/// * `Ok(String)` - A random number of the specified length with valid Luhn checksum
-/// * `Err(String)` - Error message if validation fails
+/// * `Ok(())` - The number is valid
+/// * `Err(LuhnError)` - The number is malformed or its check digit is wrong
 ///
 /// # Examples
 /// ```
-/// use luhn_tools::{random, validate};
+/// use luhn_tools::{strict_validate, LuhnError};
 ///
-/// let random_number = random("10").unwrap();
-/// assert_eq!(random_number.len(), 10);
-/// assert!(validate(&random_number).unwrap());
+/// assert!(strict_validate("79927398713").is_ok());
+/// assert_eq!(
+///     strict_validate("79927398714").unwrap_err(),
+///     LuhnError::InvalidChecksum { expected: 3, found: 4 }
+/// );
 /// ```
 ///
 /// # Errors
-/// Returns an error if:
-/// * The length string is empty
-/// * The length string contains non-numeric characters
-/// * The requested length is less than 2
-/// * The requested length is greater than 100
-#[cfg(all(feature = "random", feature = "std"))]
-pub fn random(length: &str) -> Result<String, LuhnError> {
-    handle_errors(length)?;
+/// Returns the same errors as [`validate`], plus `InvalidChecksum` if the check
+/// digit does not match.
+#[cfg(feature = "std")]
+pub fn strict_validate(value: &str) -> Result<(), LuhnError> {
+    if validate(value)? {
+        Ok(())
+    } else {
+        Err(checksum_mismatch(value, Parity::FromRight))
+    }
+}
 
-    let length_as_int: usize = length
-        .parse()
-        .map_err(|_| LuhnError::ParseError("failed to parse length".to_string()))?;
+/// Builds the `InvalidChecksum` error for a well-formed number whose check digit is
+/// wrong, or the input error if the number turns out to be malformed.
+#[cfg(feature = "std")]
+pub(crate) fn checksum_mismatch(value: &str, parity: Parity) -> LuhnError {
+    let (payload, check) = match value
+        .len()
+        .checked_sub(1)
+        .and_then(|end| Some((value.get(..end)?, value.as_bytes().get(end)?)))
+    {
+        Some(split) => split,
+        None => return LuhnError::EmptyString,
+    };
 
-    if length_as_int > 100 {
-        return Err(LuhnError::InvalidLength(
-            "string must be less than 100 characters".to_string(),
-        ));
+    match payload_checksum(payload, parity) {
+        Ok(expected) => LuhnError::InvalidChecksum {
+            expected,
+            found: check.wrapping_sub(b'0'),
+        },
+        Err(error) => error,
     }
+}
 
-    if length_as_int < 2 {
-        return Err(LuhnError::InvalidLength(
-            "string must be greater than 1".to_string(),
-        ));
+/// Validates whether a number satisfies the Luhn algorithm, with options.
+///
+/// # Arguments
+/// * `value` - A string slice that holds the number to validate
+/// * `options` - Optional configuration for validation
+///
+/// # Returns
+/// * `Ok(bool)` - True if the number is valid, false otherwise
+/// * `Err(LuhnError)` - Error if the input is malformed
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{validate_with, ValidateOptions};
+///
+/// let options = Some(ValidateOptions {
+///     allow_separators: true,
+///     ..Default::default()
+/// });
+/// assert!(validate_with("4111 1111 1111 1111", options).unwrap());
+/// assert!(validate_with("79927-39871-3", options).unwrap());
+///
+/// let options = Some(ValidateOptions {
+///     trim_whitespace: true,
+///     ..Default::default()
+/// });
+/// assert!(validate_with(" 79927398713\r\n", options).unwrap());
+///
+/// let options = Some(ValidateOptions {
+///     allow_underscores: true,
+///     ..Default::default()
+/// });
+/// assert!(validate_with("7992_7398_713", options).unwrap());
+///
+/// let options = Some(ValidateOptions {
+///     unicode_digits: true,
+///     ..Default::default()
+/// });
+/// assert!(validate_with("٧٩٩٢٧٣٩٨٧١٣", options).unwrap());
+///
+/// // Enforce payment card lengths
+/// let options = Some(ValidateOptions {
+///     allow_separators: true,
+///     min_len: Some(12),
+///     max_len: Some(19),
+///     ..Default::default()
+/// });
+/// assert!(validate_with("4111 1111 1111 1111", options).unwrap());
+/// assert!(validate_with("79927398713", options).is_err());
+/// ```
+///
+/// # Errors
+/// Returns the same errors as [`validate`] for the input left after the
+/// configured normalization, with positions pointing into the original `value`,
+/// and additionally:
+/// * `InvalidLength` if the number is shorter than `min_len` or longer than `max_len`
+/// * `DegeneratePayload` if `reject_degenerate` is set and the payload is all zeros
+///   or a single repeated digit
+/// * `PatternedNumber` if `reject_patterned` is set and the number is a single
+///   repeated digit or its payload is a sequential run
+#[cfg(feature = "std")]
+pub fn validate_with(value: &str, options: Option<ValidateOptions>) -> Result<bool, LuhnError> {
+    let options = options.unwrap_or_default();
+    validate_normalized(value, &options).map(|(_, valid)| valid)
+}
+
+/// Normalizes and validates `value` as [`validate_with`] does, returning the
+/// normalized digits along with the result.
+#[cfg(feature = "std")]
+fn validate_normalized<'a>(
+    value: &'a str,
+    options: &ValidateOptions,
+) -> Result<(Scratch<'a>, bool), LuhnError> {
+    let input = value;
+    let value = Scratch(normalize_input(input, options));
+
+    let valid = validate_parity(&value, options.parity)
+        .map_err(|error| locate_in_input(error, input, options))?;
+    check_length_bounds(value.len(), options.min_len, options.max_len)?;
+
+    let payload = value.get(..value.len() - 1);
+    if options.reject_degenerate && payload.map_or(false, is_degenerate) {
+        return Err(LuhnError::DegeneratePayload);
+    }
+    if options.reject_patterned && is_patterned(&value) {
+        return Err(LuhnError::PatternedNumber);
+    }
+
+    Ok((value, valid))
+}
+
+/// Checks a digit count against optional inclusive bounds.
+#[cfg(feature = "std")]
+fn check_length_bounds(
+    len: usize,
+    min_len: Option<usize>,
+    max_len: Option<usize>,
+) -> Result<(), LuhnError> {
+    let message = match (min_len, max_len) {
+        (Some(min), Some(max)) if len < min || len > max => {
+            format!("string must be between {} and {} characters", min, max)
+        }
+        (Some(min), None) if len < min => format!("string must be at least {} characters", min),
+        (None, Some(max)) if len > max => format!("string must be at most {} characters", max),
+        _ => return Ok(()),
+    };
+
+    Err(LuhnError::InvalidLength(message))
+}
+
+/// A temporary copy of input digits, wiped when dropped if the `zeroize` feature
+/// is enabled.
+///
+/// Only owned buffers are wiped; borrowed input belongs to the caller.
+#[cfg(feature = "std")]
+struct Scratch<'a>(Cow<'a, str>);
+
+#[cfg(feature = "std")]
+impl Scratch<'_> {
+    /// Moves the digits out, leaving nothing behind to wipe.
+    fn into_owned(mut self) -> String {
+        core::mem::take(&mut self.0).into_owned()
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::ops::Deref for Scratch<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(all(feature = "zeroize", feature = "std"))]
+impl Drop for Scratch<'_> {
+    fn drop(&mut self) {
+        if let Cow::Owned(buffer) = &mut self.0 {
+            zeroize::Zeroize::zeroize(buffer);
+        }
+    }
+}
+
+/// Applies the normalization steps enabled in `options` to raw input.
+///
+/// Borrows the input unchanged when no step applies.
+#[cfg(feature = "std")]
+fn normalize_input<'a>(value: &'a str, options: &ValidateOptions) -> Cow<'a, str> {
+    let (_, value) = trim_input(value, options);
+
+    if value
+        .chars()
+        .any(|c| is_stripped(c, options) || convert_digit(c, options) != c)
+    {
+        Cow::Owned(
+            value
+                .chars()
+                .filter(|&c| !is_stripped(c, options))
+                .map(|c| convert_digit(c, options))
+                .collect(),
+        )
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Moves the position of an input error found in the normalized form of `value`
+/// back to the byte of `value` it came from.
+#[cfg(feature = "std")]
+pub(crate) fn locate_in_input(
+    error: LuhnError,
+    value: &str,
+    options: &ValidateOptions,
+) -> LuhnError {
+    let (start, trimmed) = trim_input(value, options);
+    let locate = |index: usize| {
+        let mut normalized = 0;
+        for (offset, c) in trimmed.char_indices() {
+            if is_stripped(c, options) {
+                continue;
+            }
+            if normalized == index {
+                return start + offset;
+            }
+            normalized += convert_digit(c, options).len_utf8();
+        }
+        index
+    };
+
+    match error {
+        LuhnError::ContainsSpaces { index, character } => LuhnError::ContainsSpaces {
+            index: locate(index),
+            character,
+        },
+        LuhnError::ControlCharacter { index, character } => LuhnError::ControlCharacter {
+            index: locate(index),
+            character,
+        },
+        LuhnError::NonNumeric { index, character } => LuhnError::NonNumeric {
+            index: locate(index),
+            character,
+        },
+        error => error,
+    }
+}
+
+/// Trims the input if `options` asks for it, returning the byte offset of the
+/// trimmed text in `value` along with it.
+#[cfg(feature = "std")]
+fn trim_input<'a>(value: &'a str, options: &ValidateOptions) -> (usize, &'a str) {
+    if options.trim_whitespace {
+        let start = value.trim_start();
+        (value.len() - start.len(), start.trim_end())
+    } else {
+        (0, value)
+    }
+}
+
+/// Returns true if normalization drops `c`.
+#[cfg(feature = "std")]
+fn is_stripped(c: char, options: &ValidateOptions) -> bool {
+    (options.allow_separators && matches!(c, ' ' | '-'))
+        || (options.phone_format && matches!(c, ' ' | '-' | '.' | '(' | ')' | '+'))
+        || (options.allow_underscores && c == '_')
+        || (options.strip_control && is_control(c))
+}
+
+/// Returns what normalization turns `c` into.
+#[cfg(feature = "std")]
+fn convert_digit(c: char, options: &ValidateOptions) -> char {
+    if options.unicode_digits {
+        unicode::to_ascii_digit(c).unwrap_or(c)
+    } else {
+        c
+    }
+}
+
+/// Generates a random number of specified length with a valid Luhn checksum.
+///
+/// # Arguments
+/// * `length` - A string slice containing the desired length of the number
+///
+/// # Returns
+/// * `Ok(String)` - A random number of the specified length with valid Luhn checksum
+/// * `Err(String)` - Error message if validation fails
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{random, validate};
+///
+/// let random_number = random("10").unwrap();
+/// assert_eq!(random_number.len(), 10);
+/// assert!(validate(&random_number).unwrap());
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * The length string is empty
+/// * The length string contains non-numeric characters
+/// * The requested length is less than 2
+/// * The requested length is greater than 100 (see [`RandomOptions::max_len`])
+///
+/// With the `forbid-real-iins` feature, candidates that begin with a real card
+/// issuer prefix are discarded and regenerated.
+#[cfg(all(feature = "random", feature = "std"))]
+pub fn random(length: &str) -> Result<String, LuhnError> {
+    random_with(length, None)
+}
+
+/// Generates a random number of specified length with a valid Luhn checksum, with options.
+///
+/// # Arguments
+/// * `length` - A string slice containing the desired length of the number
+/// * `options` - Optional configuration for generation
+///
+/// # Returns
+/// * `Ok(String)` - A random number of the specified length with valid Luhn checksum
+/// * `Err(LuhnError)` - Error if the length is invalid
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{random_with, RandomOptions};
+///
+/// let options = Some(RandomOptions {
+///     reject_degenerate: true,
+///     ..Default::default()
+/// });
+/// let random_number = random_with("2", options).unwrap();
+/// assert_ne!(random_number, "00");
+/// ```
+///
+/// # Errors
+/// Returns the same errors as [`random`], with the length cap taken from
+/// `options.max_len`, and `InvalidLength` if memory for a number of that length
+/// cannot be reserved.
+#[cfg(all(feature = "random", feature = "std"))]
+pub fn random_with(length: &str, options: Option<RandomOptions>) -> Result<String, LuhnError> {
+    handle_errors(length)?;
+
+    let length_as_int: usize = length
+        .parse()
+        .map_err(|_| LuhnError::ParseError("failed to parse length".to_string()))?;
+
+    random_len_with(length_as_int, options)
+}
+
+/// Generates a random number of `len` digits with a valid Luhn checksum.
+///
+/// The same as [`random`], with the length given as a number.
+///
+/// # Arguments
+/// * `len` - The desired length of the number, including its check digit
+///
+/// # Returns
+/// * `Ok(String)` - A random number of the specified length with valid Luhn checksum
+/// * `Err(LuhnError)` - Error if the length is invalid
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{random_len, validate};
+///
+/// let random_number = random_len(16).unwrap();
+/// assert_eq!(random_number.len(), 16);
+/// assert!(validate(&random_number).unwrap());
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * The requested length is less than 2
+/// * The requested length is greater than 100 (see [`RandomOptions::max_len`])
+#[cfg(all(feature = "random", feature = "std"))]
+pub fn random_len(len: usize) -> Result<String, LuhnError> {
+    random_len_with(len, None)
+}
+
+/// Generates a random number of `len` digits with a valid Luhn checksum, with options.
+///
+/// The same as [`random_with`], with the length given as a number.
+///
+/// # Errors
+/// Returns the same errors as [`random_len`], with the length cap taken from
+/// `options.max_len`, and `InvalidLength` if memory for a number of `len` digits
+/// cannot be reserved.
+#[cfg(all(feature = "random", feature = "std"))]
+pub fn random_len_with(len: usize, options: Option<RandomOptions>) -> Result<String, LuhnError> {
+    random_from_rng(
+        len,
+        "",
+        "",
+        options.unwrap_or_default(),
+        &mut rand::thread_rng(),
+    )
+}
+
+/// Generates a random number of `len` digits with a valid Luhn checksum, drawing
+/// digits from `rng`.
+///
+/// The same as [`random_len`], but with caller-supplied randomness: a seeded RNG
+/// for reproducible tests, a thread-local one, or a hardware-backed source.
+///
+/// # Arguments
+/// * `len` - The desired length of the number, including its check digit
+/// * `rng` - The random number generator to draw digits from
+///
+/// # Returns
+/// * `Ok(String)` - A random number of the specified length with valid Luhn checksum
+/// * `Err(LuhnError)` - Error if the length is invalid
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{random_with_rng, validate};
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let first = random_with_rng(16, &mut StdRng::seed_from_u64(42)).unwrap();
+/// let second = random_with_rng(16, &mut StdRng::seed_from_u64(42)).unwrap();
+/// assert_eq!(first, second);
+/// assert!(validate(&first).unwrap());
+/// ```
+///
+/// # Errors
+/// Returns the same errors as [`random_len`].
+#[cfg(all(feature = "random", feature = "std"))]
+pub fn random_with_rng<R: rand::Rng + ?Sized>(
+    len: usize,
+    rng: &mut R,
+) -> Result<String, LuhnError> {
+    random_from_rng(len, "", "", RandomOptions::default(), rng)
+}
+
+/// Generates a random number of `len` digits with a valid Luhn checksum, drawing
+/// digits from the operating system's secure random source.
+///
+/// Use this where generated numbers double as hard-to-guess vouchers or reference
+/// tokens. Every digit comes straight from [`rand::rngs::OsRng`] (`getrandom`), with
+/// no user-space generator state that could be predicted or cloned, and digits are
+/// drawn without modulo bias. The check digit itself carries no entropy, so a
+/// number of `len` digits has `len - 1` random digits.
+///
+/// # Arguments
+/// * `len` - The desired length of the number, including its check digit
+///
+/// # Returns
+/// * `Ok(String)` - A random number of the specified length with valid Luhn checksum
+/// * `Err(LuhnError)` - Error if the length is invalid
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{random_secure, validate};
+///
+/// let voucher = random_secure(20).unwrap();
+/// assert_eq!(voucher.len(), 20);
+/// assert!(validate(&voucher).unwrap());
+/// ```
+///
+/// # Errors
+/// Returns the same errors as [`random_len`].
+#[cfg(all(feature = "random", feature = "std"))]
+pub fn random_secure(len: usize) -> Result<String, LuhnError> {
+    random_from_rng(
+        len,
+        "",
+        "",
+        RandomOptions::default(),
+        &mut rand::rngs::OsRng,
+    )
+}
+
+/// Generates `count` distinct random numbers of `len` digits with valid Luhn checksums.
+///
+/// Duplicates are discarded internally, so the result always holds exactly `count`
+/// different numbers, in the order they were drawn.
+///
+/// # Arguments
+/// * `count` - How many numbers to generate
+/// * `len` - The desired length of each number, including its check digit
+///
+/// # Returns
+/// * `Ok(Vec<String>)` - `count` distinct random numbers with valid Luhn checksums
+/// * `Err(LuhnError)` - Error if the length is invalid or too short for `count`
+///
+/// # Examples
+/// ```
+/// use std::collections::HashSet;
+/// use luhn_tools::{random_many, validate};
+///
+/// let numbers = random_many(1000, 10).unwrap();
+/// assert_eq!(numbers.iter().collect::<HashSet<_>>().len(), 1000);
+/// assert!(numbers.iter().all(|n| validate(n).unwrap()));
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * The requested length is invalid, as for [`random_len`]
+/// * There are fewer than `count` distinct numbers of length `len`
+///   (`InvalidConfiguration`)
+/// * `count` numbers cannot be allocated (`InvalidLength`)
+#[cfg(all(feature = "random", feature = "std"))]
+pub fn random_many(count: usize, len: usize) -> Result<Vec<String>, LuhnError> {
+    random_many_until(count, len, &Limit::default()).map(BatchOutcome::into_inner)
+}
+
+/// Generates `count` distinct random numbers of `len` digits, stopping early when
+/// `limit` is reached.
+///
+/// The same as [`random_many`], except that an interrupted run returns only the
+/// numbers drawn before the interruption. Duplicate draws count towards the time
+/// spent, so asking for nearly every number of a short length is bounded too.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+/// use luhn_tools::{random_many_until, Limit};
+///
+/// let outcome = random_many_until(100, 12, &Limit::timeout(Duration::from_secs(5))).unwrap();
+/// assert!(outcome.is_complete());
+/// assert_eq!(outcome.into_inner().len(), 100);
+/// ```
+///
+/// # Errors
+/// Returns the same errors as [`random_many`].
+#[cfg(all(feature = "random", feature = "std"))]
+pub fn random_many_until(
+    count: usize,
+    len: usize,
+    limit: &Limit,
+) -> Result<BatchOutcome<Vec<String>>, LuhnError> {
+    check_random_len(len, &RandomOptions::default())?;
+    // Each payload of `len - 1` digits has exactly one valid completion.
+    let distinct = u32::try_from(len - 1)
+        .ok()
+        .and_then(|digits| 10usize.checked_pow(digits));
+    if distinct.map_or(false, |distinct| count > distinct) {
+        return Err(LuhnError::InvalidConfiguration(format!(
+            "cannot generate {} distinct numbers of length {}",
+            count, len
+        )));
     }
 
-    use rand::Rng;
     let mut rng = rand::thread_rng();
+    let mut seen = std::collections::HashSet::new();
+    let mut numbers = Vec::new();
+    seen.try_reserve(count)
+        .ok()
+        .and_then(|_| numbers.try_reserve(count).ok())
+        .ok_or_else(|| LuhnError::InvalidLength(format!("cannot allocate {} numbers", count)))?;
+    let mut checker = limit.checker();
+    while numbers.len() < count {
+        if let Some(reason) = checker.poll() {
+            let completed = numbers.len();
+            return Ok(BatchOutcome::new(numbers, completed, Some(reason)));
+        }
+        let number = random_from_rng(len, "", "", RandomOptions::default(), &mut rng)?;
+        if seen.insert(number.clone()) {
+            numbers.push(number);
+        }
+    }
+    Ok(BatchOutcome::Complete(numbers))
+}
+
+/// Generates a random number of `total_len` digits that begins with `prefix`.
+///
+/// The prefix (e.g. a test BIN such as "411111") is kept as is, the digits after it
+/// are drawn at random and a valid check digit is appended.
+///
+/// # Arguments
+/// * `prefix` - The leading digits to keep; may be empty
+/// * `total_len` - The desired length of the number, including prefix and check digit
+///
+/// # Returns
+/// * `Ok(String)` - A random number starting with `prefix`, with valid Luhn checksum
+/// * `Err(LuhnError)` - Error if the prefix or length is invalid
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{random_with_prefix, validate};
+///
+/// let number = random_with_prefix("799273", 16).unwrap();
+/// assert!(number.starts_with("799273"));
+/// assert_eq!(number.len(), 16);
+/// assert!(validate(&number).unwrap());
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * The prefix fails the usual input checks (spaces, non-numeric, ...)
+/// * The prefix leaves no room for the check digit
+/// * The requested length is invalid, as for [`random_len`]
+///
+/// With the `forbid-real-iins` feature, returns `RealIssuerPrefix` if every number
+/// with this prefix and length falls in a real card issuer range.
+#[cfg(all(feature = "random", feature = "std"))]
+pub fn random_with_prefix(prefix: &str, total_len: usize) -> Result<String, LuhnError> {
+    random_from_rng(
+        total_len,
+        prefix,
+        "",
+        RandomOptions::default(),
+        &mut rand::thread_rng(),
+    )
+}
 
-    let mut random = String::with_capacity(length_as_int - 1);
+/// Generates a random number of `total_len` digits whose last digits before the
+/// check digit are `suffix`.
+///
+/// The digits ahead of the suffix are drawn at random; the check digit is then
+/// computed over the whole payload, so any suffix can be combined with a valid
+/// number.
+///
+/// # Arguments
+/// * `suffix` - The digits that must immediately precede the check digit
+/// * `total_len` - The desired length of the number, including suffix and check digit
+///
+/// # Returns
+/// * `Ok(String)` - A random number with `suffix` before a valid check digit
+/// * `Err(LuhnError)` - Error if the suffix or length is invalid
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{random_with_suffix, validate};
+///
+/// let number = random_with_suffix("0042", 10).unwrap();
+/// assert_eq!(&number[5..9], "0042");
+/// assert!(validate(&number).unwrap());
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * The suffix fails the usual input checks (spaces, non-numeric, ...)
+/// * The suffix leaves no room for the check digit
+/// * The requested length is invalid, as for [`random_len`]
+///
+/// With the `forbid-real-iins` feature, returns `RealIssuerPrefix` if the suffix
+/// fills the leading digits and every resulting number falls in a real card issuer
+/// range.
+#[cfg(all(feature = "random", feature = "std"))]
+pub fn random_with_suffix(suffix: &str, total_len: usize) -> Result<String, LuhnError> {
+    random_from_rng(
+        total_len,
+        "",
+        suffix,
+        RandomOptions::default(),
+        &mut rand::thread_rng(),
+    )
+}
 
-    loop {
-        random.clear();
+/// Draws random payloads from `rng`, between `prefix` and `suffix`, until one
+/// yields an acceptable number.
+#[cfg(all(feature = "random", feature = "std"))]
+fn random_from_rng<R: rand::Rng + ?Sized>(
+    len: usize,
+    prefix: &str,
+    suffix: &str,
+    options: RandomOptions,
+    rng: &mut R,
+) -> Result<String, LuhnError> {
+    check_random_len(len, &options)?;
+    for affix in [prefix, suffix] {
+        if !affix.is_empty() {
+            handle_errors(affix)?;
+        }
+    }
+    if prefix.len() + suffix.len() >= len {
+        let fixed = match (prefix.is_empty(), suffix.is_empty()) {
+            (_, true) => "prefix",
+            (true, false) => "suffix",
+            (false, false) => "prefix and suffix",
+        };
+        return Err(LuhnError::InvalidLength(format!(
+            "{} must be shorter than the total length",
+            fixed
+        )));
+    }
+    let free = len - 1 - prefix.len() - suffix.len();
+    if options.no_leading_zero {
+        let (fixed, leading) = match (prefix.bytes().next(), suffix.bytes().next()) {
+            (Some(digit), _) => ("prefix", Some(digit)),
+            (None, Some(digit)) if free == 0 => ("suffix", Some(digit)),
+            _ => ("", None),
+        };
+        if leading == Some(b'0') {
+            return Err(LuhnError::InvalidConfiguration(format!(
+                "{} cannot start with 0 when no_leading_zero is set",
+                fixed
+            )));
+        }
+    }
+    let allowed: Vec<u8> = (0..10u8)
+        .filter(|&d| !options.excluded_digits.contains(d))
+        .collect();
+    let allowed_leading: Vec<u8> = match options.no_leading_zero && prefix.is_empty() {
+        true => allowed.iter().copied().filter(|&d| d != 0).collect(),
+        false => allowed.clone(),
+    };
+    if free > 0 && allowed_leading.is_empty() {
+        return Err(LuhnError::InvalidConfiguration(
+            "excluded digits leave no digit to draw".to_string(),
+        ));
+    }
+    if options.reject_degenerate
+        && always_degenerate(prefix, suffix, free, &allowed_leading, &allowed)
+    {
+        return Err(LuhnError::InvalidConfiguration(
+            "excluded digits leave only degenerate payloads to draw".to_string(),
+        ));
+    }
+    let avoid_real_iins = options.avoid_real_iins || cfg!(feature = "forbid-real-iins");
+    if avoid_real_iins && iin::always_real(prefix, suffix, len, &allowed_leading, &allowed) {
+        return Err(LuhnError::RealIssuerPrefix);
+    }
+
+    let mut random = String::new();
+    random.try_reserve(len).map_err(|_| {
+        LuhnError::InvalidLength(format!("cannot allocate a number of {} digits", len))
+    })?;
+
+    // A single pass builds a valid number; drawing again is only needed when the
+    // result is rejected by the options.
+    loop {
+        random.clear();
+        random.push_str(prefix);
+
+        // Generate the free digits randomly from the allowed ones
+        for i in 0..free {
+            let digits = if i == 0 { &allowed_leading } else { &allowed };
+            let digit = digits.get(rng.gen_range(0..digits.len())).unwrap_or(&0);
+            random.push(char::from(b'0' + digit));
+        }
+        random.push_str(suffix);
+
+        if options.reject_degenerate && is_degenerate(&random) {
+            continue;
+        }
+        random.push(char::from(
+            b'0' + payload_checksum(&random, Parity::FromRight)?,
+        ));
+        if avoid_real_iins && iin::is_real_iin(&random) {
+            continue;
+        }
+        return Ok(random);
+    }
+}
+
+/// Returns true if every payload of `prefix`, `free` digits drawn from `leading`
+/// then `rest`, and `suffix` is degenerate.
+#[cfg(all(feature = "random", feature = "std"))]
+fn always_degenerate(prefix: &str, suffix: &str, free: usize, leading: &[u8], rest: &[u8]) -> bool {
+    // Only a payload with a single possible digit in every position can be forced
+    let forced = free == 0 || (leading.len() == 1 && (free == 1 || rest.len() == 1));
+    if !forced {
+        return false;
+    }
+    let len = prefix.len() + free + suffix.len();
+    let mut digits = prefix
+        .bytes()
+        .map(|b| b.wrapping_sub(b'0'))
+        .chain(leading.first().copied().filter(|_| free > 0))
+        .chain(rest.first().copied().filter(|_| free > 1))
+        .chain(suffix.bytes().map(|b| b.wrapping_sub(b'0')));
+    match digits.next() {
+        Some(first) => digits.all(|d| d == first) && (first == 0 || len > 1),
+        None => false,
+    }
+}
+
+/// Generates a reproducible number of `len` digits with a valid Luhn checksum.
+///
+/// The same `len` and `seed` produce the same number on every platform and in every
+/// release of this crate, so the output is safe to snapshot. Digits are drawn from
+/// the SplitMix64 generator seeded with `seed`: each payload digit, left to right,
+/// is the next output modulo 10, and the check digit is appended as by
+/// [`generate`]. This is the same sequence the [`fixtures!`] macro uses, and it does
+/// not need the `random` feature.
+///
+/// The output is not suitable where numbers must be hard to guess.
+///
+/// # Arguments
+/// * `len` - The desired length of the number, including its check digit
+/// * `seed` - The generator seed
+///
+/// # Returns
+/// * `Ok(String)` - A number of the specified length with valid Luhn checksum
+/// * `Err(LuhnError)` - Error if the length is invalid
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{random_seeded, validate};
+///
+/// let number = random_seeded(11, 42).unwrap();
+/// assert_eq!(number, random_seeded(11, 42).unwrap());
+/// assert_ne!(number, random_seeded(11, 43).unwrap());
+/// assert!(validate(&number).unwrap());
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * The requested length is less than 2
+/// * The requested length is greater than 100
+/// * With the `forbid-real-iins` feature, the number has a card-number length and
+///   begins with a real card issuer prefix (`RealIssuerPrefix`). The number is
+///   rejected rather than redrawn, so the feature never changes which number a seed
+///   gives.
+#[cfg(feature = "std")]
+pub fn random_seeded(len: usize, seed: u64) -> Result<String, LuhnError> {
+    check_random_len(len, &RandomOptions::default())?;
+
+    let mut state = seed;
+    let mut payload = String::with_capacity(len - 1);
+    for _ in 0..(len - 1) {
+        let (next, output) = fixtures::splitmix64(state);
+        state = next;
+        payload.push(char::from(b'0' + (output % 10) as u8));
+    }
+    generate(&payload, None)
+}
+
+/// Checks a requested random length against the bounds in `options`.
+#[cfg(feature = "std")]
+fn check_random_len(len: usize, options: &RandomOptions) -> Result<(), LuhnError> {
+    let max_len = options.max_len.unwrap_or(RandomOptions::DEFAULT_MAX_LEN);
+    if len > max_len {
+        return Err(LuhnError::InvalidLength(format!(
+            "string must be less than {} characters",
+            max_len
+        )));
+    }
+
+    if len < 2 {
+        return Err(LuhnError::InvalidLength(
+            "string must be greater than 1".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "std")]
+    use super::*;
+
+    #[cfg(all(feature = "random", feature = "std"))]
+    use std::collections::HashSet;
+
+    #[cfg(feature = "std")]
+    mod error {
+        use super::*;
+
+        #[test]
+        fn test_static_str_matches_display_for_fixed_messages() {
+            let errors = [
+                LuhnError::EmptyString,
+                LuhnError::NegativeNumber,
+                LuhnError::FloatingPoint,
+                LuhnError::RealIssuerPrefix,
+                LuhnError::DegeneratePayload,
+                LuhnError::PatternedNumber,
+            ];
+            for error in errors {
+                assert_eq!(error.as_static_str(), error.to_string());
+            }
+        }
+
+        #[test]
+        fn test_static_str_omits_dynamic_parts() {
+            assert_eq!(
+                LuhnError::InvalidLength("string must be at most 3 characters".to_string())
+                    .as_static_str(),
+                "invalid length"
+            );
+            assert_eq!(
+                LuhnError::ParseError("failed to parse length".to_string()).as_static_str(),
+                "failed to parse"
+            );
+            assert_eq!(
+                LuhnError::InvalidConfiguration("weights cannot be empty".to_string())
+                    .as_static_str(),
+                "invalid configuration"
+            );
+            let spaces = LuhnError::ContainsSpaces {
+                index: 4,
+                character: ' ',
+            };
+            assert_eq!(spaces.as_static_str(), "string cannot contain spaces");
+            assert_eq!(
+                spaces.to_string(),
+                "string cannot contain spaces (at byte 4)"
+            );
+        }
+
+        #[test]
+        fn test_error_codes_are_stable_and_unique() {
+            let errors = [
+                (LuhnError::EmptyString, 1),
+                (
+                    LuhnError::ContainsSpaces {
+                        index: 0,
+                        character: ' ',
+                    },
+                    2,
+                ),
+                (LuhnError::NegativeNumber, 3),
+                (LuhnError::FloatingPoint, 4),
+                (
+                    LuhnError::NonNumeric {
+                        index: 0,
+                        character: 'a',
+                    },
+                    5,
+                ),
+                (LuhnError::InvalidLength(String::new()), 6),
+                (LuhnError::ParseError(String::new()), 7),
+                (LuhnError::RealIssuerPrefix, 8),
+                (LuhnError::DegeneratePayload, 9),
+                (
+                    LuhnError::InvalidChecksum {
+                        expected: 0,
+                        found: 1,
+                    },
+                    10,
+                ),
+                (LuhnError::InvalidConfiguration(String::new()), 11),
+                (LuhnError::PatternedNumber, 12),
+                (
+                    LuhnError::ControlCharacter {
+                        index: 0,
+                        character: '\0',
+                    },
+                    13,
+                ),
+            ];
+            let mut seen = std::collections::HashSet::new();
+            for (error, code) in &errors {
+                assert_eq!(error.error_code(), *code);
+                assert!(seen.insert(error.error_code()));
+            }
+        }
+
+        #[test]
+        fn test_errors_can_be_map_keys() {
+            let mut counts = std::collections::HashMap::new();
+            for value in ["", "1a", "1a", "79927398714"] {
+                *counts
+                    .entry(strict_validate(value).unwrap_err())
+                    .or_insert(0) += 1;
+            }
+            assert_eq!(counts.len(), 3);
+            assert_eq!(
+                counts[&LuhnError::NonNumeric {
+                    index: 1,
+                    character: 'a'
+                }],
+                2
+            );
+        }
+
+        #[test]
+        fn test_control_characters() {
+            let cases = [
+                ("7992\u{0}7398713", 4, '\0', "NUL U+0000 at byte 4"),
+                (
+                    "\u{FEFF}79927398713",
+                    0,
+                    '\u{FEFF}',
+                    "byte order mark U+FEFF at byte 0",
+                ),
+                (
+                    "79927398713\r",
+                    11,
+                    '\r',
+                    "control character U+000D at byte 11",
+                ),
+            ];
+            for (value, index, character, detail) in cases {
+                let error = validate(value).unwrap_err();
+                assert_eq!(error, LuhnError::ControlCharacter { index, character });
+                assert_eq!(
+                    error.to_string(),
+                    format!(
+                        "string cannot contain control characters (found {})",
+                        detail
+                    )
+                );
+                assert_eq!(
+                    error.as_static_str(),
+                    "string cannot contain control characters"
+                );
+            }
+            // Control characters take precedence over other input errors
+            assert_eq!(
+                validate("a b\t").unwrap_err(),
+                LuhnError::ControlCharacter {
+                    index: 3,
+                    character: '\t'
+                }
+            );
+        }
+
+        #[test]
+        fn test_position_in_display() {
+            let error = validate("7992x398713").unwrap_err();
+            assert_eq!(
+                error,
+                LuhnError::NonNumeric {
+                    index: 4,
+                    character: 'x'
+                }
+            );
+            assert_eq!(
+                error.to_string(),
+                "string must be convertible to a number (found 'x' at byte 4)"
+            );
+            // Byte index, not character index
+            assert_eq!(
+                validate("٧٩a").unwrap_err(),
+                LuhnError::NonNumeric {
+                    index: 0,
+                    character: '٧'
+                }
+            );
+            assert_eq!(
+                validate("12é4").unwrap_err(),
+                LuhnError::NonNumeric {
+                    index: 2,
+                    character: 'é'
+                }
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod generate {
+        use super::*;
+
+        #[test]
+        fn test_error_cases() {
+            assert_eq!(generate("", None).unwrap_err(), LuhnError::EmptyString);
+            assert_eq!(
+                generate("1a", None).unwrap_err(),
+                LuhnError::NonNumeric {
+                    index: 1,
+                    character: 'a'
+                }
+            );
+            assert_eq!(
+                generate(" 123 ", None).unwrap_err(),
+                LuhnError::ContainsSpaces {
+                    index: 0,
+                    character: ' '
+                }
+            );
+            assert_eq!(
+                generate("-123", None).unwrap_err(),
+                LuhnError::NegativeNumber
+            );
+            assert_eq!(
+                generate("123.45", None).unwrap_err(),
+                LuhnError::FloatingPoint
+            );
+        }
+
+        #[test]
+        fn test_generate_without_options() {
+            assert_eq!(generate("1", None).unwrap(), "18");
+            assert_eq!(generate("7992739871", None).unwrap(), "79927398713");
+            assert_eq!(generate("0123", None).unwrap(), "01230");
+        }
+
+        #[test]
+        fn test_generate_with_checksum_false() {
+            let options = Some(GenerateOptions {
+                checksum_only: false,
+                ..Default::default()
+            });
+            assert_eq!(generate("1", options).unwrap(), "18");
+            assert_eq!(generate("12", options).unwrap(), "125");
+            assert_eq!(generate("123", options).unwrap(), "1230");
+            assert_eq!(generate("1234", options).unwrap(), "12344");
+            assert_eq!(generate("12345", options).unwrap(), "123455");
+            assert_eq!(generate("123456", options).unwrap(), "1234566");
+            assert_eq!(generate("1234567", options).unwrap(), "12345674");
+            assert_eq!(generate("12345678", options).unwrap(), "123456782");
+            assert_eq!(generate("123456789", options).unwrap(), "1234567897");
+            assert_eq!(generate("7992739871", options).unwrap(), "79927398713");
+        }
+
+        #[test]
+        fn test_generate_with_checksum_only() {
+            let options = Some(GenerateOptions {
+                checksum_only: true,
+                ..Default::default()
+            });
+            assert_eq!(generate("1", options).unwrap(), "8");
+            assert_eq!(generate("12", options).unwrap(), "5");
+            assert_eq!(generate("123", options).unwrap(), "0");
+            assert_eq!(generate("1234", options).unwrap(), "4");
+            assert_eq!(generate("12345", options).unwrap(), "5");
+            assert_eq!(generate("123456", options).unwrap(), "6");
+            assert_eq!(generate("1234567", options).unwrap(), "4");
+            assert_eq!(generate("12345678", options).unwrap(), "2");
+            assert_eq!(generate("123456789", options).unwrap(), "7");
+            assert_eq!(generate("7992739871", options).unwrap(), "3");
+        }
+
+        #[test]
+        fn test_never_panics_on_arbitrary_input() {
+            let inputs = [
+                "\u{0}",
+                "١٢٣",
+                "１２３",
+                "💳",
+                "12\u{301}",
+                "\u{feff}123",
+                "+1",
+                "1e5",
+            ];
+            for input in inputs {
+                assert!(generate(input, None).is_err());
+                assert!(validate(input).is_err());
+            }
+        }
+
+        #[test]
+        fn test_very_long_inputs() {
+            // Leading zeros never change the check digit
+            let padded = format!("{}7992739871", "0".repeat(100_000));
+            assert!(generate(&padded, None).unwrap().ends_with('3'));
+
+            // 9s contribute the maximum to the sum on every position
+            let nines = "9".repeat(1_000_000);
+            let checksum = Some(GenerateOptions {
+                checksum_only: true,
+                ..Default::default()
+            });
+            assert_eq!(generate(&nines, checksum).unwrap(), "0");
+            assert_eq!(generate(&nines[1..], checksum).unwrap(), "9");
+        }
+
+        #[test]
+        fn test_allow_underscores() {
+            let options = Some(GenerateOptions {
+                allow_underscores: true,
+                ..Default::default()
+            });
+            assert_eq!(generate("7992_7398_71", options).unwrap(), "79927398713");
+            assert_eq!(generate("1_", options).unwrap(), "18");
+            assert_eq!(
+                generate("7992_7398_71", None).unwrap_err(),
+                LuhnError::NonNumeric {
+                    index: 4,
+                    character: '_'
+                }
+            );
+            assert_eq!(generate("_", options).unwrap_err(), LuhnError::EmptyString);
+            assert_eq!(
+                generate("1_2 3", options).unwrap_err(),
+                LuhnError::ContainsSpaces {
+                    index: 3,
+                    character: ' '
+                }
+            );
+
+            let checksum_only = Some(GenerateOptions {
+                checksum_only: true,
+                allow_underscores: true,
+                ..Default::default()
+            });
+            assert_eq!(generate("7992_7398_71", checksum_only).unwrap(), "3");
+        }
+
+        #[test]
+        fn test_group_output() {
+            let options = Some(GenerateOptions {
+                group_size: Some(4),
+                ..Default::default()
+            });
+            assert_eq!(generate("7992739871", options).unwrap(), "7992 7398 713");
+            assert_eq!(
+                generate("799273987100000", options).unwrap(),
+                "7992 7398 7100 0004"
+            );
+            assert_eq!(generate("1", options).unwrap(), "18");
+
+            let dashes = Some(GenerateOptions {
+                group_size: Some(3),
+                group_separator: '-',
+                ..Default::default()
+            });
+            assert_eq!(generate("7992739871", dashes).unwrap(), "799-273-987-13");
+
+            let checksum_only = Some(GenerateOptions {
+                checksum_only: true,
+                group_size: Some(1),
+                ..Default::default()
+            });
+            assert_eq!(generate("7992739871", checksum_only).unwrap(), "3");
+
+            let zero = Some(GenerateOptions {
+                group_size: Some(0),
+                ..Default::default()
+            });
+            assert_eq!(generate("7992739871", zero).unwrap(), "79927398713");
+        }
+
+        #[test]
+        fn test_pad_to() {
+            let options = Some(GenerateOptions {
+                pad_to: Some(12),
+                ..Default::default()
+            });
+            assert_eq!(generate("12345", options).unwrap(), "000000123455");
+            assert_eq!(generate("79927398710", options).unwrap(), "799273987104");
+            assert_eq!(generate("0", options).unwrap(), "000000000000");
+            assert_eq!(
+                generate("799273987100", options).unwrap_err(),
+                LuhnError::InvalidLength(
+                    "string must be at most 11 characters to pad to 12".to_string()
+                )
+            );
+            assert_eq!(generate("", options).unwrap_err(), LuhnError::EmptyString);
+            assert_eq!(
+                generate("1a", options).unwrap_err(),
+                LuhnError::NonNumeric {
+                    index: 1,
+                    character: 'a'
+                }
+            );
+
+            let grouped = Some(GenerateOptions {
+                pad_to: Some(12),
+                group_size: Some(4),
+                ..Default::default()
+            });
+            assert_eq!(generate("12345", grouped).unwrap(), "0000 0012 3455");
+
+            let too_small = Some(GenerateOptions {
+                pad_to: Some(1),
+                ..Default::default()
+            });
+            assert_eq!(
+                generate("1", too_small).unwrap_err(),
+                LuhnError::InvalidLength("padded width must be greater than 1".to_string())
+            );
+
+            // Widths beyond what a format width can express
+            let wide = Some(GenerateOptions {
+                pad_to: Some(70_000),
+                ..Default::default()
+            });
+            let padded = generate("1", wide).unwrap();
+            assert_eq!(padded.len(), 70_000);
+            assert!(padded.ends_with("018") && validate(&padded).unwrap());
+            let unreservable = Some(GenerateOptions {
+                pad_to: Some(usize::MAX),
+                ..Default::default()
+            });
+            assert_eq!(
+                generate("1", unreservable).unwrap_err(),
+                LuhnError::InvalidLength(format!(
+                    "cannot allocate a number padded to {}",
+                    usize::MAX
+                ))
+            );
+        }
+
+        #[test]
+        fn test_left_parity() {
+            let options = Some(GenerateOptions {
+                parity: Parity::FromLeft,
+                ..Default::default()
+            });
+            // The check digit lands on a doubled position for even-length payloads
+            assert_eq!(generate("7992739871", options).unwrap(), "79927398712");
+            assert_eq!(generate("12", options).unwrap(), "123");
+            assert_eq!(generate("0", options).unwrap(), "00");
+            // Odd-length payloads match standard Luhn
+            assert_eq!(generate("799273987", options).unwrap(), "7992739875");
+            assert_eq!(generate("123", options).unwrap(), "1230");
+
+            // Padding shifts positions, so it changes the check digit
+            let padded = Some(GenerateOptions {
+                parity: Parity::FromLeft,
+                pad_to: Some(4),
+                ..Default::default()
+            });
+            assert_eq!(generate("12", padded).unwrap(), "0125");
+        }
+
+        #[test]
+        fn test_reject_degenerate() {
+            let options = Some(GenerateOptions {
+                reject_degenerate: true,
+                ..Default::default()
+            });
+            for value in ["0", "00", "0000000000", "11", "999999"] {
+                assert_eq!(
+                    generate(value, options).unwrap_err(),
+                    LuhnError::DegeneratePayload
+                );
+                assert!(generate(value, None).is_ok());
+            }
+            assert_eq!(generate("1", options).unwrap(), "18");
+            assert_eq!(generate("1000", options).unwrap(), "10009");
+            assert_eq!(
+                generate("0a", options).unwrap_err(),
+                LuhnError::NonNumeric {
+                    index: 1,
+                    character: 'a'
+                }
+            );
+        }
+
+        #[cfg(feature = "forbid-real-iins")]
+        #[test]
+        fn test_forbid_real_iins() {
+            assert_eq!(
+                generate("411111111111111", None).unwrap_err(),
+                LuhnError::RealIssuerPrefix
+            );
+            let options = Some(GenerateOptions {
+                checksum_only: true,
+                ..Default::default()
+            });
+            assert_eq!(
+                generate("411111111111111", options).unwrap_err(),
+                LuhnError::RealIssuerPrefix
+            );
+            assert_eq!(
+                generate("799273987100000", None).unwrap(),
+                "7992739871000004"
+            );
+            // Short values are not card numbers
+            assert_eq!(generate("4", None).unwrap(), "42");
+        }
+
+        #[test]
+        fn test_edge_cases() {
+            assert_eq!(generate("0", None).unwrap(), "00");
+            assert_eq!(generate("00123", None).unwrap(), "001230");
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod validate {
+        use super::*;
+
+        #[test]
+        fn test_error_cases() {
+            assert_eq!(validate("").unwrap_err(), LuhnError::EmptyString);
+            assert_eq!(
+                validate("1").unwrap_err(),
+                LuhnError::InvalidLength("string must be longer than 1 character".to_string())
+            );
+            assert_eq!(
+                validate("1a").unwrap_err(),
+                LuhnError::NonNumeric {
+                    index: 1,
+                    character: 'a'
+                }
+            );
+            assert_eq!(
+                validate("a").unwrap_err(),
+                LuhnError::NonNumeric {
+                    index: 0,
+                    character: 'a'
+                }
+            );
+        }
+
+        #[test]
+        fn test_error_precedence() {
+            // Spaces are reported before other problems regardless of position
+            assert_eq!(
+                validate("1a 2").unwrap_err(),
+                LuhnError::ContainsSpaces {
+                    index: 2,
+                    character: ' '
+                }
+            );
+            assert_eq!(validate("1a-2").unwrap_err(), LuhnError::NegativeNumber);
+            assert_eq!(validate("1a.2").unwrap_err(), LuhnError::FloatingPoint);
+        }
+
+        #[test]
+        fn test_very_long_inputs() {
+            let long = "1234567890".repeat(100_000);
+            assert!(validate(&format!("{}0", long)).unwrap());
+            assert!(!validate(&format!("{}1", long)).unwrap());
+        }
+
+        #[test]
+        fn test_agrees_with_generate() {
+            for value in [
+                "1",
+                "12",
+                "123",
+                "7992739871",
+                "00123",
+                "98765432109876543210",
+            ] {
+                assert!(validate(&generate(value, None).unwrap()).unwrap());
+            }
+        }
+
+        #[test]
+        fn test_invalid_checksums() {
+            assert!(!validate("10").unwrap());
+            assert!(!validate("120").unwrap());
+            assert!(!validate("1231").unwrap());
+        }
+
+        #[test]
+        fn test_valid_checksums() {
+            assert!(validate("18").unwrap());
+            assert!(validate("125").unwrap());
+            assert!(validate("1230").unwrap());
+            assert!(validate("01230").unwrap());
+            assert!(validate("001230").unwrap());
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod strict_validate {
+        use super::*;
+
+        #[test]
+        fn test_valid_checksums() {
+            assert_eq!(strict_validate("18"), Ok(()));
+            assert_eq!(strict_validate("79927398713"), Ok(()));
+        }
+
+        #[test]
+        fn test_invalid_checksums() {
+            assert_eq!(
+                strict_validate("10"),
+                Err(LuhnError::InvalidChecksum {
+                    expected: 8,
+                    found: 0
+                })
+            );
+            assert_eq!(
+                strict_validate("79927398714"),
+                Err(LuhnError::InvalidChecksum {
+                    expected: 3,
+                    found: 4
+                })
+            );
+            assert_eq!(
+                strict_validate("79927398714").unwrap_err().to_string(),
+                "check digit is invalid (expected 3, found 4)"
+            );
+        }
+
+        #[test]
+        fn test_error_cases() {
+            assert_eq!(strict_validate(""), Err(LuhnError::EmptyString));
+            assert_eq!(
+                strict_validate("1a"),
+                Err(LuhnError::NonNumeric {
+                    index: 1,
+                    character: 'a'
+                })
+            );
+            assert_eq!(
+                strict_validate("1"),
+                Err(LuhnError::InvalidLength(
+                    "string must be longer than 1 character".to_string()
+                ))
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod check_input_all {
+        use super::*;
+
+        #[test]
+        fn test_valid_input() {
+            assert_eq!(check_input_all("79927398713"), Ok(()));
+            // Checksums are not checked
+            assert_eq!(check_input_all("79927398714"), Ok(()));
+        }
+
+        #[test]
+        fn test_single_errors_match_validate() {
+            for value in ["", "1", "1a", "1 2", "-12", "1.2"] {
+                assert_eq!(
+                    check_input_all(value).unwrap_err(),
+                    vec![validate(value).unwrap_err()]
+                );
+            }
+        }
+
+        #[test]
+        fn test_control_characters_are_not_non_numeric() {
+            assert_eq!(
+                check_input_all("\u{FEFF}12").unwrap_err(),
+                vec![LuhnError::ControlCharacter {
+                    index: 0,
+                    character: '\u{FEFF}'
+                }]
+            );
+            assert_eq!(
+                check_input_all("1\0a").unwrap_err(),
+                vec![
+                    LuhnError::ControlCharacter {
+                        index: 1,
+                        character: '\0'
+                    },
+                    LuhnError::NonNumeric {
+                        index: 2,
+                        character: 'a'
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn test_collects_all_errors() {
+            assert_eq!(
+                check_input_all("a").unwrap_err(),
+                vec![
+                    LuhnError::NonNumeric {
+                        index: 0,
+                        character: 'a'
+                    },
+                    LuhnError::InvalidLength("string must be longer than 1 character".to_string()),
+                ]
+            );
+            assert_eq!(
+                check_input_all("-1.5 x").unwrap_err(),
+                vec![
+                    LuhnError::ContainsSpaces {
+                        index: 4,
+                        character: ' '
+                    },
+                    LuhnError::NegativeNumber,
+                    LuhnError::FloatingPoint,
+                    LuhnError::NonNumeric {
+                        index: 5,
+                        character: 'x'
+                    },
+                ]
+            );
+            assert_eq!(
+                check_input_all(" ").unwrap_err(),
+                vec![
+                    LuhnError::ContainsSpaces {
+                        index: 0,
+                        character: ' '
+                    },
+                    LuhnError::InvalidLength("string must be longer than 1 character".to_string()),
+                ]
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod validate_with {
+        use super::*;
+
+        const SEPARATORS: Option<ValidateOptions> = Some(ValidateOptions {
+            allow_separators: true,
+            ..ValidateOptions::STRICT
+        });
+
+        const PHONE: Option<ValidateOptions> = Some(ValidateOptions {
+            phone_format: true,
+            ..ValidateOptions::STRICT
+        });
+
+        const TRIM: Option<ValidateOptions> = Some(ValidateOptions {
+            trim_whitespace: true,
+            ..ValidateOptions::STRICT
+        });
+
+        const STRIP_CONTROL: Option<ValidateOptions> = Some(ValidateOptions {
+            strip_control: true,
+            ..ValidateOptions::STRICT
+        });
+
+        const UNDERSCORES: Option<ValidateOptions> = Some(ValidateOptions {
+            allow_underscores: true,
+            ..ValidateOptions::STRICT
+        });
+
+        const UNICODE: Option<ValidateOptions> = Some(ValidateOptions {
+            unicode_digits: true,
+            ..ValidateOptions::STRICT
+        });
+
+        const DEGENERATE: Option<ValidateOptions> = Some(ValidateOptions {
+            reject_degenerate: true,
+            ..ValidateOptions::STRICT
+        });
+
+        const PATTERNED: Option<ValidateOptions> = Some(ValidateOptions {
+            reject_patterned: true,
+            ..ValidateOptions::STRICT
+        });
+
+        const LEFT_PARITY: Option<ValidateOptions> = Some(ValidateOptions {
+            parity: Parity::FromLeft,
+            ..ValidateOptions::STRICT
+        });
+
+        const PAN_LENGTH: Option<ValidateOptions> = Some(ValidateOptions {
+            min_len: Some(12),
+            max_len: Some(19),
+            ..ValidateOptions::STRICT
+        });
+
+        #[test]
+        fn test_without_options() {
+            assert!(validate_with("79927398713", None).unwrap());
+            assert_eq!(
+                validate_with("7992 7398 713", None).unwrap_err(),
+                LuhnError::ContainsSpaces {
+                    index: 4,
+                    character: ' '
+                }
+            );
+            assert_eq!(
+                validate_with("79927-39871-3", None).unwrap_err(),
+                LuhnError::NegativeNumber
+            );
+        }
+
+        #[test]
+        fn test_allow_separators() {
+            assert!(validate_with("4111 1111 1111 1111", SEPARATORS).unwrap());
+            assert!(validate_with("79927-39871-3", SEPARATORS).unwrap());
+            assert!(validate_with("7992 7398-713", SEPARATORS).unwrap());
+            assert!(!validate_with("7992 7398 714", SEPARATORS).unwrap());
+        }
+
+        #[test]
+        fn test_strip_control() {
+            assert!(validate_with("\u{FEFF}79927398713", STRIP_CONTROL).unwrap());
+            assert!(validate_with("7992\u{0}7398713\r", STRIP_CONTROL).unwrap());
+            assert!(!validate_with("\u{FEFF}79927398714", STRIP_CONTROL).unwrap());
+            assert_eq!(
+                validate_with("\u{FEFF}79927398713", None).unwrap_err(),
+                LuhnError::ControlCharacter {
+                    index: 0,
+                    character: '\u{FEFF}'
+                }
+            );
+        }
+
+        #[test]
+        fn test_phone_format() {
+            assert!(validate_with("+7 (992) 739.871-3", PHONE).unwrap());
+            assert!(validate_with("(7992) 7398-713", PHONE).unwrap());
+            assert!(validate_with("7992.7398.713", PHONE).unwrap());
+            assert!(!validate_with("+7 (992) 739.871-4", PHONE).unwrap());
+            assert_eq!(
+                validate_with("7992.7398.713", SEPARATORS).unwrap_err(),
+                LuhnError::FloatingPoint
+            );
+            assert_eq!(
+                validate_with("(7992) 7398/713", PHONE).unwrap_err(),
+                LuhnError::NonNumeric {
+                    index: 11,
+                    character: '/'
+                }
+            );
+        }
+
+        #[test]
+        fn test_allow_underscores() {
+            assert!(validate_with("7992_7398_713", UNDERSCORES).unwrap());
+            assert!(validate_with("7992__7398713_", UNDERSCORES).unwrap());
+            assert!(!validate_with("7992_7398_714", UNDERSCORES).unwrap());
+            assert_eq!(
+                validate_with("7992_7398_713", None).unwrap_err(),
+                LuhnError::NonNumeric {
+                    index: 4,
+                    character: '_'
+                }
+            );
+            assert_eq!(
+                validate_with("7992-7398_713", UNDERSCORES).unwrap_err(),
+                LuhnError::NegativeNumber
+            );
+        }
+
+        #[test]
+        fn test_unicode_digits() {
+            assert!(validate_with("٧٩٩٢٧٣٩٨٧١٣", UNICODE).unwrap());
+            assert!(validate_with("７９９２７３９８７１３", UNICODE).unwrap());
+            assert!(validate_with("۷۹۹۲۷۳۹۸۷۱۳", UNICODE).unwrap());
+            assert!(validate_with("7992७३९८७१३", UNICODE).unwrap());
+            assert!(!validate_with("٧٩٩٢٧٣٩٨٧١٤", UNICODE).unwrap());
+            assert_eq!(
+                validate_with("٧٩٩٢٧٣٩٨٧١٣", None).unwrap_err(),
+                LuhnError::NonNumeric {
+                    index: 0,
+                    character: '٧'
+                }
+            );
+            // Positions are bytes of the input, not of the converted digits
+            assert_eq!(
+                validate_with("٧٩٩x", UNICODE).unwrap_err(),
+                LuhnError::NonNumeric {
+                    index: 6,
+                    character: 'x'
+                }
+            );
+        }
+
+        #[test]
+        fn test_reject_degenerate() {
+            for value in [
+                "00",
+                "0000000000000000",
+                "1111111111111117",
+                "5555555555555557",
+            ] {
+                assert_eq!(
+                    validate_with(value, DEGENERATE).unwrap_err(),
+                    LuhnError::DegeneratePayload
+                );
+            }
+            assert!(validate_with("0000000000000000", None).unwrap());
+            assert!(validate_with("18", DEGENERATE).unwrap());
+            assert!(validate_with("79927398713", DEGENERATE).unwrap());
+        }
+
+        #[test]
+        fn test_reject_patterned() {
+            for value in [
+                "0000000000000000",
+                "5555555555555555",
+                "1234567890123452",
+                "9876543210987655",
+                "1230",
+            ] {
+                assert_eq!(
+                    validate_with(value, PATTERNED).unwrap_err(),
+                    LuhnError::PatternedNumber
+                );
+            }
+            assert!(validate_with("1234567890123452", None).unwrap());
+            assert!(validate_with("79927398713", PATTERNED).unwrap());
+            assert!(validate_with("1245", PATTERNED).is_ok());
+            assert!(validate_with("125", PATTERNED).is_ok());
+            // Patterned but invalid numbers still report the pattern
+            assert_eq!(
+                validate_with("1111", PATTERNED).unwrap_err(),
+                LuhnError::PatternedNumber
+            );
+        }
+
+        #[test]
+        fn test_left_parity() {
+            assert!(validate_with("79927398712", LEFT_PARITY).unwrap());
+            assert!(!validate_with("79927398713", LEFT_PARITY).unwrap());
+            assert!(validate_with("7992739875", LEFT_PARITY).unwrap());
+            assert!(validate_with("123", LEFT_PARITY).unwrap());
+
+            let options = Some(GenerateOptions {
+                parity: Parity::FromLeft,
+                ..Default::default()
+            });
+            for payload in ["1", "12", "123", "98765", "0000123456789"] {
+                let number = generate(payload, options).unwrap();
+                assert!(validate_with(&number, LEFT_PARITY).unwrap());
+            }
+        }
+
+        #[test]
+        fn test_length_bounds() {
+            assert!(validate_with("411111111117", PAN_LENGTH).unwrap());
+            assert!(validate_with("4111111111111111", PAN_LENGTH).unwrap());
+            assert!(!validate_with("4111111111111112", PAN_LENGTH).unwrap());
+            assert!(validate_with("0000000000000000000", PAN_LENGTH).unwrap());
+
+            let expected =
+                LuhnError::InvalidLength("string must be between 12 and 19 characters".to_string());
+            assert_eq!(
+                validate_with("79927398713", PAN_LENGTH).unwrap_err(),
+                expected
+            );
+            assert_eq!(
+                validate_with("00000000000000000000", PAN_LENGTH).unwrap_err(),
+                expected
+            );
+        }
+
+        #[test]
+        fn test_single_length_bound() {
+            let min_only = Some(ValidateOptions {
+                min_len: Some(4),
+                ..ValidateOptions::STRICT
+            });
+            let max_only = Some(ValidateOptions {
+                max_len: Some(3),
+                ..ValidateOptions::STRICT
+            });
+            assert!(validate_with("1230", min_only).unwrap());
+            assert_eq!(
+                validate_with("125", min_only).unwrap_err(),
+                LuhnError::InvalidLength("string must be at least 4 characters".to_string())
+            );
+            assert!(validate_with("125", max_only).unwrap());
+            assert_eq!(
+                validate_with("1230", max_only).unwrap_err(),
+                LuhnError::InvalidLength("string must be at most 3 characters".to_string())
+            );
+        }
 
-        // Generate all digits randomly (0-9)
-        for _ in 0..(length_as_int - 1) {
-            random.push(char::from_digit(rng.gen_range(0..10), 10).unwrap());
+        #[test]
+        fn test_length_counts_normalized_digits() {
+            let options = Some(ValidateOptions {
+                allow_separators: true,
+                ..PAN_LENGTH.unwrap()
+            });
+            assert!(validate_with("4111 1111 1111 1111", options).unwrap());
+            // Malformed input is reported before length problems
+            assert_eq!(
+                validate_with("41a", PAN_LENGTH).unwrap_err(),
+                LuhnError::NonNumeric {
+                    index: 2,
+                    character: 'a'
+                }
+            );
         }
 
-        // Add checksum and check if valid
-        if let Ok(result) = generate(&random, None) {
-            if validate(&result).unwrap_or(false) {
-                return Ok(result);
-            }
+        #[test]
+        fn test_error_positions_point_into_input() {
+            assert_eq!(
+                validate_with("7992-7398-7x3", SEPARATORS).unwrap_err(),
+                LuhnError::NonNumeric {
+                    index: 11,
+                    character: 'x'
+                }
+            );
+            assert_eq!(
+                validate_with("  7992 739\u{0}8713", TRIM).unwrap_err(),
+                LuhnError::ControlCharacter {
+                    index: 10,
+                    character: '\u{0}'
+                }
+            );
+            assert_eq!(
+                generate(
+                    "79_92x",
+                    Some(GenerateOptions {
+                        allow_underscores: true,
+                        ..Default::default()
+                    })
+                )
+                .unwrap_err(),
+                LuhnError::NonNumeric {
+                    index: 5,
+                    character: 'x'
+                }
+            );
         }
-    }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        #[test]
+        fn test_trim_whitespace() {
+            assert!(validate_with("79927398713\n", TRIM).unwrap());
+            assert!(validate_with("  79927398713\r\n", TRIM).unwrap());
+            assert!(validate_with("\t79927398713 ", TRIM).unwrap());
+            assert!(!validate_with(" 79927398714 ", TRIM).unwrap());
+            assert_eq!(
+                validate_with("7992 7398713", TRIM).unwrap_err(),
+                LuhnError::ContainsSpaces {
+                    index: 4,
+                    character: ' '
+                }
+            );
+            assert_eq!(
+                validate_with(" \n ", TRIM).unwrap_err(),
+                LuhnError::EmptyString
+            );
+            assert_eq!(
+                validate_with(" 79927398713", None).unwrap_err(),
+                LuhnError::ContainsSpaces {
+                    index: 0,
+                    character: ' '
+                }
+            );
+        }
 
-    #[cfg(feature = "random")]
-    use std::collections::HashSet;
+        #[test]
+        fn test_error_cases() {
+            assert_eq!(
+                validate_with(" - ", SEPARATORS).unwrap_err(),
+                LuhnError::EmptyString
+            );
+            assert_eq!(
+                validate_with("1 ", SEPARATORS).unwrap_err(),
+                LuhnError::InvalidLength("string must be longer than 1 character".to_string())
+            );
+            assert_eq!(
+                validate_with("7992.7398", SEPARATORS).unwrap_err(),
+                LuhnError::FloatingPoint
+            );
+        }
+    }
 
-    #[cfg(feature = "std")]
-    mod generate {
+    #[cfg(all(feature = "random", feature = "std"))]
+    mod random {
         use super::*;
 
         #[test]
         fn test_error_cases() {
-            assert_eq!(generate("", None).unwrap_err(), LuhnError::EmptyString);
-            assert_eq!(generate("1a", None).unwrap_err(), LuhnError::NonNumeric);
+            assert_eq!(random("").unwrap_err(), LuhnError::EmptyString);
             assert_eq!(
-                generate(" 123 ", None).unwrap_err(),
-                LuhnError::ContainsSpaces
+                random("1a").unwrap_err(),
+                LuhnError::NonNumeric {
+                    index: 1,
+                    character: 'a'
+                }
             );
             assert_eq!(
-                generate("-123", None).unwrap_err(),
-                LuhnError::NegativeNumber
+                random("1").unwrap_err(),
+                LuhnError::InvalidLength("string must be greater than 1".to_string())
             );
             assert_eq!(
-                generate("123.45", None).unwrap_err(),
-                LuhnError::FloatingPoint
+                random("101").unwrap_err(),
+                LuhnError::InvalidLength("string must be less than 100 characters".to_string())
             );
         }
 
         #[test]
-        fn test_generate_without_options() {
-            assert_eq!(generate("1", None).unwrap(), "18");
-            assert_eq!(generate("7992739871", None).unwrap(), "79927398713");
-            assert_eq!(generate("0123", None).unwrap(), "01230");
+        fn test_valid_lengths() {
+            let lengths = ["2", "25", "50", "99"];
+            for length in lengths.iter() {
+                let value = random(length).unwrap();
+                assert!(validate(&value).unwrap());
+                assert_eq!(value.len(), length.parse::<usize>().unwrap());
+            }
         }
 
         #[test]
-        fn test_generate_with_checksum_false() {
-            let options = Some(GenerateOptions {
-                checksum_only: false,
+        fn test_reject_degenerate() {
+            let options = Some(RandomOptions {
+                reject_degenerate: true,
+                ..Default::default()
             });
-            assert_eq!(generate("1", options).unwrap(), "18");
-            assert_eq!(generate("12", options).unwrap(), "125");
-            assert_eq!(generate("123", options).unwrap(), "1230");
-            assert_eq!(generate("1234", options).unwrap(), "12344");
-            assert_eq!(generate("12345", options).unwrap(), "123455");
-            assert_eq!(generate("123456", options).unwrap(), "1234566");
-            assert_eq!(generate("1234567", options).unwrap(), "12345674");
-            assert_eq!(generate("12345678", options).unwrap(), "123456782");
-            assert_eq!(generate("123456789", options).unwrap(), "1234567897");
-            assert_eq!(generate("7992739871", options).unwrap(), "79927398713");
+            for _ in 0..200 {
+                assert_ne!(random_with("2", options).unwrap(), "00");
+                let value = random_with("3", options).unwrap();
+                assert_ne!(value[..1], value[1..2]);
+            }
         }
 
         #[test]
-        fn test_generate_with_checksum_only() {
-            let options = Some(GenerateOptions {
-                checksum_only: true,
+        fn test_max_len() {
+            let uncapped = Some(RandomOptions {
+                max_len: Some(usize::MAX),
+                ..Default::default()
             });
-            assert_eq!(generate("1", options).unwrap(), "8");
-            assert_eq!(generate("12", options).unwrap(), "5");
-            assert_eq!(generate("123", options).unwrap(), "0");
-            assert_eq!(generate("1234", options).unwrap(), "4");
-            assert_eq!(generate("12345", options).unwrap(), "5");
-            assert_eq!(generate("123456", options).unwrap(), "6");
-            assert_eq!(generate("1234567", options).unwrap(), "4");
-            assert_eq!(generate("12345678", options).unwrap(), "2");
-            assert_eq!(generate("123456789", options).unwrap(), "7");
-            assert_eq!(generate("7992739871", options).unwrap(), "3");
+            for length in ["101", "150", "300"] {
+                let value = random_with(length, uncapped).unwrap();
+                assert_eq!(value.len(), length.parse::<usize>().unwrap());
+                assert!(validate(&value).unwrap());
+            }
+
+            let capped = Some(RandomOptions {
+                max_len: Some(10),
+                ..Default::default()
+            });
+            assert_eq!(random_with("10", capped).unwrap().len(), 10);
+            assert_eq!(
+                random_with("11", capped).unwrap_err(),
+                LuhnError::InvalidLength("string must be less than 10 characters".to_string())
+            );
         }
 
         #[test]
-        fn test_edge_cases() {
-            assert_eq!(generate("0", None).unwrap(), "00");
-            assert_eq!(generate("00123", None).unwrap(), "001230");
+        fn test_random_len() {
+            for len in [2, 16, 99, 100] {
+                let value = random_len(len).unwrap();
+                assert_eq!(value.len(), len);
+                assert!(validate(&value).unwrap());
+            }
+            assert_eq!(random_len(1).unwrap_err(), random("1").unwrap_err());
+            assert_eq!(random_len(101).unwrap_err(), random("101").unwrap_err());
+
+            let uncapped = Some(RandomOptions {
+                max_len: Some(usize::MAX),
+                ..Default::default()
+            });
+            assert_eq!(random_len_with(150, uncapped).unwrap().len(), 150);
+            let unreservable = LuhnError::InvalidLength(format!(
+                "cannot allocate a number of {} digits",
+                usize::MAX
+            ));
+            assert_eq!(
+                random_len_with(usize::MAX, uncapped).unwrap_err(),
+                unreservable
+            );
+            assert_eq!(
+                random_with(&usize::MAX.to_string(), uncapped).unwrap_err(),
+                unreservable
+            );
         }
-    }
 
-    #[cfg(feature = "std")]
-    mod validate {
-        use super::*;
+        #[test]
+        fn test_random_with_rng() {
+            use rand::rngs::StdRng;
+            use rand::SeedableRng;
+
+            let mut rng = StdRng::seed_from_u64(7);
+            let first: Vec<String> = (0..5)
+                .map(|_| random_with_rng(12, &mut rng).unwrap())
+                .collect();
+            let mut rng = StdRng::seed_from_u64(7);
+            let second: Vec<String> = (0..5)
+                .map(|_| random_with_rng(12, &mut rng).unwrap())
+                .collect();
+            assert_eq!(first, second);
+            for value in &first {
+                assert_eq!(value.len(), 12);
+                assert!(validate(value).unwrap());
+            }
+
+            // Unsized RNGs work too
+            let rng: &mut dyn rand::RngCore = &mut rand::thread_rng();
+            assert!(validate(&random_with_rng(10, rng).unwrap()).unwrap());
+            assert_eq!(
+                random_with_rng(1, &mut StdRng::seed_from_u64(7)).unwrap_err(),
+                random_len(1).unwrap_err()
+            );
+        }
 
         #[test]
-        fn test_error_cases() {
-            assert_eq!(validate("").unwrap_err(), LuhnError::EmptyString);
+        fn test_random_secure() {
+            let mut results = HashSet::new();
+            for _ in 0..50 {
+                let value = random_secure(20).unwrap();
+                assert_eq!(value.len(), 20);
+                assert!(validate(&value).unwrap());
+                results.insert(value);
+            }
+            assert_eq!(results.len(), 50);
+            assert_eq!(random_secure(1).unwrap_err(), random_len(1).unwrap_err());
+        }
+
+        #[test]
+        fn test_random_with_prefix() {
+            for (prefix, len) in [("799273", 16), ("", 10), ("7992739871", 11), ("0", 2)] {
+                let value = random_with_prefix(prefix, len).unwrap();
+                assert!(value.starts_with(prefix));
+                assert_eq!(value.len(), len);
+                assert!(validate(&value).unwrap());
+            }
             assert_eq!(
-                validate("1").unwrap_err(),
-                LuhnError::InvalidLength("string must be longer than 1 character".to_string())
+                random_with_prefix("79927398713", 11).unwrap_err(),
+                LuhnError::InvalidLength(
+                    "prefix must be shorter than the total length".to_string()
+                )
+            );
+            assert_eq!(
+                random_with_prefix("4111 11", 16).unwrap_err(),
+                LuhnError::ContainsSpaces {
+                    index: 4,
+                    character: ' '
+                }
+            );
+            assert_eq!(
+                random_with_prefix("4", 1).unwrap_err(),
+                random_len(1).unwrap_err()
             );
-            assert_eq!(validate("1a").unwrap_err(), LuhnError::NonNumeric);
         }
 
         #[test]
-        fn test_invalid_checksums() {
-            assert!(!validate("10").unwrap());
-            assert!(!validate("120").unwrap());
-            assert!(!validate("1231").unwrap());
+        fn test_random_with_suffix() {
+            for (suffix, len) in [("0042", 10), ("", 10), ("7992739871", 11), ("0", 2)] {
+                let value = random_with_suffix(suffix, len).unwrap();
+                assert_eq!(value.len(), len);
+                assert_eq!(&value[len - 1 - suffix.len()..len - 1], suffix);
+                assert!(validate(&value).unwrap());
+            }
+            assert_eq!(
+                random_with_suffix("0042", 4).unwrap_err(),
+                LuhnError::InvalidLength(
+                    "suffix must be shorter than the total length".to_string()
+                )
+            );
+            assert_eq!(
+                random_with_suffix("00a2", 10).unwrap_err(),
+                LuhnError::NonNumeric {
+                    index: 2,
+                    character: 'a'
+                }
+            );
         }
 
         #[test]
-        fn test_valid_checksums() {
-            assert!(validate("18").unwrap());
-            assert!(validate("125").unwrap());
-            assert!(validate("1230").unwrap());
-            assert!(validate("01230").unwrap());
-            assert!(validate("001230").unwrap());
+        #[cfg(not(feature = "forbid-real-iins"))]
+        fn test_random_with_test_bin() {
+            let value = random_with_prefix("411111", 16).unwrap();
+            assert!(value.starts_with("411111"));
+            assert!(validate(&value).unwrap());
         }
-    }
 
-    #[cfg(all(feature = "random", feature = "std"))]
-    mod random {
-        use super::*;
+        #[test]
+        #[cfg(feature = "forbid-real-iins")]
+        fn test_random_with_real_prefix() {
+            assert_eq!(
+                random_with_prefix("411111", 16).unwrap_err(),
+                LuhnError::RealIssuerPrefix
+            );
+            // Some completions of "2" are unallocated, so generation still succeeds
+            let value = random_with_prefix("2", 16).unwrap();
+            assert!(!iin::is_real_iin(&value));
+        }
 
         #[test]
-        fn test_error_cases() {
-            assert_eq!(random("").unwrap_err(), LuhnError::EmptyString);
-            assert_eq!(random("1a").unwrap_err(), LuhnError::NonNumeric);
+        fn test_random_many() {
+            let numbers = random_many(500, 12).unwrap();
+            assert_eq!(numbers.len(), 500);
+            assert_eq!(numbers.iter().collect::<HashSet<_>>().len(), 500);
+            assert!(numbers
+                .iter()
+                .all(|n| n.len() == 12 && validate(n).unwrap()));
+
+            // Every two-digit number is produced when all of them are requested
+            let mut pairs = random_many(10, 2).unwrap();
+            pairs.sort();
+            let expected: Vec<String> = (0..10)
+                .map(|d| generate(&d.to_string(), None).unwrap())
+                .collect();
+            assert_eq!(pairs, expected);
+
+            assert!(random_many(0, 10).unwrap().is_empty());
             assert_eq!(
-                random("1").unwrap_err(),
-                LuhnError::InvalidLength("string must be greater than 1".to_string())
+                random_many(11, 2).unwrap_err(),
+                LuhnError::InvalidConfiguration(
+                    "cannot generate 11 distinct numbers of length 2".to_string()
+                )
             );
+            assert_eq!(random_many(5, 1).unwrap_err(), random_len(1).unwrap_err());
+            // Too many to hold is an error, not a panic
             assert_eq!(
-                random("101").unwrap_err(),
-                LuhnError::InvalidLength("string must be less than 100 characters".to_string())
+                random_many(usize::MAX, 100).unwrap_err(),
+                LuhnError::InvalidLength(format!("cannot allocate {} numbers", usize::MAX))
             );
         }
 
         #[test]
-        fn test_valid_lengths() {
-            let lengths = ["2", "25", "50", "99"];
-            for length in lengths.iter() {
-                let value = random(length).unwrap();
+        fn test_random_many_until() {
+            let expired = Limit {
+                deadline: Some(std::time::Instant::now()),
+                cancel: None,
+            };
+            assert_eq!(
+                random_many_until(10, 12, &expired).unwrap(),
+                BatchOutcome::Interrupted {
+                    partial: vec![],
+                    completed: 0,
+                    reason: Interruption::Deadline
+                }
+            );
+            assert_eq!(
+                random_many_until(11, 2, &expired).unwrap_err(),
+                random_many(11, 2).unwrap_err()
+            );
+
+            let outcome = random_many_until(10, 2, &Limit::default()).unwrap();
+            assert!(outcome.is_complete());
+            assert_eq!(outcome.value().len(), 10);
+        }
+
+        #[test]
+        fn test_avoid_real_iins() {
+            let options = Some(RandomOptions {
+                avoid_real_iins: true,
+                ..Default::default()
+            });
+            for len in [12, 15, 16, 19] {
+                for _ in 0..200 {
+                    let value = random_len_with(len, options).unwrap();
+                    assert!(!iin::is_real_iin(&value), "{}", value);
+                    assert!(validate(&value).unwrap());
+                }
+            }
+            // Outside card lengths the flag changes nothing
+            assert_eq!(random_len_with(11, options).unwrap().len(), 11);
+        }
+
+        #[test]
+        fn test_no_leading_zero() {
+            let options = RandomOptions {
+                no_leading_zero: true,
+                ..Default::default()
+            };
+            for _ in 0..200 {
+                let value = random_len_with(2, Some(options)).unwrap();
+                assert!(!value.starts_with('0'));
                 assert!(validate(&value).unwrap());
-                assert_eq!(value.len(), length.parse::<usize>().unwrap());
             }
+            let mut rng = rand::thread_rng();
+            for _ in 0..200 {
+                let value = random_from_rng(6, "", "42", options, &mut rng).unwrap();
+                assert!(!value.starts_with('0'));
+            }
+            assert_eq!(
+                random_from_rng(6, "0", "", options, &mut rng).unwrap_err(),
+                LuhnError::InvalidConfiguration(
+                    "prefix cannot start with 0 when no_leading_zero is set".to_string()
+                )
+            );
+            assert_eq!(
+                random_from_rng(5, "", "0042", options, &mut rng).unwrap_err(),
+                LuhnError::InvalidConfiguration(
+                    "suffix cannot start with 0 when no_leading_zero is set".to_string()
+                )
+            );
+            assert!(random_from_rng(6, "", "0042", options, &mut rng).is_ok());
+        }
+
+        #[test]
+        fn test_excluded_digits() {
+            let options = RandomOptions {
+                excluded_digits: DigitSet::EMPTY.with(0).with(1),
+                ..Default::default()
+            };
+            for _ in 0..200 {
+                let value = random_len_with(16, Some(options)).unwrap();
+                let (body, _) = value.split_at(15);
+                assert!(!body.contains('0') && !body.contains('1'), "{}", value);
+                assert!(validate(&value).unwrap());
+            }
+
+            let only_nine = RandomOptions {
+                excluded_digits: "012345678".parse().unwrap(),
+                ..Default::default()
+            };
+            assert!(random_len_with(10, Some(only_nine))
+                .unwrap()
+                .starts_with("999999999"));
+
+            let leading = RandomOptions {
+                excluded_digits: "123456789".parse().unwrap(),
+                no_leading_zero: true,
+                ..Default::default()
+            };
+            assert_eq!(
+                random_len_with(10, Some(leading)).unwrap_err(),
+                LuhnError::InvalidConfiguration(
+                    "excluded digits leave no digit to draw".to_string()
+                )
+            );
+        }
+
+        #[test]
+        fn test_excluded_digits_with_avoid_real_iins() {
+            // Every draw would start with 4, a Visa prefix
+            let only_four = RandomOptions {
+                avoid_real_iins: true,
+                excluded_digits: "012356789".parse().unwrap(),
+                ..Default::default()
+            };
+            assert_eq!(
+                random_with("16", Some(only_four)).unwrap_err(),
+                LuhnError::RealIssuerPrefix
+            );
+            // Outside card lengths the interlock does not apply
+            assert!(random_with("11", Some(only_four)).is_ok());
+            let four_or_seven = RandomOptions {
+                excluded_digits: "01235689".parse().unwrap(),
+                ..only_four
+            };
+            assert!(random_with("16", Some(four_or_seven))
+                .unwrap()
+                .starts_with('7'));
+        }
+
+        #[test]
+        fn test_excluded_digits_with_reject_degenerate() {
+            let only_nine = RandomOptions {
+                excluded_digits: "012345678".parse().unwrap(),
+                reject_degenerate: true,
+                ..Default::default()
+            };
+            let degenerate = LuhnError::InvalidConfiguration(
+                "excluded digits leave only degenerate payloads to draw".to_string(),
+            );
+            let mut rng = rand::thread_rng();
+            assert_eq!(random_len_with(5, Some(only_nine)).unwrap_err(), degenerate);
+            assert_eq!(
+                random_from_rng(5, "9", "9", only_nine, &mut rng).unwrap_err(),
+                degenerate
+            );
+            // A fixed digit that differs from the only drawable one breaks the pattern
+            assert_eq!(
+                random_from_rng(5, "1", "", only_nine, &mut rng).unwrap(),
+                generate("1999", None).unwrap()
+            );
+            assert!(random_from_rng(5, "", "8", only_nine, &mut rng).is_ok());
+            // A single non-zero digit is not degenerate, a single zero is
+            assert!(random_len_with(2, Some(only_nine)).is_ok());
+            let only_zero = RandomOptions {
+                excluded_digits: "123456789".parse().unwrap(),
+                reject_degenerate: true,
+                ..Default::default()
+            };
+            assert_eq!(random_len_with(2, Some(only_zero)).unwrap_err(), degenerate);
+            // Two drawable digits always allow a non-degenerate payload
+            let two = RandomOptions {
+                excluded_digits: "01234567".parse().unwrap(),
+                reject_degenerate: true,
+                ..Default::default()
+            };
+            for _ in 0..50 {
+                assert!(!is_degenerate(&random_len_with(4, Some(two)).unwrap()[..3]));
+            }
+        }
+
+        #[test]
+        fn test_digit_set() {
+            let set: DigitSet = "9100".parse().unwrap();
+            assert_eq!(set, DigitSet::EMPTY.with(0).with(1).with(9));
+            assert!(set.contains(9) && !set.contains(5) && !set.contains(10));
+            assert_eq!(DigitSet::EMPTY.with(10), DigitSet::EMPTY);
+            assert!("".parse::<DigitSet>().unwrap().is_empty());
+            assert_eq!(DigitSet::default(), DigitSet::EMPTY);
+            assert_eq!(
+                "0,1".parse::<DigitSet>().unwrap_err(),
+                LuhnError::NonNumeric {
+                    index: 1,
+                    character: ','
+                }
+            );
+            assert_eq!(
+                "0٣".parse::<DigitSet>().unwrap_err(),
+                LuhnError::NonNumeric {
+                    index: 1,
+                    character: '٣'
+                }
+            );
         }
 
         #[test]
@@ -462,4 +3470,50 @@ mod tests {
             }
         }
     }
+
+    #[cfg(feature = "std")]
+    mod random_seeded {
+        use super::*;
+
+        #[test]
+        fn test_reproducible() {
+            // Pinned outputs: changing these breaks users' snapshots
+            assert_eq!(random_seeded(11, 0).unwrap(), "50947030909");
+            assert_eq!(random_seeded(2, 42).unwrap(), "34");
+
+            for seed in [0, 1, 42, u64::MAX] {
+                let value = random_seeded(11, seed).unwrap();
+                assert_eq!(value, random_seeded(11, seed).unwrap());
+                assert!(validate(&value).unwrap());
+            }
+            assert_ne!(random_seeded(11, 1).unwrap(), random_seeded(11, 2).unwrap());
+        }
+
+        #[cfg(feature = "forbid-real-iins")]
+        #[test]
+        fn test_real_iins_rejected_not_redrawn() {
+            let results: Vec<_> = (0..100).map(|seed| random_seeded(16, seed)).collect();
+            assert!(results.contains(&Err(LuhnError::RealIssuerPrefix)));
+            for number in results.iter().flatten() {
+                assert!(!iin::is_real_iin(number));
+            }
+        }
+
+        #[test]
+        fn test_matches_fixtures() {
+            let fixture = Fixtures::<1, 11>::generate(9, "").unwrap();
+            assert_eq!(fixture.get(0).unwrap(), random_seeded(11, 9).unwrap());
+        }
+
+        #[test]
+        fn test_lengths() {
+            assert_eq!(random_seeded(100, 3).unwrap().len(), 100);
+            assert_eq!(random_seeded(1, 3).unwrap_err(), random_len_err(1));
+            assert_eq!(random_seeded(101, 3).unwrap_err(), random_len_err(101));
+        }
+
+        fn random_len_err(len: usize) -> LuhnError {
+            check_random_len(len, &RandomOptions::default()).unwrap_err()
+        }
+    }
 }
@@ -0,0 +1,241 @@
+//! Deadlines and cancellation for batch operations.
+//!
+//! A service handling one oversized request should not spend unbounded time on
+//! it. The `_until` variants of the batch, scanning and bulk-generation APIs take
+//! a [`Limit`] (a deadline, a [`CancelToken`], or both) and stop early when it is
+//! reached, returning the work done so far as a [`BatchOutcome::Interrupted`].
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How many items are processed between deadline checks. Reading the clock costs
+/// about as much as validating a short number, so it is not read for every item.
+const DEADLINE_INTERVAL: usize = 64;
+
+/// A flag shared between a running operation and whoever may cancel it.
+///
+/// Clones share the flag, so a clone can be handed to another thread and
+/// cancelled from there.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{validate_bitmap_until, BatchOutcome, CancelToken, Interruption, Limit};
+///
+/// let token = CancelToken::new();
+/// let limit = Limit { cancel: Some(token.clone()), ..Limit::default() };
+/// token.cancel();
+///
+/// let outcome = validate_bitmap_until(["79927398713"], &limit);
+/// assert_eq!(outcome.interruption(), Some(Interruption::Cancelled));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Returns a token that has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Asks every operation watching this token to stop.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true once [`CancelToken::cancel`] has been called on any clone.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// When a batch operation should stop early.
+///
+/// The default has neither a deadline nor a token and never interrupts.
+#[derive(Debug, Clone, Default)]
+pub struct Limit {
+    /// Stop once this instant has passed
+    pub deadline: Option<Instant>,
+    /// Stop once this token is cancelled
+    pub cancel: Option<CancelToken>,
+}
+
+impl Limit {
+    /// Returns a limit with a deadline `duration` from now.
+    ///
+    /// A duration too large to represent means no deadline.
+    pub fn timeout(duration: Duration) -> Self {
+        Limit {
+            deadline: Instant::now().checked_add(duration),
+            cancel: None,
+        }
+    }
+
+    /// Returns a checker that polls this limit once per item.
+    pub(crate) fn checker(&self) -> Checker<'_> {
+        Checker {
+            limit: self,
+            polls: 0,
+        }
+    }
+}
+
+/// Why a batch operation stopped early.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interruption {
+    /// The deadline passed
+    Deadline,
+    /// The cancel token was cancelled
+    Cancelled,
+}
+
+impl fmt::Display for Interruption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Interruption::Deadline => write!(f, "deadline exceeded"),
+            Interruption::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+/// The result of a batch operation run under a [`Limit`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BatchOutcome<T> {
+    /// Every item was processed
+    Complete(T),
+    /// The limit was reached before every item was processed
+    Interrupted {
+        /// The results for the items processed before the interruption
+        partial: T,
+        /// How many items were processed
+        completed: usize,
+        /// Why the operation stopped
+        reason: Interruption,
+    },
+}
+
+impl<T> BatchOutcome<T> {
+    /// Returns true if every item was processed.
+    pub fn is_complete(&self) -> bool {
+        matches!(self, BatchOutcome::Complete(_))
+    }
+
+    /// Returns why the operation stopped early, if it did.
+    pub fn interruption(&self) -> Option<Interruption> {
+        match self {
+            BatchOutcome::Complete(_) => None,
+            BatchOutcome::Interrupted { reason, .. } => Some(*reason),
+        }
+    }
+
+    /// Returns the results, complete or partial.
+    pub fn value(&self) -> &T {
+        match self {
+            BatchOutcome::Complete(value) => value,
+            BatchOutcome::Interrupted { partial, .. } => partial,
+        }
+    }
+
+    /// Unwraps the results, complete or partial.
+    pub fn into_inner(self) -> T {
+        match self {
+            BatchOutcome::Complete(value) => value,
+            BatchOutcome::Interrupted { partial, .. } => partial,
+        }
+    }
+
+    /// Wraps `value` as complete, or as interrupted after `completed` items.
+    pub(crate) fn new(value: T, completed: usize, interruption: Option<Interruption>) -> Self {
+        match interruption {
+            None => BatchOutcome::Complete(value),
+            Some(reason) => BatchOutcome::Interrupted {
+                partial: value,
+                completed,
+                reason,
+            },
+        }
+    }
+}
+
+/// Polls a [`Limit`] before each item of a batch.
+pub(crate) struct Checker<'a> {
+    limit: &'a Limit,
+    polls: usize,
+}
+
+impl Checker<'_> {
+    /// Returns why the batch must stop, if it must. The token is checked on every
+    /// call and the deadline on every [`DEADLINE_INTERVAL`]th, starting with the first.
+    pub(crate) fn poll(&mut self) -> Option<Interruption> {
+        let polls = self.polls;
+        self.polls = polls.wrapping_add(1);
+        if self
+            .limit
+            .cancel
+            .as_ref()
+            .map_or(false, CancelToken::is_cancelled)
+        {
+            return Some(Interruption::Cancelled);
+        }
+        match self.limit.deadline {
+            Some(deadline) if polls % DEADLINE_INTERVAL == 0 && Instant::now() >= deadline => {
+                Some(Interruption::Deadline)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_never_interrupts() {
+        let limit = Limit::default();
+        let mut checker = limit.checker();
+        assert!((0..1000).all(|_| checker.poll().is_none()));
+        assert!(Limit::timeout(Duration::MAX).deadline.is_none());
+    }
+
+    #[test]
+    fn test_cancel_token() {
+        let token = CancelToken::new();
+        let limit = Limit {
+            cancel: Some(token.clone()),
+            ..Limit::default()
+        };
+        let mut checker = limit.checker();
+        assert_eq!(checker.poll(), None);
+        token.clone().cancel();
+        assert!(token.is_cancelled());
+        assert_eq!(checker.poll(), Some(Interruption::Cancelled));
+    }
+
+    #[test]
+    fn test_deadline_checked_periodically() {
+        let limit = Limit {
+            deadline: Some(Instant::now()),
+            cancel: None,
+        };
+        let mut checker = limit.checker();
+        assert_eq!(checker.poll(), Some(Interruption::Deadline));
+        assert!((1..DEADLINE_INTERVAL).all(|_| checker.poll().is_none()));
+        assert_eq!(checker.poll(), Some(Interruption::Deadline));
+    }
+
+    #[test]
+    fn test_outcome_accessors() {
+        let complete = BatchOutcome::new(vec![1], 1, None);
+        assert!(complete.is_complete());
+        assert_eq!(complete.interruption(), None);
+        assert_eq!(complete.value(), &[1]);
+
+        let interrupted = BatchOutcome::new(vec![1, 2], 2, Some(Interruption::Deadline));
+        assert!(!interrupted.is_complete());
+        assert_eq!(interrupted.interruption(), Some(Interruption::Deadline));
+        assert_eq!(interrupted.into_inner(), [1, 2]);
+        assert_eq!(Interruption::Cancelled.to_string(), "cancelled");
+    }
+}
@@ -0,0 +1,280 @@
+//! Locale-aware digit grouping for displaying numeric identifiers.
+//!
+//! Identifiers that are not card numbers are often shown to users with the same
+//! digit grouping as ordinary numbers in their locale. [`format_grouped_locale`]
+//! renders canonical digits that way and [`parse_grouped_locale`] accepts the
+//! grouped form back, returning the canonical digits.
+
+use crate::{handle_errors, LuhnError};
+
+/// Digit grouping conventions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DigitGrouping {
+    /// Groups of three digits from the right, e.g. `12,345,678`.
+    #[default]
+    Thousands,
+    /// Indian lakh/crore grouping: the last three digits, then groups of two,
+    /// e.g. `1,23,45,678`.
+    Indian,
+}
+
+/// Configuration for locale-aware grouping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LocaleFormat {
+    /// The grouping convention to apply.
+    pub grouping: DigitGrouping,
+    /// The character placed between groups, e.g. `,`, `.` or `'`.
+    pub separator: char,
+}
+
+impl Default for LocaleFormat {
+    fn default() -> Self {
+        LocaleFormat {
+            grouping: DigitGrouping::Thousands,
+            separator: ',',
+        }
+    }
+}
+
+impl DigitGrouping {
+    /// Returns true if a separator belongs before the digit at `index` of a
+    /// string of `len` digits.
+    fn separator_before(self, index: usize, len: usize) -> bool {
+        if index == 0 {
+            return false;
+        }
+        let from_right = len - index;
+        match self {
+            DigitGrouping::Thousands => from_right % 3 == 0,
+            DigitGrouping::Indian => from_right == 3 || (from_right > 3 && from_right % 2 == 1),
+        }
+    }
+}
+
+/// Groups a canonical digit string according to a locale convention.
+///
+/// # Arguments
+/// * `value` - A string slice containing only digits
+/// * `format` - The grouping convention and separator to use
+///
+/// # Returns
+/// * `Ok(String)` - The grouped representation
+/// * `Err(LuhnError)` - Error if the input is not a canonical digit string
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{format_grouped_locale, DigitGrouping, LocaleFormat};
+///
+/// let thousands = LocaleFormat::default();
+/// assert_eq!(format_grouped_locale("12345678", thousands).unwrap(), "12,345,678");
+///
+/// let indian = LocaleFormat { grouping: DigitGrouping::Indian, separator: ',' };
+/// assert_eq!(format_grouped_locale("12345678", indian).unwrap(), "1,23,45,678");
+/// ```
+///
+/// # Errors
+/// Returns an error if the input fails the usual input checks (empty, spaces,
+/// non-numeric, ...).
+pub fn format_grouped_locale(value: &str, format: LocaleFormat) -> Result<String, LuhnError> {
+    handle_errors(value)?;
+
+    let len = value.len();
+    let mut grouped = String::with_capacity(len + len / 2);
+    for (index, c) in value.chars().enumerate() {
+        if format.grouping.separator_before(index, len) {
+            grouped.push(format.separator);
+        }
+        grouped.push(c);
+    }
+
+    Ok(grouped)
+}
+
+/// Parses a locale-grouped number back into canonical digits.
+///
+/// Separators must sit exactly where [`format_grouped_locale`] would place them;
+/// ungrouped input is accepted as-is.
+///
+/// # Arguments
+/// * `value` - The grouped (or ungrouped) representation
+/// * `format` - The grouping convention and separator to expect
+///
+/// # Returns
+/// * `Ok(String)` - The canonical digits
+/// * `Err(LuhnError)` - Error if the input is malformed
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{parse_grouped_locale, DigitGrouping, LocaleFormat};
+///
+/// let indian = LocaleFormat { grouping: DigitGrouping::Indian, separator: ',' };
+/// assert_eq!(parse_grouped_locale("1,23,45,678", indian).unwrap(), "12345678");
+/// assert!(parse_grouped_locale("12,345,678", indian).is_err());
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * The digits fail the usual input checks (empty, spaces, non-numeric, ...)
+/// * A separator is missing or misplaced for the configured grouping
+pub fn parse_grouped_locale(value: &str, format: LocaleFormat) -> Result<String, LuhnError> {
+    let mut digits = String::with_capacity(value.len());
+    // The byte offset in `value` of each byte of `digits`
+    let mut offsets = Vec::with_capacity(value.len());
+    for (offset, c) in value.char_indices() {
+        if c != format.separator {
+            digits.push(c);
+            offsets.extend(offset..offset + c.len_utf8());
+        }
+    }
+    // Report positions in the input as written, separators included
+    let original = |index: usize| offsets.get(index).copied().unwrap_or(index);
+    handle_errors(&digits).map_err(|e| match e {
+        LuhnError::ContainsSpaces { index, character } => LuhnError::ContainsSpaces {
+            index: original(index),
+            character,
+        },
+        LuhnError::ControlCharacter { index, character } => LuhnError::ControlCharacter {
+            index: original(index),
+            character,
+        },
+        LuhnError::NonNumeric { index, character } => LuhnError::NonNumeric {
+            index: original(index),
+            character,
+        },
+        other => other,
+    })?;
+
+    if digits.len() == value.chars().count() || format_grouped_locale(&digits, format)? == value {
+        Ok(digits)
+    } else {
+        Err(LuhnError::ParseError(
+            "group separators do not match the expected grouping".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INDIAN: LocaleFormat = LocaleFormat {
+        grouping: DigitGrouping::Indian,
+        separator: ',',
+    };
+
+    #[test]
+    fn test_format_thousands() {
+        let format = LocaleFormat::default();
+        assert_eq!(format_grouped_locale("1", format).unwrap(), "1");
+        assert_eq!(format_grouped_locale("123", format).unwrap(), "123");
+        assert_eq!(format_grouped_locale("1234", format).unwrap(), "1,234");
+        assert_eq!(format_grouped_locale("123456", format).unwrap(), "123,456");
+        assert_eq!(
+            format_grouped_locale("0012345", format).unwrap(),
+            "0,012,345"
+        );
+        let swiss = LocaleFormat {
+            separator: '\'',
+            ..format
+        };
+        assert_eq!(
+            format_grouped_locale("1234567", swiss).unwrap(),
+            "1'234'567"
+        );
+    }
+
+    #[test]
+    fn test_format_indian() {
+        assert_eq!(format_grouped_locale("123", INDIAN).unwrap(), "123");
+        assert_eq!(format_grouped_locale("1234", INDIAN).unwrap(), "1,234");
+        assert_eq!(format_grouped_locale("123456", INDIAN).unwrap(), "1,23,456");
+        assert_eq!(
+            format_grouped_locale("1234567890", INDIAN).unwrap(),
+            "1,23,45,67,890"
+        );
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        for format in [LocaleFormat::default(), INDIAN] {
+            for value in ["1", "12", "123", "1234", "79927398713", "0000012345"] {
+                let grouped = format_grouped_locale(value, format).unwrap();
+                assert_eq!(parse_grouped_locale(&grouped, format).unwrap(), value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_ungrouped() {
+        assert_eq!(
+            parse_grouped_locale("12345678", INDIAN).unwrap(),
+            "12345678"
+        );
+    }
+
+    #[test]
+    fn test_error_cases() {
+        let format = LocaleFormat::default();
+        assert_eq!(
+            format_grouped_locale("", format).unwrap_err(),
+            LuhnError::EmptyString
+        );
+        assert_eq!(
+            format_grouped_locale("12a", format).unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 2,
+                character: 'a'
+            }
+        );
+        assert_eq!(
+            parse_grouped_locale(",", format).unwrap_err(),
+            LuhnError::EmptyString
+        );
+        assert!(matches!(
+            parse_grouped_locale("12,34", format).unwrap_err(),
+            LuhnError::ParseError(_)
+        ));
+        assert!(matches!(
+            parse_grouped_locale("1,23,456", format).unwrap_err(),
+            LuhnError::ParseError(_)
+        ));
+    }
+
+    #[test]
+    fn test_parse_error_positions() {
+        let format = LocaleFormat::default();
+        assert_eq!(
+            parse_grouped_locale("12,345,6x8", format).unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 8,
+                character: 'x'
+            }
+        );
+        assert_eq!(
+            parse_grouped_locale("1,23,45 678", INDIAN).unwrap_err(),
+            LuhnError::ContainsSpaces {
+                index: 7,
+                character: ' '
+            }
+        );
+        assert_eq!(
+            parse_grouped_locale("1,234,56\r", format).unwrap_err(),
+            LuhnError::ControlCharacter {
+                index: 8,
+                character: '\r'
+            }
+        );
+        // Multi-byte separators and digits shift byte offsets too
+        let thin_space = LocaleFormat {
+            separator: '\u{202F}',
+            ..format
+        };
+        assert_eq!(
+            parse_grouped_locale("1\u{202F}2é4", thin_space).unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 5,
+                character: 'é'
+            }
+        );
+    }
+}
@@ -0,0 +1,421 @@
+//! A reusable, pre-configured validator and generator.
+//!
+//! When the validation policy is fixed application-wide, passing option structs to
+//! every call is clumsy. [`Luhn`] captures the options once, via [`LuhnBuilder`], and
+//! applies them on every [`Luhn::validate`] and [`Luhn::generate`] call.
+//!
+//! A [`Sanitizer`] can be registered to rewrite raw input (strip a country prefix,
+//! drop a known suffix, ...) before any of the built-in checks run.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use crate::{
+    generate, prepare_generate, validate_with, CheckDigit, GenerateOptions, LuhnError, Parity,
+    ValidateOptions,
+};
+
+type SanitizeFn = dyn for<'a> Fn(&'a str) -> Cow<'a, str> + Send + Sync;
+
+/// A pre-validation hook that transforms raw input before the built-in checks run.
+///
+/// Sanitizers compare equal (and hash) by identity: two [`Luhn`]s are equal only if
+/// they share the same registered sanitizer.
+///
+/// # Examples
+/// ```
+/// use std::borrow::Cow;
+/// use luhn_tools::Sanitizer;
+///
+/// let strip_prefix = Sanitizer::new(|s: &str| Cow::Borrowed(s.strip_prefix("ID").unwrap_or(s)));
+/// assert_eq!(strip_prefix.apply("ID79927398713"), "79927398713");
+/// ```
+#[derive(Clone)]
+pub struct Sanitizer(Arc<SanitizeFn>);
+
+impl Sanitizer {
+    /// Wraps a closure or function as a sanitizer.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: for<'a> Fn(&'a str) -> Cow<'a, str> + Send + Sync + 'static,
+    {
+        Sanitizer(Arc::new(f))
+    }
+
+    /// Runs the sanitizer on `value`.
+    pub fn apply<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        (self.0)(value)
+    }
+
+    fn addr(&self) -> *const () {
+        Arc::as_ptr(&self.0) as *const ()
+    }
+}
+
+impl fmt::Debug for Sanitizer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Sanitizer").field(&self.addr()).finish()
+    }
+}
+
+impl PartialEq for Sanitizer {
+    fn eq(&self, other: &Self) -> bool {
+        self.addr() == other.addr()
+    }
+}
+
+impl Eq for Sanitizer {}
+
+impl Hash for Sanitizer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.addr().hash(state);
+    }
+}
+
+/// A Luhn validator and generator with a fixed configuration.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::Luhn;
+///
+/// let cards = Luhn::builder()
+///     .allow_separators(true)
+///     .min_len(12)
+///     .max_len(19)
+///     .build();
+///
+/// assert!(cards.validate("4111 1111 1111 1111").unwrap());
+/// assert!(cards.validate("79927398713").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Luhn {
+    validate_options: ValidateOptions,
+    generate_options: GenerateOptions,
+    sanitizer: Option<Sanitizer>,
+}
+
+impl Luhn {
+    /// Returns a builder with the default (strict) configuration.
+    pub fn builder() -> LuhnBuilder {
+        LuhnBuilder::default()
+    }
+
+    /// Returns the options applied by [`Luhn::validate`].
+    pub fn validate_options(&self) -> ValidateOptions {
+        self.validate_options
+    }
+
+    /// Returns the options applied by [`Luhn::generate`].
+    pub fn generate_options(&self) -> GenerateOptions {
+        self.generate_options
+    }
+
+    /// Returns the registered sanitizer, if any.
+    pub fn sanitizer(&self) -> Option<&Sanitizer> {
+        self.sanitizer.as_ref()
+    }
+
+    /// Validates a number using the configured sanitizer and options.
+    ///
+    /// See [`validate_with`](crate::validate_with) for details and errors.
+    pub fn validate(&self, value: &str) -> Result<bool, LuhnError> {
+        validate_with(&self.sanitize(value), Some(self.validate_options))
+    }
+
+    /// Generates a Luhn number or checksum using the configured sanitizer and options.
+    ///
+    /// See [`generate`](crate::generate) for details and errors.
+    pub fn generate(&self, value: &str) -> Result<String, LuhnError> {
+        generate(&self.sanitize(value), Some(self.generate_options))
+    }
+
+    fn sanitize<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        match &self.sanitizer {
+            Some(sanitizer) => sanitizer.apply(value),
+            None => Cow::Borrowed(value),
+        }
+    }
+}
+
+/// Check digits under the configured sanitizer and parity.
+///
+/// `validate` is [`Luhn::validate`], and `compute` and `append` check and prepare
+/// the payload as [`Luhn::generate`] does, with all the configured options. Only
+/// the display options (grouping and `checksum_only`) are ignored: `compute`
+/// always returns the bare check digit, and `append` the payload followed by it.
+impl CheckDigit for Luhn {
+    fn compute(&self, payload: &str) -> Result<String, LuhnError> {
+        let (_, check) = prepare_generate(&self.sanitize(payload), &self.generate_options)?;
+        Ok(check.to_string())
+    }
+
+    fn validate(&self, value: &str) -> Result<bool, LuhnError> {
+        Luhn::validate(self, value)
+    }
+
+    fn append(&self, payload: &str) -> Result<String, LuhnError> {
+        let payload = self.sanitize(payload);
+        let (payload, check) = prepare_generate(&payload, &self.generate_options)?;
+        Ok(format!("{}{}", payload, check))
+    }
+}
+
+/// Builder for [`Luhn`].
+///
+/// Settings that apply to both validation and generation (underscores, degenerate
+/// payloads, parity) configure both; the rest apply to one operation only.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct LuhnBuilder {
+    validate_options: ValidateOptions,
+    generate_options: GenerateOptions,
+    sanitizer: Option<Sanitizer>,
+}
+
+impl LuhnBuilder {
+    /// Strips spaces and dashes before validation.
+    pub fn allow_separators(mut self, allow: bool) -> Self {
+        self.validate_options.allow_separators = allow;
+        self
+    }
+
+    /// Strips phone-style punctuation (spaces, dashes, dots, parentheses, plus
+    /// signs) before validation.
+    pub fn phone_format(mut self, allow: bool) -> Self {
+        self.validate_options.phone_format = allow;
+        self
+    }
+
+    /// Trims leading and trailing whitespace before validation.
+    pub fn trim_whitespace(mut self, trim: bool) -> Self {
+        self.validate_options.trim_whitespace = trim;
+        self
+    }
+
+    /// Strips control characters, NULs and byte order marks before validation.
+    pub fn strip_control(mut self, strip: bool) -> Self {
+        self.validate_options.strip_control = strip;
+        self
+    }
+
+    /// Strips underscore separators before validation and generation.
+    pub fn allow_underscores(mut self, allow: bool) -> Self {
+        self.validate_options.allow_underscores = allow;
+        self.generate_options.allow_underscores = allow;
+        self
+    }
+
+    /// Converts non-ASCII decimal digits to ASCII before validation.
+    pub fn unicode_digits(mut self, convert: bool) -> Self {
+        self.validate_options.unicode_digits = convert;
+        self
+    }
+
+    /// Sets the minimum number of digits accepted by validation.
+    pub fn min_len(mut self, min_len: usize) -> Self {
+        self.validate_options.min_len = Some(min_len);
+        self
+    }
+
+    /// Sets the maximum number of digits accepted by validation.
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.validate_options.max_len = Some(max_len);
+        self
+    }
+
+    /// Rejects degenerate payloads in validation and generation.
+    pub fn reject_degenerate(mut self, reject: bool) -> Self {
+        self.validate_options.reject_degenerate = reject;
+        self.generate_options.reject_degenerate = reject;
+        self
+    }
+
+    /// Rejects repeated-digit and sequential numbers in validation.
+    pub fn reject_patterned(mut self, reject: bool) -> Self {
+        self.validate_options.reject_patterned = reject;
+        self
+    }
+
+    /// Sets which digits are doubled, for both validation and generation.
+    pub fn parity(mut self, parity: Parity) -> Self {
+        self.validate_options.parity = parity;
+        self.generate_options.parity = parity;
+        self
+    }
+
+    /// Makes generation return only the check digit.
+    pub fn checksum_only(mut self, checksum_only: bool) -> Self {
+        self.generate_options.checksum_only = checksum_only;
+        self
+    }
+
+    /// Groups generated numbers into blocks of `size` digits joined by `separator`.
+    pub fn group(mut self, size: usize, separator: char) -> Self {
+        self.generate_options.group_size = Some(size);
+        self.generate_options.group_separator = separator;
+        self
+    }
+
+    /// Left-pads generated numbers with zeros to `width` digits.
+    pub fn pad_to(mut self, width: usize) -> Self {
+        self.generate_options.pad_to = Some(width);
+        self
+    }
+
+    /// Registers a hook that rewrites raw input before validation and generation.
+    ///
+    /// Replaces any previously registered sanitizer.
+    pub fn sanitizer<F>(mut self, f: F) -> Self
+    where
+        F: for<'a> Fn(&'a str) -> Cow<'a, str> + Send + Sync + 'static,
+    {
+        self.sanitizer = Some(Sanitizer::new(f));
+        self
+    }
+
+    /// Builds the configured [`Luhn`].
+    pub fn build(self) -> Luhn {
+        Luhn {
+            validate_options: self.validate_options,
+            generate_options: self.generate_options,
+            sanitizer: self.sanitizer,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_matches_free_functions() {
+        let luhn = Luhn::builder().build();
+        assert_eq!(luhn, Luhn::default());
+        assert!(luhn.validate("79927398713").unwrap());
+        assert_eq!(luhn.generate("7992739871").unwrap(), "79927398713");
+        assert_eq!(
+            luhn.validate("7992 7398 713").unwrap_err(),
+            LuhnError::ContainsSpaces {
+                index: 4,
+                character: ' '
+            }
+        );
+    }
+
+    #[test]
+    fn test_check_digit_trait() {
+        let luhn = Luhn::builder()
+            .sanitizer(|s: &str| Cow::Borrowed(s.strip_prefix("ID").unwrap_or(s)))
+            .checksum_only(true)
+            .build();
+        assert_eq!(CheckDigit::compute(&luhn, "ID7992739871").unwrap(), "3");
+        assert_eq!(
+            CheckDigit::append(&luhn, "ID7992739871").unwrap(),
+            "79927398713"
+        );
+        assert!(CheckDigit::validate(&luhn, "ID79927398713").unwrap());
+
+        let left = Luhn::builder().parity(Parity::FromLeft).build();
+        let number = CheckDigit::append(&left, "7992739871").unwrap();
+        assert!(left.validate(&number).unwrap());
+    }
+
+    #[test]
+    fn test_check_digit_trait_matches_generate() {
+        let luhn = Luhn::builder()
+            .allow_underscores(true)
+            .reject_degenerate(true)
+            .pad_to(12)
+            .group(4, ' ')
+            .build();
+        assert_eq!(luhn.generate("7992_7398_71").unwrap(), "0799 2739 8713");
+        assert_eq!(
+            CheckDigit::append(&luhn, "7992_7398_71").unwrap(),
+            "079927398713"
+        );
+        assert_eq!(CheckDigit::compute(&luhn, "7992_7398_71").unwrap(), "3");
+        for payload in ["00", "0_0"] {
+            assert_eq!(
+                CheckDigit::compute(&luhn, payload).unwrap_err(),
+                LuhnError::DegeneratePayload
+            );
+            assert_eq!(
+                CheckDigit::append(&luhn, payload).unwrap_err(),
+                luhn.generate(payload).unwrap_err()
+            );
+        }
+    }
+
+    #[test]
+    fn test_validation_policy() {
+        let luhn = Luhn::builder()
+            .allow_separators(true)
+            .trim_whitespace(true)
+            .min_len(12)
+            .max_len(19)
+            .build();
+        assert!(luhn.validate(" 4111-1111-1111-1111\n").unwrap());
+        assert_eq!(
+            luhn.validate("79927398713").unwrap_err(),
+            LuhnError::InvalidLength("string must be between 12 and 19 characters".to_string())
+        );
+        // Validation-only settings leave generation untouched
+        assert_eq!(luhn.generate_options(), GenerateOptions::default());
+    }
+
+    #[test]
+    fn test_generation_policy() {
+        let luhn = Luhn::builder().pad_to(12).group(4, '-').build();
+        assert_eq!(luhn.generate("12345").unwrap(), "0000-0012-3455");
+
+        let checksum = Luhn::builder().checksum_only(true).build();
+        assert_eq!(checksum.generate("7992739871").unwrap(), "3");
+    }
+
+    #[test]
+    fn test_shared_settings() {
+        let luhn = Luhn::builder()
+            .allow_underscores(true)
+            .reject_degenerate(true)
+            .build();
+        assert!(luhn.validate("7992_7398_713").unwrap());
+        assert_eq!(luhn.generate("7992_7398_71").unwrap(), "79927398713");
+        assert_eq!(
+            luhn.validate("00").unwrap_err(),
+            LuhnError::DegeneratePayload
+        );
+        assert_eq!(
+            luhn.generate("00").unwrap_err(),
+            LuhnError::DegeneratePayload
+        );
+    }
+
+    #[test]
+    fn test_sanitizer() {
+        let luhn = Luhn::builder()
+            .sanitizer(|s| Cow::Borrowed(s.strip_prefix("+44").unwrap_or(s)))
+            .allow_separators(true)
+            .build();
+        assert!(luhn.validate("+447992-7398-713").unwrap());
+        assert!(luhn.validate("79927398713").unwrap());
+        assert_eq!(luhn.generate("+447992739871").unwrap(), "79927398713");
+        // Built-in checks still run on the sanitized value
+        assert_eq!(
+            luhn.validate("+447992.7398.713").unwrap_err(),
+            LuhnError::FloatingPoint
+        );
+    }
+
+    #[test]
+    fn test_sanitizer_identity() {
+        let luhn = Luhn::builder()
+            .sanitizer(|s| Cow::Owned(s.replace('/', "")))
+            .build();
+        assert!(luhn.validate("7992/7398/713").unwrap());
+        assert_eq!(luhn.clone(), luhn);
+        assert_ne!(luhn, Luhn::default());
+        assert!(luhn.sanitizer().is_some());
+        assert!(Luhn::default().sanitizer().is_none());
+    }
+}
@@ -0,0 +1,245 @@
+//! ISO/IEC 7064 MOD 11-2.
+//!
+//! MOD 11-2 works modulo 11, so its check character is a digit or `X` (for 10).
+//! ORCID and ISNI identifiers use it: 15 digits followed by the check character.
+//!
+//! # Examples
+//! ```
+//! use luhn_tools::mod11_2;
+//!
+//! assert_eq!(mod11_2::generate("000000021825009").unwrap(), "0000000218250097");
+//! assert_eq!(mod11_2::checksum("000000021694233").unwrap(), 'X');
+//! assert!(mod11_2::validate("000000021694233X").unwrap());
+//! ```
+
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use crate::{handle_errors, CheckDigit, LuhnError};
+
+/// Length of an ORCID iD or ISNI without hyphens, check character included.
+const ORCID_LEN: usize = 16;
+
+/// ISO/IEC 7064 MOD 11-2 as a [`CheckDigit`] implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Mod11_2;
+
+impl CheckDigit for Mod11_2 {
+    fn compute(&self, payload: &str) -> Result<String, LuhnError> {
+        checksum(payload).map(String::from)
+    }
+
+    fn validate(&self, value: &str) -> Result<bool, LuhnError> {
+        validate(value)
+    }
+}
+
+/// Computes the MOD 11-2 check character for a payload: `'0'`–`'9'` or `'X'`.
+///
+/// # Errors
+/// Returns an error if the payload fails the usual input checks (empty, spaces,
+/// non-numeric, ...).
+pub fn checksum(payload: &str) -> Result<char, LuhnError> {
+    handle_errors(payload)?;
+    let remainder = payload
+        .bytes()
+        .fold(0, |p, byte| (p + u32::from(byte - b'0')) * 2 % 11);
+    Ok(match (12 - remainder) % 11 {
+        10 => 'X',
+        check => char::from(b'0' + check as u8),
+    })
+}
+
+/// Appends the MOD 11-2 check character to a payload.
+///
+/// # Errors
+/// Returns the same errors as [`checksum`].
+pub fn generate(payload: &str) -> Result<String, LuhnError> {
+    Ok(format!("{}{}", payload, checksum(payload)?))
+}
+
+/// Validates a number whose last character is its MOD 11-2 check character.
+///
+/// The payload must be digits; the check character may also be an upper case `X`.
+///
+/// # Errors
+/// Returns the same errors as [`checksum`] for the payload, `NonNumeric` if the
+/// check character is neither a digit nor `X`, and `InvalidLength` if the input is
+/// only one character long.
+pub fn validate(value: &str) -> Result<bool, LuhnError> {
+    let (index, check) = value
+        .char_indices()
+        .next_back()
+        .ok_or(LuhnError::EmptyString)?;
+    let payload = value.get(..index).unwrap_or_default();
+    if payload.is_empty() {
+        return Err(LuhnError::InvalidLength(
+            "string must be longer than 1 character".to_string(),
+        ));
+    }
+    let expected = checksum(payload)?;
+    if check != 'X' && !check.is_ascii_digit() {
+        // The payload passed, so the usual checks report the check character
+        handle_errors(value)?;
+        return Err(LuhnError::NonNumeric {
+            index,
+            character: check,
+        });
+    }
+    Ok(check == expected)
+}
+
+/// Validates an ORCID iD, with or without the standard hyphens.
+///
+/// ORCID iDs are 16 characters, written in groups of four as
+/// `0000-0002-1825-0097`. The same format and check apply to ISNIs.
+///
+/// # Arguments
+/// * `value` - The iD, as 16 characters or as four hyphen-separated groups of four
+///
+/// # Returns
+/// * `Ok(true)` - If the check character is correct
+/// * `Ok(false)` - If it is not
+/// * `Err(LuhnError)` - If the input is invalid
+///
+/// # Errors
+/// * `InvalidLength` - If `value` is not 16 characters, or 19 with hyphens in the
+///   standard places
+/// * The same errors as [`validate`] for the characters themselves
+///
+/// # Examples
+/// ```
+/// use luhn_tools::validate_orcid;
+///
+/// assert!(validate_orcid("0000-0002-1825-0097").unwrap());
+/// assert!(validate_orcid("0000000218250097").unwrap());
+/// assert!(validate_orcid("0000-0002-1694-233X").unwrap());
+/// assert!(!validate_orcid("0000-0002-1825-0098").unwrap());
+/// assert!(validate_orcid("0000-00021825-0097").is_err());
+/// ```
+pub fn validate_orcid(value: &str) -> Result<bool, LuhnError> {
+    let hyphenated = value.len() == ORCID_LEN + 3
+        && value
+            .char_indices()
+            .all(|(i, c)| (c == '-') == (i % 5 == 4));
+    if hyphenated {
+        let digits: String = value.chars().filter(|&c| c != '-').collect();
+        // Report positions in the hyphenated input: one hyphen per preceding group
+        validate(&digits).map_err(|e| match e {
+            LuhnError::NonNumeric { index, character } => LuhnError::NonNumeric {
+                index: index + index / 4,
+                character,
+            },
+            LuhnError::ContainsSpaces { index, character } => LuhnError::ContainsSpaces {
+                index: index + index / 4,
+                character,
+            },
+            other => other,
+        })
+    } else if value.len() == ORCID_LEN && !value.contains('-') {
+        validate(value)
+    } else {
+        Err(LuhnError::InvalidLength(
+            "ORCID iD must be 16 characters, optionally hyphenated as XXXX-XXXX-XXXX-XXXX"
+                .to_string(),
+        ))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_values() {
+        // ORCID's documented examples
+        assert_eq!(checksum("000000021825009").unwrap(), '7');
+        assert_eq!(checksum("000000021694233").unwrap(), 'X');
+        assert!(validate("0000000218250097").unwrap());
+        assert!(!validate("0000000218250098").unwrap());
+    }
+
+    #[test]
+    fn test_x_check_character() {
+        assert!(validate("000000021694233X").unwrap());
+        assert!(!validate("000000021825009X").unwrap());
+        // Only the check character can be X, and only in upper case
+        assert_eq!(
+            validate("000000021694233x").unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 15,
+                character: 'x'
+            }
+        );
+        assert_eq!(
+            validate("00000002169423X3").unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 14,
+                character: 'X'
+            }
+        );
+    }
+
+    #[test]
+    fn test_check_digit_trait() {
+        assert_eq!(Mod11_2.compute("000000021694233").unwrap(), "X");
+        assert_eq!(
+            Mod11_2.append("000000021825009").unwrap(),
+            "0000000218250097"
+        );
+        assert!(CheckDigit::validate(&Mod11_2, "000000021694233X").unwrap());
+    }
+
+    #[test]
+    fn test_orcid_formats() {
+        assert!(validate_orcid("0000-0002-1825-0097").unwrap());
+        assert!(validate_orcid("0000000218250097").unwrap());
+        let message =
+            "ORCID iD must be 16 characters, optionally hyphenated as XXXX-XXXX-XXXX-XXXX";
+        for bad in [
+            "",
+            "000000021825009",
+            "0000-0002-1825-00970",
+            "00000-002-1825-0097",
+            "0000-0002-1825-009-",
+            "000000021825-0097",
+        ] {
+            assert_eq!(
+                validate_orcid(bad).unwrap_err(),
+                LuhnError::InvalidLength(message.to_string()),
+                "{bad}"
+            );
+        }
+        assert_eq!(
+            validate_orcid("0000-0002-18a5-0097").unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 12,
+                character: 'a'
+            }
+        );
+        assert_eq!(
+            validate_orcid("0000-0002-1825-009 ").unwrap_err(),
+            LuhnError::ContainsSpaces {
+                index: 18,
+                character: ' '
+            }
+        );
+    }
+
+    #[test]
+    fn test_error_cases() {
+        assert_eq!(checksum("").unwrap_err(), LuhnError::EmptyString);
+        assert_eq!(validate("").unwrap_err(), LuhnError::EmptyString);
+        assert_eq!(
+            validate("X").unwrap_err(),
+            LuhnError::InvalidLength("string must be longer than 1 character".to_string())
+        );
+        assert_eq!(
+            validate("123é").unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 3,
+                character: 'é'
+            }
+        );
+    }
+}
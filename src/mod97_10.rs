@@ -0,0 +1,121 @@
+//! ISO/IEC 7064 MOD 97-10.
+//!
+//! MOD 97-10 appends two check digits, which catch every single substitution and
+//! transposition and almost all other errors. It is the check behind IBANs and
+//! legal entity identifiers (LEIs).
+//!
+//! # Examples
+//! ```
+//! use luhn_tools::mod97_10;
+//!
+//! assert_eq!(mod97_10::checksum("794").unwrap(), "44");
+//! assert_eq!(mod97_10::generate("794").unwrap(), "79444");
+//! assert!(mod97_10::validate("79444").unwrap());
+//! ```
+
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use crate::{handle_errors, CheckDigit, LuhnError};
+
+/// ISO/IEC 7064 MOD 97-10 as a [`CheckDigit`] implementation, with a two-digit
+/// check value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Mod97_10;
+
+impl CheckDigit for Mod97_10 {
+    fn compute(&self, payload: &str) -> Result<String, LuhnError> {
+        checksum(payload)
+    }
+
+    fn check_len(&self) -> usize {
+        2
+    }
+
+    fn validate(&self, value: &str) -> Result<bool, LuhnError> {
+        validate(value)
+    }
+}
+
+/// Computes the two MOD 97-10 check digits for a payload, `"02"` to `"98"`.
+///
+/// # Errors
+/// Returns an error if the payload fails the usual input checks (empty, spaces,
+/// non-numeric, ...).
+pub fn checksum(payload: &str) -> Result<String, LuhnError> {
+    handle_errors(payload)?;
+    let remainder = payload
+        .bytes()
+        .fold(0, |r, byte| (r * 10 + u32::from(byte - b'0')) % 97);
+    // The check digits take the place of two trailing zeros
+    Ok(format!("{:02}", 98 - remainder * 100 % 97))
+}
+
+/// Appends the MOD 97-10 check digits to a payload.
+///
+/// # Errors
+/// Returns the same errors as [`checksum`].
+pub fn generate(payload: &str) -> Result<String, LuhnError> {
+    Ok(format!("{}{}", payload, checksum(payload)?))
+}
+
+/// Validates a number whose last two digits are its MOD 97-10 check digits.
+///
+/// # Errors
+/// Returns the usual input errors (empty, spaces, non-numeric, ...) and
+/// `InvalidLength` if the input has no digits before the check digits.
+pub fn validate(value: &str) -> Result<bool, LuhnError> {
+    handle_errors(value)?;
+    match value.len().checked_sub(2) {
+        Some(end) if end > 0 => {
+            let (payload, check) = value.split_at(end);
+            Ok(checksum(payload)? == check)
+        }
+        _ => Err(LuhnError::InvalidLength(
+            "string must be longer than 2 characters".to_string(),
+        )),
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_values() {
+        // The worked example of ISO/IEC 7064
+        assert_eq!(checksum("794").unwrap(), "44");
+        assert!(validate("79444").unwrap());
+        assert!(!validate("79445").unwrap());
+        assert!(!validate("74944").unwrap());
+        assert_eq!(checksum("1").unwrap(), "95");
+        assert_eq!(checksum("0").unwrap(), "98");
+    }
+
+    #[test]
+    fn test_check_digit_trait() {
+        assert_eq!(Mod97_10.check_len(), 2);
+        assert_eq!(Mod97_10.compute("794").unwrap(), "44");
+        assert_eq!(Mod97_10.append("794").unwrap(), "79444");
+        assert!(CheckDigit::validate(&Mod97_10, "79444").unwrap());
+    }
+
+    #[test]
+    fn test_error_cases() {
+        assert_eq!(checksum("").unwrap_err(), LuhnError::EmptyString);
+        assert_eq!(validate("").unwrap_err(), LuhnError::EmptyString);
+        for short in ["4", "44"] {
+            assert_eq!(
+                validate(short).unwrap_err(),
+                LuhnError::InvalidLength("string must be longer than 2 characters".to_string())
+            );
+        }
+        assert_eq!(
+            validate("79a44").unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 2,
+                character: 'a'
+            }
+        );
+    }
+}
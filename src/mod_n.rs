@@ -0,0 +1,407 @@
+//! The Luhn mod N algorithm for alphanumeric identifiers.
+//!
+//! Luhn mod N applies the Luhn doubling to the positions of characters in a base-N
+//! alphabet instead of to decimal digits, so codes mixing digits and letters get a
+//! check character from the same alphabet. With `n = 10` it is exactly the Luhn
+//! algorithm. The built-in alphabet is `0-9` followed by `A-Z`, truncated to `n`
+//! characters; letters are upper case. Other alphabets, such as Crockford's base 32,
+//! are supplied as an [`Alphabet`].
+
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use crate::LuhnError;
+
+/// Digits then upper case letters; base `n` uses the first `n` characters.
+const BASE36: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// An ordered set of characters for Luhn mod N, where N is the alphabet's size.
+///
+/// A character's position in the alphabet is its value, and check characters are
+/// always drawn from the alphabet.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{generate_mod_n_with, validate_mod_n_with, Alphabet, LuhnError};
+///
+/// let code = generate_mod_n_with("7ZJ4KM", &Alphabet::CROCKFORD).unwrap();
+/// assert!(validate_mod_n_with(&code, &Alphabet::CROCKFORD).unwrap());
+///
+/// let vowels = Alphabet::new("AEIOU").unwrap();
+/// assert_eq!(vowels.base(), 5);
+/// assert_eq!(
+///     Alphabet::new("ABCA").unwrap_err(),
+///     LuhnError::InvalidConfiguration("alphabet cannot contain 'A' more than once".to_string())
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Alphabet<'a>(&'a str);
+
+impl Alphabet<'static> {
+    /// Crockford's base 32: digits and upper case letters without I, L, O and U.
+    pub const CROCKFORD: Alphabet<'static> = Alphabet("0123456789ABCDEFGHJKMNPQRSTVWXYZ");
+
+    /// Digits then upper case letters, the alphabet [`generate_mod_n`] uses for base 36.
+    pub const BASE36: Alphabet<'static> = Alphabet(BASE36);
+}
+
+impl<'a> Alphabet<'a> {
+    /// Creates an alphabet from its characters, in value order.
+    ///
+    /// # Errors
+    /// Returns `InvalidConfiguration` if the alphabet has fewer than two
+    /// characters, repeats a character, or contains whitespace.
+    pub fn new(chars: &'a str) -> Result<Self, LuhnError> {
+        if chars.chars().nth(1).is_none() {
+            return Err(LuhnError::InvalidConfiguration(
+                "alphabet must have at least 2 characters".to_string(),
+            ));
+        }
+        for (index, c) in chars.char_indices() {
+            if c.is_whitespace() {
+                return Err(LuhnError::InvalidConfiguration(
+                    "alphabet cannot contain whitespace".to_string(),
+                ));
+            }
+            let rest = chars.get(index + c.len_utf8()..).unwrap_or_default();
+            if rest.contains(c) {
+                return Err(LuhnError::InvalidConfiguration(format!(
+                    "alphabet cannot contain {:?} more than once",
+                    c
+                )));
+            }
+        }
+        Ok(Alphabet(chars))
+    }
+
+    /// Returns the characters, in value order.
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+
+    /// Returns N, the number of characters.
+    pub fn base(&self) -> u32 {
+        self.0.chars().count() as u32
+    }
+}
+
+/// Appends a Luhn mod N check character to a base-`n` value.
+///
+/// # Arguments
+/// * `value` - The payload, using the first `n` characters of `0-9A-Z`
+/// * `n` - The base, from 2 to 36
+///
+/// # Returns
+/// * `Ok(String)` - The payload followed by its check character
+/// * `Err(LuhnError)` - If the input is invalid
+///
+/// # Errors
+/// * `InvalidConfiguration` - If `n` is outside 2–36
+/// * `EmptyString` - If `value` is empty
+/// * `ContainsSpaces` - If `value` contains whitespace
+/// * `NonNumeric` - If `value` contains a character outside the base-`n` alphabet
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{generate_mod_n, validate_mod_n};
+///
+/// let voucher = generate_mod_n("K7QX92", 36).unwrap();
+/// assert_eq!(voucher, "K7QX924");
+/// assert!(validate_mod_n(&voucher, 36).unwrap());
+///
+/// // Base 10 is plain Luhn
+/// assert_eq!(generate_mod_n("7992739871", 10).unwrap(), "79927398713");
+/// ```
+pub fn generate_mod_n(value: &str, n: u32) -> Result<String, LuhnError> {
+    let alphabet = builtin_alphabet(n)?;
+    let check = check_character(value, alphabet)?;
+    Ok(format!("{}{}", value, check))
+}
+
+/// Validates a base-`n` value whose last character is its Luhn mod N check
+/// character.
+///
+/// # Arguments
+/// * `value` - The value to validate, check character included
+/// * `n` - The base, from 2 to 36
+///
+/// # Returns
+/// * `Ok(true)` - If the check character is correct
+/// * `Ok(false)` - If it is not
+/// * `Err(LuhnError)` - If the input is invalid
+///
+/// # Errors
+/// The same errors as [`generate_mod_n`], plus `InvalidLength` if `value` is only
+/// one character long.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::validate_mod_n;
+///
+/// assert!(validate_mod_n("K7QX924", 36).unwrap());
+/// assert!(!validate_mod_n("K7XQ924", 36).unwrap());
+/// assert!(validate_mod_n("k7qx924", 36).is_err());
+/// ```
+pub fn validate_mod_n(value: &str, n: u32) -> Result<bool, LuhnError> {
+    let alphabet = builtin_alphabet(n)?;
+    validate_in(value, alphabet)
+}
+
+/// Appends a Luhn mod N check character to a value over a custom alphabet.
+///
+/// # Arguments
+/// * `value` - The payload, using only characters from `alphabet`
+/// * `alphabet` - The characters and their order
+///
+/// # Returns
+/// * `Ok(String)` - The payload followed by its check character
+/// * `Err(LuhnError)` - If the input is invalid
+///
+/// # Errors
+/// The same errors as [`generate_mod_n`], except that the alphabet was already
+/// checked by [`Alphabet::new`].
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{generate_mod_n_with, Alphabet};
+///
+/// assert_eq!(generate_mod_n_with("7ZJ4KM", &Alphabet::CROCKFORD).unwrap(), "7ZJ4KM4");
+/// // Crockford's alphabet has no 'U'
+/// assert!(generate_mod_n_with("7ZJ4KU", &Alphabet::CROCKFORD).is_err());
+/// ```
+pub fn generate_mod_n_with(value: &str, alphabet: &Alphabet) -> Result<String, LuhnError> {
+    let check = check_character(value, alphabet.0)?;
+    Ok(format!("{}{}", value, check))
+}
+
+/// Validates a value over a custom alphabet whose last character is its Luhn mod N
+/// check character.
+///
+/// # Returns
+/// * `Ok(true)` - If the check character is correct
+/// * `Ok(false)` - If it is not
+/// * `Err(LuhnError)` - If the input is invalid
+///
+/// # Errors
+/// The same errors as [`validate_mod_n`]. A check character outside the alphabet
+/// is reported as `NonNumeric`, like any other.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{validate_mod_n_with, Alphabet};
+///
+/// assert!(validate_mod_n_with("7ZJ4KM4", &Alphabet::CROCKFORD).unwrap());
+/// assert!(!validate_mod_n_with("7ZJ4KM5", &Alphabet::CROCKFORD).unwrap());
+/// ```
+pub fn validate_mod_n_with(value: &str, alphabet: &Alphabet) -> Result<bool, LuhnError> {
+    validate_in(value, alphabet.0)
+}
+
+/// Returns the first `n` characters of [`BASE36`].
+fn builtin_alphabet(n: u32) -> Result<&'static str, LuhnError> {
+    match BASE36.get(..n as usize) {
+        Some(alphabet) if n >= 2 => Ok(alphabet),
+        _ => Err(LuhnError::InvalidConfiguration(
+            "n must be between 2 and 36".to_string(),
+        )),
+    }
+}
+
+/// Computes the check character for `payload` over `alphabet`.
+pub(crate) fn check_character(payload: &str, alphabet: &str) -> Result<char, LuhnError> {
+    check_symbols(payload, alphabet)?;
+    let n = alphabet.chars().count() as u32;
+    let sum = sum_mod_n(payload, alphabet, true);
+    let index = ((n - sum) % n) as usize;
+    alphabet
+        .chars()
+        .nth(index)
+        .ok_or(LuhnError::InvalidConfiguration(
+            "alphabet must not be empty".to_string(),
+        ))
+}
+
+/// Validates `value`, check character included, over `alphabet`.
+pub(crate) fn validate_in(value: &str, alphabet: &str) -> Result<bool, LuhnError> {
+    check_symbols(value, alphabet)?;
+    if value.chars().nth(1).is_none() {
+        return Err(LuhnError::InvalidLength(
+            "string must be longer than 1 character".to_string(),
+        ));
+    }
+    Ok(sum_mod_n(value, alphabet, false) == 0)
+}
+
+/// Rejects empty input and reports the first character outside `alphabet`.
+pub(crate) fn check_symbols(value: &str, alphabet: &str) -> Result<(), LuhnError> {
+    if value.is_empty() {
+        return Err(LuhnError::EmptyString);
+    }
+    match value.char_indices().find(|&(_, c)| !alphabet.contains(c)) {
+        Some((index, character)) if character.is_whitespace() => {
+            Err(LuhnError::ContainsSpaces { index, character })
+        }
+        Some((index, character)) => Err(LuhnError::NonNumeric { index, character }),
+        None => Ok(()),
+    }
+}
+
+/// Computes the Luhn mod N sum of `value`, modulo N.
+///
+/// Positions are doubled from the rightmost character leftwards, starting with it
+/// when `double_first` is set (for a payload) and with the one before it otherwise
+/// (for a value ending in its check character). Every character must be in
+/// `alphabet`.
+fn sum_mod_n(value: &str, alphabet: &str, double_first: bool) -> u32 {
+    let n = alphabet.chars().count() as u32;
+    let mut factor = if double_first { 2 } else { 1 };
+    let mut sum = 0;
+    for c in value.chars().rev() {
+        let position = alphabet.chars().position(|a| a == c).unwrap_or(0) as u32;
+        let addend = factor * position;
+        sum = (sum + addend / n + addend % n) % n;
+        factor = 3 - factor;
+    }
+    sum
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{generate, validate};
+
+    #[test]
+    fn test_base_10_is_luhn() {
+        for payload in ["07992739871", "0", "000999", "01234567890123456789"] {
+            let number = generate_mod_n(payload, 10).unwrap();
+            assert_eq!(number, generate(payload, None).unwrap());
+            assert!(validate_mod_n(&number, 10).unwrap());
+            assert!(validate(&number).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_round_trip_all_bases() {
+        for n in 2..=36 {
+            let alphabet = builtin_alphabet(n).unwrap();
+            let payload: String = alphabet.chars().rev().chain(alphabet.chars()).collect();
+            let value = generate_mod_n(&payload, n).unwrap();
+            assert!(validate_mod_n(&value, n).unwrap(), "base {n}");
+            assert!(alphabet.contains(value.chars().next_back().unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_detects_single_character_errors() {
+        let value = generate_mod_n("VOUCHER2024", 36).unwrap();
+        for (i, original) in value.char_indices() {
+            for replacement in BASE36.chars().filter(|&c| c != original) {
+                let mut changed = value.clone();
+                changed.replace_range(i..i + 1, &replacement.to_string());
+                assert!(!validate_mod_n(&changed, 36).unwrap(), "{changed}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_hex() {
+        let value = generate_mod_n("A1B2C3", 16).unwrap();
+        assert!(validate_mod_n(&value, 16).unwrap());
+        // 'G' is not a hex digit
+        assert_eq!(
+            generate_mod_n("A1G2", 16).unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 2,
+                character: 'G'
+            }
+        );
+    }
+
+    #[test]
+    fn test_custom_alphabets() {
+        assert_eq!(Alphabet::CROCKFORD.base(), 32);
+        assert_eq!(Alphabet::BASE36.base(), 36);
+        assert_eq!(Alphabet::new(BASE36).unwrap(), Alphabet::BASE36);
+        assert_eq!(
+            generate_mod_n_with("VOUCHER", &Alphabet::BASE36).unwrap(),
+            generate_mod_n("VOUCHER", 36).unwrap()
+        );
+
+        // Order matters, not just membership
+        let reversed = Alphabet::new("ZYXWVUTSRQPONMLKJIHGFEDCBA9876543210").unwrap();
+        let value = generate_mod_n_with("VOUCHER", &reversed).unwrap();
+        assert!(validate_mod_n_with(&value, &reversed).unwrap());
+        assert!(!validate_mod_n_with(&value, &Alphabet::BASE36).unwrap());
+
+        // Non-ASCII characters are single symbols
+        let greek = Alphabet::new("αβγδεζ").unwrap();
+        assert_eq!(greek.base(), 6);
+        let value = generate_mod_n_with("γαδ", &greek).unwrap();
+        assert_eq!(value.chars().count(), 4);
+        assert!(greek.as_str().contains(value.chars().next_back().unwrap()));
+        assert!(validate_mod_n_with(&value, &greek).unwrap());
+
+        // Every check character comes from the alphabet
+        for payload in ["0", "Z", "7ZJ4KM", "ZZZZZZZZ", "0000000001"] {
+            let code = generate_mod_n_with(payload, &Alphabet::CROCKFORD).unwrap();
+            let check = code.chars().next_back().unwrap();
+            assert!(Alphabet::CROCKFORD.as_str().contains(check));
+        }
+    }
+
+    #[test]
+    fn test_invalid_alphabets() {
+        for (chars, message) in [
+            ("", "alphabet must have at least 2 characters"),
+            ("A", "alphabet must have at least 2 characters"),
+            ("AB CD", "alphabet cannot contain whitespace"),
+            (
+                "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ0",
+                "alphabet cannot contain '0' more than once",
+            ),
+            ("αβγα", "alphabet cannot contain 'α' more than once"),
+        ] {
+            assert_eq!(
+                Alphabet::new(chars).unwrap_err(),
+                LuhnError::InvalidConfiguration(message.to_string())
+            );
+        }
+        // A check character outside the alphabet is rejected, not just mismatched
+        assert_eq!(
+            validate_mod_n_with("7ZJ4KMU", &Alphabet::CROCKFORD).unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 6,
+                character: 'U'
+            }
+        );
+    }
+
+    #[test]
+    fn test_error_cases() {
+        for n in [0, 1, 37] {
+            assert_eq!(
+                generate_mod_n("1", n).unwrap_err(),
+                LuhnError::InvalidConfiguration("n must be between 2 and 36".to_string())
+            );
+        }
+        assert_eq!(validate_mod_n("", 36).unwrap_err(), LuhnError::EmptyString);
+        assert_eq!(
+            validate_mod_n("A", 36).unwrap_err(),
+            LuhnError::InvalidLength("string must be longer than 1 character".to_string())
+        );
+        assert_eq!(
+            validate_mod_n("AB C", 36).unwrap_err(),
+            LuhnError::ContainsSpaces {
+                index: 2,
+                character: ' '
+            }
+        );
+        assert_eq!(
+            validate_mod_n("abc", 36).unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 0,
+                character: 'a'
+            }
+        );
+    }
+}
@@ -0,0 +1,407 @@
+//! Runtime support for the types that users define with this crate's macros.
+//!
+//! Code expanded from [`luhn_newtype!`](crate::luhn_newtype),
+//! `#[derive(LuhnValidated)]` and `#[derive(LuhnChecked)]` lives in the user's
+//! crate and can only reach public items, so the actual work goes through these
+//! hidden functions rather than being spelled out at every expansion site.
+
+use std::fmt::Display;
+
+use crate::{
+    check_length_bounds, handle_errors, locate_in_input, normalize_input, payload_checksum,
+    strict_validate, LuhnError, Parity, Scratch, ValidateOptions,
+};
+
+/// Validates a newtype's input and returns the digits it should store.
+///
+/// With `separators`, spaces and dashes are stripped first. The length bounds
+/// apply to the digits, check digit included.
+#[doc(hidden)]
+pub fn validate_newtype(
+    value: &str,
+    min_len: Option<usize>,
+    max_len: Option<usize>,
+    separators: bool,
+) -> Result<String, LuhnError> {
+    let options = ValidateOptions {
+        allow_separators: separators,
+        ..ValidateOptions::STRICT
+    };
+    let digits = Scratch(normalize_input(value, &options));
+    handle_errors(&digits).map_err(|error| locate_in_input(error, value, &options))?;
+    check_length_bounds(digits.len(), min_len, max_len)?;
+    strict_validate(&digits)?;
+    Ok(digits.into_owned())
+}
+
+/// Declares a `String` newtype that can only hold a valid Luhn number.
+///
+/// `luhn_newtype!(pub struct Sin, len = 9)` expands to the struct plus a
+/// `new(&str)` constructor, `as_str`, `into_string`, `TryFrom<&str>`, `FromStr`,
+/// `Display`, `AsRef<str>` and `From<Sin> for String`. Attributes such as doc
+/// comments and `#[derive(...)]` are passed through to the struct.
+///
+/// Options follow the name, separated by commas:
+/// * `len = N` - Exactly `N` digits, check digit included
+/// * `min_len = N`, `max_len = N` - Inclusive bounds on the number of digits
+/// * `separators` - Strip spaces and dashes before validating
+///
+/// A `min_len` greater than `max_len` is rejected at compile time. The expansion
+/// relies on the `std` feature.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{luhn_newtype, LuhnError};
+///
+/// luhn_newtype!(
+///     /// A Canadian Social Insurance Number.
+///     #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+///     pub struct Sin, len = 9, separators
+/// );
+///
+/// let sin: Sin = "046 454 286".parse().unwrap();
+/// assert_eq!(sin.as_str(), "046454286");
+/// assert_eq!(
+///     Sin::new("046454287").unwrap_err(),
+///     LuhnError::InvalidChecksum { expected: 6, found: 7 }
+/// );
+/// assert!(Sin::new("79927398713").is_err());
+/// ```
+///
+/// ```compile_fail
+/// luhn_tools::luhn_newtype!(pub struct Empty, min_len = 12, max_len = 9);
+/// ```
+#[macro_export]
+macro_rules! luhn_newtype {
+    (@parse $head:tt [$min:tt $max:tt $sep:tt]) => {
+        $crate::luhn_newtype!(@emit $head [$min $max $sep]);
+    };
+    (@parse $head:tt [$min:tt $max:tt $sep:tt] len = $len:expr $(, $($rest:tt)*)?) => {
+        $crate::luhn_newtype!(@parse $head [[$len] [$len] $sep] $($($rest)*)?);
+    };
+    (@parse $head:tt [$min:tt $max:tt $sep:tt] min_len = $len:expr $(, $($rest:tt)*)?) => {
+        $crate::luhn_newtype!(@parse $head [[$len] $max $sep] $($($rest)*)?);
+    };
+    (@parse $head:tt [$min:tt $max:tt $sep:tt] max_len = $len:expr $(, $($rest:tt)*)?) => {
+        $crate::luhn_newtype!(@parse $head [$min [$len] $sep] $($($rest)*)?);
+    };
+    (@parse $head:tt [$min:tt $max:tt $sep:tt] separators $(, $($rest:tt)*)?) => {
+        $crate::luhn_newtype!(@parse $head [$min $max true] $($($rest)*)?);
+    };
+    (@parse $head:tt $state:tt $($other:tt)*) => {
+        ::core::compile_error!("expected `len`, `min_len`, `max_len` or `separators`");
+    };
+    (@check [$min:expr] [$max:expr]) => {
+        const _: () = ::core::assert!(
+            $min <= $max,
+            "`min_len` must not be greater than `max_len`"
+        );
+    };
+    (@check $min:tt $max:tt) => {};
+    (@len []) => {
+        ::core::option::Option::None
+    };
+    (@len [$len:expr]) => {
+        ::core::option::Option::Some($len)
+    };
+    (@emit [$(#[$attr:meta])* $vis:vis struct $name:ident] [$min:tt $max:tt $sep:tt]) => {
+        $crate::luhn_newtype!(@check $min $max);
+
+        $(#[$attr])*
+        $vis struct $name(::std::string::String);
+
+        // Not every declared type uses every accessor
+        #[allow(dead_code)]
+        impl $name {
+            /// Validates `value` as a Luhn number and wraps its digits.
+            $vis fn new(value: &str) -> ::core::result::Result<Self, $crate::LuhnError> {
+                $crate::__private::validate_newtype(
+                    value,
+                    $crate::luhn_newtype!(@len $min),
+                    $crate::luhn_newtype!(@len $max),
+                    $sep,
+                )
+                .map($name)
+            }
+
+            /// Returns the validated digits.
+            $vis fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Unwraps the digits into their string.
+            $vis fn into_string(self) -> ::std::string::String {
+                self.0
+            }
+        }
+
+        impl ::core::convert::TryFrom<&str> for $name {
+            type Error = $crate::LuhnError;
+
+            fn try_from(value: &str) -> ::core::result::Result<Self, Self::Error> {
+                $name::new(value)
+            }
+        }
+
+        impl ::core::str::FromStr for $name {
+            type Err = $crate::LuhnError;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                $name::new(s)
+            }
+        }
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl ::core::convert::AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl ::core::convert::From<$name> for ::std::string::String {
+            fn from(value: $name) -> ::std::string::String {
+                value.0
+            }
+        }
+    };
+    ($(#[$attr:meta])* $vis:vis struct $name:ident $(, $($option:tt)*)?) => {
+        $crate::luhn_newtype!(@parse [$(#[$attr])* $vis struct $name] [[] [] false] $($($option)*)?);
+    };
+}
+
+/// Appends a struct field's digits to a composite payload.
+///
+/// With a `width`, the value is zero-padded to exactly that many digits and
+/// rejected if it needs more.
+#[doc(hidden)]
+pub fn push_field(
+    payload: &mut String,
+    name: &str,
+    value: &dyn Display,
+    width: Option<usize>,
+) -> Result<(), LuhnError> {
+    let digits = value.to_string();
+    handle_errors(&digits)?;
+    if let Some(width) = width {
+        if digits.len() > width {
+            return Err(LuhnError::InvalidLength(format!(
+                "field `{}` must fit in {} digits",
+                name, width
+            )));
+        }
+        // Padded by hand: format widths cannot exceed u16::MAX
+        payload.extend(core::iter::repeat('0').take(width - digits.len()));
+    }
+    payload.push_str(&digits);
+    Ok(())
+}
+
+/// Computes the standard check digit for a composite payload.
+#[doc(hidden)]
+pub fn payload_check_digit(payload: &str) -> Result<u8, LuhnError> {
+    payload_checksum(payload, Parity::FromRight)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_newtype() {
+        assert_eq!(
+            validate_newtype("79927398713", None, None, false).unwrap(),
+            "79927398713"
+        );
+        assert_eq!(
+            validate_newtype("7992-7398-713", Some(11), Some(11), true).unwrap(),
+            "79927398713"
+        );
+        assert_eq!(
+            validate_newtype("7992-7398-713", None, None, false).unwrap_err(),
+            LuhnError::NegativeNumber
+        );
+        assert_eq!(
+            validate_newtype("79927398713", Some(12), None, false).unwrap_err(),
+            LuhnError::InvalidLength("string must be at least 12 characters".to_string())
+        );
+        assert_eq!(
+            validate_newtype("79927398714", None, None, false).unwrap_err(),
+            LuhnError::InvalidChecksum {
+                expected: 3,
+                found: 4
+            }
+        );
+    }
+
+    crate::luhn_newtype!(
+        #[derive(Debug, PartialEq)]
+        struct AccountId
+    );
+
+    crate::luhn_newtype!(
+        #[derive(Debug)]
+        pub(crate) struct Sin, separators, len = 9,
+    );
+
+    crate::luhn_newtype!(struct CardRef, min_len = 12, max_len = 19);
+
+    #[test]
+    fn test_declared_newtypes() {
+        let id = AccountId::new("79927398713").unwrap();
+        assert_eq!(id.as_str(), "79927398713");
+        assert_eq!(id.to_string(), "79927398713");
+        assert_eq!(id.as_ref(), "79927398713");
+        assert_eq!("79927398713".parse::<AccountId>().unwrap(), id);
+        assert_eq!(AccountId::try_from("0018").unwrap().into_string(), "0018");
+        assert_eq!(String::from(id), "79927398713");
+
+        assert_eq!(Sin::new("046-454-286").unwrap().as_str(), "046454286");
+        assert_eq!(
+            Sin::new("79927398713").unwrap_err(),
+            LuhnError::InvalidLength("string must be between 9 and 9 characters".to_string())
+        );
+        assert!(CardRef::new("4111111111111111").is_ok());
+        assert!(CardRef::new("4111 1111 1111 1111").is_err());
+        assert!(CardRef::new("79927398713").is_err());
+    }
+
+    #[test]
+    fn test_push_field() {
+        let mut payload = String::new();
+        push_field(&mut payload, "branch", &12, Some(3)).unwrap();
+        push_field(&mut payload, "account", &34_567u32, None).unwrap();
+        assert_eq!(payload, "01234567");
+        assert_eq!(payload_check_digit(&payload).unwrap(), 4);
+
+        assert_eq!(
+            push_field(&mut payload, "branch", &1234, Some(3)).unwrap_err(),
+            LuhnError::InvalidLength("field `branch` must fit in 3 digits".to_string())
+        );
+        assert_eq!(
+            push_field(&mut payload, "delta", &-5, Some(3)).unwrap_err(),
+            LuhnError::NegativeNumber
+        );
+        assert_eq!(payload, "01234567");
+
+        let mut wide = String::new();
+        push_field(&mut wide, "wide", &7, Some(70_000)).unwrap();
+        assert_eq!(wide.len(), 70_000);
+        assert!(wide.ends_with("007"));
+    }
+
+    #[cfg(feature = "derive")]
+    mod derived {
+        use crate::{LuhnChecked, LuhnError, LuhnValidated};
+
+        #[derive(Debug, PartialEq, LuhnValidated)]
+        struct MemberId(String);
+
+        #[derive(Debug, LuhnValidated)]
+        #[luhn(min_len = 9, max_len = 9, separators)]
+        pub(crate) struct Sin(String);
+
+        #[test]
+        fn test_conversions() {
+            let id = MemberId::new("79927398713").unwrap();
+            assert_eq!(id.as_str(), "79927398713");
+            assert_eq!(id.to_string(), "79927398713");
+            assert_eq!(id.as_ref(), "79927398713");
+            assert_eq!("79927398713".parse::<MemberId>().unwrap(), id);
+            assert_eq!(MemberId::try_from("79927398713").unwrap(), id);
+            assert_eq!(
+                MemberId::try_from("79927398714").unwrap_err(),
+                LuhnError::InvalidChecksum {
+                    expected: 3,
+                    found: 4
+                }
+            );
+        }
+
+        #[test]
+        fn test_attributes() {
+            assert_eq!(Sin::new("046 454 286").unwrap().as_str(), "046454286");
+            assert_eq!(
+                Sin::new("79927398713").unwrap_err(),
+                LuhnError::InvalidLength("string must be between 9 and 9 characters".to_string())
+            );
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn test_serde() {
+            #[derive(Debug, LuhnValidated)]
+            #[luhn(serde, separators)]
+            struct CardId(String);
+
+            let id: CardId = serde_json::from_str("\"7992 7398 713\"").unwrap();
+            assert_eq!(serde_json::to_string(&id).unwrap(), "\"79927398713\"");
+            assert_eq!(
+                serde_json::from_str::<CardId>("\"79927398714\"")
+                    .unwrap_err()
+                    .to_string(),
+                "invalid CardId: check digit is invalid (expected 3, found 4)"
+            );
+        }
+
+        #[derive(LuhnChecked)]
+        struct Reference {
+            #[luhn(width = 3)]
+            branch: u16,
+            note: &'static str,
+            #[luhn]
+            account: i64,
+            #[luhn(check)]
+            check: u8,
+        }
+
+        #[derive(LuhnChecked)]
+        struct Pair(#[luhn(width = 2)] u8, #[luhn(width = 4)] u16);
+
+        #[test]
+        fn test_checked_fields() {
+            let reference = Reference {
+                branch: 79,
+                note: "ignored",
+                account: 92_739_871,
+                check: 3,
+            };
+            assert_eq!(reference.note, "ignored");
+            assert_eq!(reference.luhn_payload().unwrap(), "07992739871");
+            assert_eq!(reference.to_luhn_string().unwrap(), "079927398713");
+            assert!(reference.is_luhn_valid().unwrap());
+
+            let stale = Reference {
+                check: 4,
+                ..reference
+            };
+            assert!(!stale.is_luhn_valid().unwrap());
+
+            let pair = Pair(7, 42);
+            assert_eq!(pair.luhn_payload().unwrap(), "070042");
+            assert_eq!(pair.luhn_check_digit().unwrap(), 7);
+        }
+
+        #[test]
+        fn test_checked_errors() {
+            assert_eq!(
+                Pair(100, 0).luhn_payload().unwrap_err(),
+                LuhnError::InvalidLength("field `0` must fit in 2 digits".to_string())
+            );
+            let negative = Reference {
+                branch: 1,
+                note: "",
+                account: -5,
+                check: 0,
+            };
+            assert_eq!(
+                negative.to_luhn_string().unwrap_err(),
+                LuhnError::NegativeNumber
+            );
+        }
+    }
+}
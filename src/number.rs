@@ -0,0 +1,326 @@
+//! A string that is known to be a valid Luhn number.
+//!
+//! Passing raw `&str` around means every layer has to decide whether to trust it.
+//! [`LuhnNumber`] can only be built from a number that passes
+//! [`strict_validate`](crate::strict_validate), so holding one is proof the check
+//! digit was verified.
+
+use core::borrow::Borrow;
+use core::str::FromStr;
+use std::fmt;
+
+use crate::card::mask_pan;
+use crate::{strict_validate, LuhnError, Scratch};
+
+/// A validated Luhn number.
+///
+/// A Luhn number is often a card number, so `Debug` output is masked the same way
+/// as [`CardNumber`](crate::CardNumber): only the first six and last four digits
+/// are shown, and shorter numbers are hidden entirely. `Display` prints every
+/// digit. With the `zeroize` feature the digits are wiped when the value is
+/// dropped.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{LuhnError, LuhnNumber};
+///
+/// let number: LuhnNumber = "79927398713".parse().unwrap();
+/// assert_eq!(number.body(), "7992739871");
+/// assert_eq!(number.check_digit(), 3);
+/// assert_eq!(number.to_string(), "79927398713");
+/// assert_eq!(format!("{:?}", number), "LuhnNumber(***********)");
+///
+/// assert_eq!(
+///     LuhnNumber::try_from("79927398714").unwrap_err(),
+///     LuhnError::InvalidChecksum { expected: 3, found: 4 }
+/// );
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LuhnNumber(String);
+
+impl LuhnNumber {
+    /// Validates `value` and wraps it.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`strict_validate`](crate::strict_validate):
+    /// the usual input checks, plus `InvalidChecksum` if the check digit is wrong.
+    pub fn new(value: impl Into<String>) -> Result<Self, LuhnError> {
+        let value = Scratch(value.into().into());
+        strict_validate(&value)?;
+        Ok(LuhnNumber(value.into_owned()))
+    }
+
+    /// Wraps a value the caller has already validated.
+    pub(crate) fn from_validated(value: String) -> Self {
+        LuhnNumber(value)
+    }
+
+    /// Returns the full number, check digit included.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the payload, everything but the trailing check digit.
+    pub fn body(&self) -> &str {
+        self.0
+            .get(..self.0.len().saturating_sub(1))
+            .unwrap_or_default()
+    }
+
+    /// Returns the trailing check digit (0–9).
+    pub fn check_digit(&self) -> u8 {
+        self.0.bytes().last().map_or(0, |b| b - b'0')
+    }
+
+    /// Unwraps the number into its string.
+    pub fn into_string(mut self) -> String {
+        core::mem::take(&mut self.0)
+    }
+}
+
+impl fmt::Debug for LuhnNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("LuhnNumber")
+            .field(&format_args!("{}", mask_pan(&self.0)))
+            .finish()
+    }
+}
+
+impl fmt::Display for LuhnNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for LuhnNumber {
+    type Err = LuhnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LuhnNumber::new(s)
+    }
+}
+
+impl TryFrom<&str> for LuhnNumber {
+    type Error = LuhnError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        LuhnNumber::new(value)
+    }
+}
+
+impl TryFrom<String> for LuhnNumber {
+    type Error = LuhnError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        LuhnNumber::new(value)
+    }
+}
+
+impl AsRef<str> for LuhnNumber {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Hashing and ordering match `str`, so a `LuhnNumber` key can be looked up by `&str`.
+impl Borrow<str> for LuhnNumber {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<LuhnNumber> for String {
+    fn from(value: LuhnNumber) -> String {
+        value.into_string()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for LuhnNumber {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for LuhnNumber {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for LuhnNumber {}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for LuhnNumber {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for LuhnNumber {
+    /// Deserializes a string and validates it as [`LuhnNumber::new`] does.
+    ///
+    /// The error message names the problem but never echoes the input.
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = <String as ::serde::Deserialize>::deserialize(deserializer)?;
+        LuhnNumber::new(value)
+            .map_err(|e| ::serde::de::Error::custom(format_args!("invalid Luhn number: {}", e)))
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for LuhnNumber {
+    fn schema_name() -> String {
+        "LuhnNumber".to_string()
+    }
+
+    fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        crate::schema::digit_string(
+            "^[0-9]{2,}$",
+            "A number of at least two digits whose last digit is a valid Luhn check digit.",
+        )
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+impl quickcheck::Arbitrary for LuhnNumber {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        let payload_len = 1 + <usize as quickcheck::Arbitrary>::arbitrary(g) % g.size().max(1);
+        LuhnNumber(crate::property::valid_digits(g, payload_len))
+    }
+
+    /// Shrinks toward shorter valid numbers, down to two digits.
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(
+            crate::property::shrink_payload(self.body(), 1)
+                .into_iter()
+                .map(LuhnNumber),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accessors() {
+        let number = LuhnNumber::new("79927398713").unwrap();
+        assert_eq!(number.as_str(), "79927398713");
+        assert_eq!(number.as_ref(), "79927398713");
+        assert_eq!(number.body(), "7992739871");
+        assert_eq!(number.check_digit(), 3);
+        assert_eq!(number.to_string(), "79927398713");
+        assert_eq!(String::from(number.clone()), "79927398713");
+        assert_eq!(number.into_string(), "79927398713");
+
+        // Leading zeros are kept
+        let number: LuhnNumber = "0018".parse().unwrap();
+        assert_eq!(number.body(), "001");
+        assert_eq!(number.check_digit(), 8);
+    }
+
+    #[test]
+    fn test_map_keys() {
+        use std::collections::{BTreeMap, HashMap, HashSet};
+
+        let a = LuhnNumber::new("79927398713").unwrap();
+        let b = LuhnNumber::new("0018").unwrap();
+
+        let mut hashed = HashMap::new();
+        hashed.insert(a.clone(), 1);
+        hashed.insert(b.clone(), 2);
+        assert_eq!(hashed.get("79927398713"), Some(&1));
+        assert_eq!(hashed.get("79927398714"), None);
+
+        let sorted: BTreeMap<LuhnNumber, u32> = hashed.into_iter().collect();
+        assert_eq!(sorted.get("0018"), Some(&2));
+        assert_eq!(sorted.keys().collect::<Vec<_>>(), [&b, &a]);
+
+        let seen: HashSet<LuhnNumber> = [a.clone(), a.clone(), b].into_iter().collect();
+        assert_eq!(seen.len(), 2);
+        assert!(seen.contains("79927398713"));
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroize() {
+        use zeroize::Zeroize;
+
+        let mut value = LuhnNumber::new("79927398713").unwrap();
+        value.zeroize();
+        assert_eq!(value.as_str(), "");
+        // Accessors stay panic-free on a wiped value
+        assert_eq!(value.body(), "");
+        assert_eq!(value.check_digit(), 0);
+    }
+
+    #[test]
+    fn test_debug_is_masked() {
+        let number = LuhnNumber::new("4111111111111111").unwrap();
+        assert_eq!(format!("{:?}", number), "LuhnNumber(411111******1111)");
+        assert_eq!(
+            format!("{:?}", LuhnNumber::new("0018").unwrap()),
+            "LuhnNumber(****)"
+        );
+    }
+
+    #[test]
+    fn test_conversions_agree() {
+        let parsed: LuhnNumber = "4111111111111111".parse().unwrap();
+        assert_eq!(LuhnNumber::try_from("4111111111111111").unwrap(), parsed);
+        assert_eq!(
+            LuhnNumber::try_from("4111111111111111".to_string()).unwrap(),
+            parsed
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let number: LuhnNumber = serde_json::from_str("\"79927398713\"").unwrap();
+        assert_eq!(number.as_str(), "79927398713");
+        assert_eq!(serde_json::to_string(&number).unwrap(), "\"79927398713\"");
+
+        let err = serde_json::from_str::<LuhnNumber>("\"79927398714\"").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid Luhn number: check digit is invalid (expected 3, found 4)"
+        );
+        assert!(serde_json::from_str::<LuhnNumber>("79927398713").is_err());
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_json_schema() {
+        let schema = serde_json::to_value(schemars::schema_for!(LuhnNumber)).unwrap();
+        assert_eq!(schema["type"], "string");
+        assert_eq!(schema["pattern"], "^[0-9]{2,}$");
+        assert!(schema["description"].as_str().unwrap().contains("Luhn"));
+    }
+
+    #[test]
+    fn test_error_cases() {
+        assert_eq!(
+            "79927398714".parse::<LuhnNumber>().unwrap_err(),
+            LuhnError::InvalidChecksum {
+                expected: 3,
+                found: 4
+            }
+        );
+        assert_eq!(
+            LuhnNumber::try_from("").unwrap_err(),
+            LuhnError::EmptyString
+        );
+        assert_eq!(
+            LuhnNumber::try_from("7992 7398713").unwrap_err(),
+            LuhnError::ContainsSpaces {
+                index: 4,
+                character: ' '
+            }
+        );
+    }
+}
@@ -0,0 +1,332 @@
+//! Composable validation pipelines.
+//!
+//! Services that validate identifiers usually chain the same few steps: clean up the
+//! raw input, check its length, check the Luhn digit, then apply some domain rule. A
+//! [`Pipeline`] is configured once with those steps and run on many inputs; failures
+//! report which [`Stage`] rejected the input.
+
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+use crate::{
+    check_length_bounds, checksum_mismatch, handle_errors, locate_in_input, normalize_input,
+    validate_parity,
+};
+use crate::{LuhnError, Parity, ValidateOptions};
+
+type CheckFn = dyn Fn(&str) -> Result<(), LuhnError> + Send + Sync;
+
+/// A step of a [`Pipeline`], used to report where validation failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stage {
+    /// Input normalization and the usual input checks
+    Normalize,
+    /// The length check
+    Length,
+    /// The Luhn check digit
+    Luhn,
+    /// A custom check, by name
+    Check(&'static str),
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stage::Normalize => f.write_str("normalize"),
+            Stage::Length => f.write_str("length"),
+            Stage::Luhn => f.write_str("luhn"),
+            Stage::Check(name) => f.write_str(name),
+        }
+    }
+}
+
+/// The error returned by [`Pipeline::run`]: the failing stage and its error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelineError {
+    /// The stage that rejected the input.
+    pub stage: Stage,
+    /// Why it was rejected. A wrong check digit is reported as
+    /// [`LuhnError::InvalidChecksum`].
+    pub error: LuhnError,
+}
+
+impl fmt::Display for PipelineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.stage, self.error)
+    }
+}
+
+impl Error for PipelineError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[derive(Clone)]
+enum Step {
+    Normalize(ValidateOptions),
+    Length(RangeInclusive<usize>),
+    Luhn(Parity),
+    Check(&'static str, Arc<CheckFn>),
+}
+
+/// An ordered chain of validation steps, configured once and run on many inputs.
+///
+/// Steps run in the order they were added. Each step sees the output of the
+/// normalization steps before it.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{LuhnError, Pipeline, Stage, ValidateOptions};
+///
+/// let cards = Pipeline::new()
+///     .normalize(ValidateOptions { allow_separators: true, ..Default::default() })
+///     .length(12..=19)
+///     .luhn()
+///     .check("brand", |n| {
+///         if n.starts_with('4') {
+///             Ok(())
+///         } else {
+///             Err(LuhnError::ParseError("unsupported brand".to_string()))
+///         }
+///     });
+///
+/// assert_eq!(cards.run("4111 1111 1111 1111").unwrap(), "4111111111111111");
+///
+/// let error = cards.run("79927398713").unwrap_err();
+/// assert_eq!(error.stage, Stage::Length);
+/// assert_eq!(
+///     cards.run("4111 1111 1111 1112").unwrap_err().error,
+///     LuhnError::InvalidChecksum { expected: 1, found: 2 }
+/// );
+/// ```
+#[derive(Clone, Default)]
+pub struct Pipeline {
+    steps: Vec<Step>,
+}
+
+impl Pipeline {
+    /// Returns an empty pipeline, which accepts any input unchanged.
+    pub fn new() -> Self {
+        Pipeline::default()
+    }
+
+    /// Adds a normalization step, applying the input-cleanup settings of `options`
+    /// (trimming, separators, underscores, Unicode digits) and then the usual input
+    /// checks (empty, spaces, non-numeric, ...). Error positions point into the
+    /// value the step received.
+    pub fn normalize(mut self, options: ValidateOptions) -> Self {
+        self.steps.push(Step::Normalize(options));
+        self
+    }
+
+    /// Adds a length check, with inclusive bounds.
+    pub fn length(mut self, range: RangeInclusive<usize>) -> Self {
+        self.steps.push(Step::Length(range));
+        self
+    }
+
+    /// Adds a Luhn check with the usual (rightmost) parity.
+    pub fn luhn(self) -> Self {
+        self.luhn_with_parity(Parity::FromRight)
+    }
+
+    /// Adds a Luhn check with the given parity.
+    pub fn luhn_with_parity(mut self, parity: Parity) -> Self {
+        self.steps.push(Step::Luhn(parity));
+        self
+    }
+
+    /// Adds a custom check, reported as [`Stage::Check`] with `name` on failure.
+    pub fn check<F>(mut self, name: &'static str, check: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), LuhnError> + Send + Sync + 'static,
+    {
+        self.steps.push(Step::Check(name, Arc::new(check)));
+        self
+    }
+
+    /// Returns the stages of the pipeline, in order.
+    pub fn stages(&self) -> Vec<Stage> {
+        self.steps.iter().map(Step::stage).collect()
+    }
+
+    /// Runs every step on `value`.
+    ///
+    /// # Returns
+    /// * `Ok(Cow<str>)` - The input after normalization, if every step passed
+    /// * `Err(PipelineError)` - The first stage that rejected the input
+    ///
+    /// # Errors
+    /// Returns the first failing stage along with its error. A wrong check digit
+    /// is reported as [`LuhnError::InvalidChecksum`].
+    pub fn run<'a>(&self, value: &'a str) -> Result<Cow<'a, str>, PipelineError> {
+        let mut value = Cow::Borrowed(value);
+
+        for step in &self.steps {
+            let fail = |error| PipelineError {
+                stage: step.stage(),
+                error,
+            };
+            match step {
+                Step::Normalize(options) => {
+                    let normalized = match &value {
+                        Cow::Borrowed(v) => normalize_input(v, options),
+                        Cow::Owned(v) => Cow::Owned(normalize_input(v, options).into_owned()),
+                    };
+                    handle_errors(&normalized)
+                        .map_err(|error| fail(locate_in_input(error, &value, options)))?;
+                    value = normalized;
+                }
+                Step::Length(range) => {
+                    check_length_bounds(value.len(), Some(*range.start()), Some(*range.end()))
+                        .map_err(fail)?;
+                }
+                Step::Luhn(parity) => {
+                    if !validate_parity(&value, *parity).map_err(fail)? {
+                        return Err(fail(checksum_mismatch(&value, *parity)));
+                    }
+                }
+                Step::Check(_, check) => check(&value).map_err(fail)?,
+            }
+        }
+
+        Ok(value)
+    }
+}
+
+impl Step {
+    fn stage(&self) -> Stage {
+        match self {
+            Step::Normalize(_) => Stage::Normalize,
+            Step::Length(_) => Stage::Length,
+            Step::Luhn(_) => Stage::Luhn,
+            Step::Check(name, _) => Stage::Check(name),
+        }
+    }
+}
+
+impl fmt::Debug for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("stages", &self.stages())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cards() -> Pipeline {
+        Pipeline::new()
+            .normalize(ValidateOptions {
+                allow_separators: true,
+                trim_whitespace: true,
+                ..Default::default()
+            })
+            .length(12..=19)
+            .luhn()
+            .check("visa", |n| {
+                if n.starts_with('4') {
+                    Ok(())
+                } else {
+                    Err(LuhnError::ParseError("not a visa number".to_string()))
+                }
+            })
+    }
+
+    #[test]
+    fn test_passes_and_returns_normalized() {
+        assert_eq!(
+            cards().run(" 4111-1111-1111-1111\n").unwrap(),
+            "4111111111111111"
+        );
+        assert!(matches!(
+            Pipeline::new().run("abc"),
+            Ok(Cow::Borrowed("abc"))
+        ));
+    }
+
+    #[test]
+    fn test_reports_failing_stage() {
+        let pipeline = cards();
+        let cases = [
+            ("", Stage::Normalize, LuhnError::EmptyString),
+            ("4111.1111", Stage::Normalize, LuhnError::FloatingPoint),
+            (
+                " 4111-x",
+                Stage::Normalize,
+                LuhnError::NonNumeric {
+                    index: 6,
+                    character: 'x',
+                },
+            ),
+            (
+                "79927398713",
+                Stage::Length,
+                LuhnError::InvalidLength("string must be between 12 and 19 characters".to_string()),
+            ),
+            (
+                "4111111111111112",
+                Stage::Luhn,
+                LuhnError::InvalidChecksum {
+                    expected: 1,
+                    found: 2,
+                },
+            ),
+            (
+                "5555555555554444",
+                Stage::Check("visa"),
+                LuhnError::ParseError("not a visa number".to_string()),
+            ),
+        ];
+        for (value, stage, error) in cases {
+            assert_eq!(
+                pipeline.run(value).unwrap_err(),
+                PipelineError { stage, error }
+            );
+        }
+    }
+
+    #[test]
+    fn test_steps_run_in_order() {
+        // Without a normalize step, the Luhn stage reports malformed input itself
+        let pipeline = Pipeline::new().luhn().length(1..=3);
+        assert_eq!(pipeline.run("1 2").unwrap_err().stage, Stage::Luhn);
+        assert_eq!(
+            pipeline.run("79927398713").unwrap_err().stage,
+            Stage::Length
+        );
+        assert_eq!(pipeline.stages(), vec![Stage::Luhn, Stage::Length]);
+    }
+
+    #[test]
+    fn test_left_parity_reports_expected_digit() {
+        let pipeline = Pipeline::new().luhn_with_parity(Parity::FromLeft);
+        assert!(pipeline.run("79927398712").is_ok());
+        assert_eq!(
+            pipeline.run("79927398713").unwrap_err().error,
+            LuhnError::InvalidChecksum {
+                expected: 2,
+                found: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_error_display() {
+        let error = cards().run("4111111111111112").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "luhn: check digit is invalid (expected 1, found 2)"
+        );
+        assert_eq!(
+            error.source().unwrap().to_string(),
+            "check digit is invalid (expected 1, found 2)"
+        );
+    }
+}
@@ -0,0 +1,260 @@
+//! Batch validation over a contiguous string pool.
+//!
+//! Language bindings and columnar formats such as Arrow already hold many strings as
+//! one UTF-8 buffer plus an offsets array, where value `i` spans
+//! `data[offsets[i]..offsets[i + 1]]`. [`validate_pool`] works on that layout
+//! directly and writes one status byte per value, so a batch crosses an FFI boundary
+//! as three flat buffers instead of one marshalled string per value.
+
+use crate::limit::Checker;
+use crate::{validate, BatchOutcome, Interruption, Limit, LuhnError};
+
+/// Per-value status written to the results buffer by [`validate_pool_into`].
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PoolStatus {
+    /// Well formed, but the check digit is wrong
+    Invalid = 0,
+    /// A valid Luhn number
+    Valid = 1,
+    /// Not UTF-8, or fails the usual input checks (empty, non-numeric, ...)
+    Malformed = 2,
+}
+
+/// Validates every value in a string pool, returning one [`PoolStatus`] byte per value.
+///
+/// # Arguments
+/// * `data` - The concatenated UTF-8 bytes of all values
+/// * `offsets` - `n + 1` offsets into `data`; value `i` is `data[offsets[i]..offsets[i + 1]]`
+///
+/// # Returns
+/// * `Ok(Vec<u8>)` - `n` status bytes, see [`PoolStatus`]
+/// * `Err(LuhnError)` - Error if the offsets do not describe `data`
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{validate_pool, PoolStatus};
+///
+/// let data = b"7992739871379927398714x";
+/// let offsets: [i32; 4] = [0, 11, 22, 23];
+/// let results = validate_pool(data, &offsets).unwrap();
+/// assert_eq!(
+///     results,
+///     [PoolStatus::Valid as u8, PoolStatus::Invalid as u8, PoolStatus::Malformed as u8]
+/// );
+/// ```
+///
+/// # Errors
+/// Returns `ParseError` if an offset is negative, decreasing, or past the end of
+/// `data`. Individual malformed values do not fail the batch.
+pub fn validate_pool<O>(data: &[u8], offsets: &[O]) -> Result<Vec<u8>, LuhnError>
+where
+    O: Copy + TryInto<usize>,
+{
+    let mut results = vec![0; offsets.len().saturating_sub(1)];
+    validate_pool_into(data, offsets, &mut results)?;
+    Ok(results)
+}
+
+/// Validates every value in a string pool, stopping early when `limit` is reached.
+///
+/// The same as [`validate_pool`], except that an interrupted run returns status
+/// bytes for only the values validated before the interruption.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{validate_pool_until, CancelToken, Interruption, Limit};
+///
+/// let token = CancelToken::new();
+/// let limit = Limit { cancel: Some(token.clone()), ..Limit::default() };
+///
+/// let outcome = validate_pool_until(b"1879927398713", &[0u64, 2, 13], &limit).unwrap();
+/// assert_eq!(outcome.into_inner(), [1, 1]);
+///
+/// token.cancel();
+/// let outcome = validate_pool_until(b"1879927398713", &[0u64, 2, 13], &limit).unwrap();
+/// assert_eq!(outcome.interruption(), Some(Interruption::Cancelled));
+/// assert!(outcome.value().is_empty());
+/// ```
+///
+/// # Errors
+/// Returns the same errors as [`validate_pool`], for the values reached before
+/// any interruption.
+pub fn validate_pool_until<O>(
+    data: &[u8],
+    offsets: &[O],
+    limit: &Limit,
+) -> Result<BatchOutcome<Vec<u8>>, LuhnError>
+where
+    O: Copy + TryInto<usize>,
+{
+    let mut results = vec![0; offsets.len().saturating_sub(1)];
+    let (completed, interruption) =
+        validate_pool_checked(data, offsets, &mut results, &mut limit.checker())?;
+    results.truncate(completed);
+    Ok(BatchOutcome::new(results, completed, interruption))
+}
+
+/// Validates every value in a string pool into a caller-provided results buffer.
+///
+/// The allocation-free form of [`validate_pool`], for callers that own the results
+/// buffer on the other side of an FFI boundary.
+///
+/// # Arguments
+/// * `data` - The concatenated UTF-8 bytes of all values
+/// * `offsets` - `n + 1` offsets into `data`; value `i` is `data[offsets[i]..offsets[i + 1]]`
+/// * `results` - Receives `n` status bytes, see [`PoolStatus`]
+///
+/// # Examples
+/// ```
+/// use luhn_tools::validate_pool_into;
+///
+/// let mut results = [0u8; 2];
+/// validate_pool_into(b"1879927398713", &[0u64, 2, 13], &mut results).unwrap();
+/// assert_eq!(results, [1, 1]);
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * An offset is negative, decreasing, or past the end of `data` (`ParseError`)
+/// * `results` does not hold exactly one byte per value (`InvalidLength`)
+///
+/// On error, `results` may be partially written.
+pub fn validate_pool_into<O>(
+    data: &[u8],
+    offsets: &[O],
+    results: &mut [u8],
+) -> Result<(), LuhnError>
+where
+    O: Copy + TryInto<usize>,
+{
+    validate_pool_checked(data, offsets, results, &mut Limit::default().checker()).map(|_| ())
+}
+
+/// Validates a string pool into `results`, polling `checker` before each value.
+/// Returns how many values were validated and why it stopped early, if it did.
+fn validate_pool_checked<O>(
+    data: &[u8],
+    offsets: &[O],
+    results: &mut [u8],
+    checker: &mut Checker,
+) -> Result<(usize, Option<Interruption>), LuhnError>
+where
+    O: Copy + TryInto<usize>,
+{
+    let count = offsets.len().saturating_sub(1);
+    if results.len() != count {
+        return Err(LuhnError::InvalidLength(format!(
+            "results buffer must hold exactly {} values",
+            count
+        )));
+    }
+
+    for (completed, (window, result)) in offsets.windows(2).zip(results.iter_mut()).enumerate() {
+        if let Some(reason) = checker.poll() {
+            return Ok((completed, Some(reason)));
+        }
+        let bytes = match (window[0].try_into(), window[1].try_into()) {
+            (Ok(start), Ok(end)) if start <= end => data.get(start..end),
+            _ => None,
+        }
+        .ok_or_else(|| {
+            LuhnError::ParseError(
+                "offsets must be non-negative, non-decreasing and within the data buffer"
+                    .to_string(),
+            )
+        })?;
+
+        let status = match core::str::from_utf8(bytes).map(validate) {
+            Ok(Ok(true)) => PoolStatus::Valid,
+            Ok(Ok(false)) => PoolStatus::Invalid,
+            _ => PoolStatus::Malformed,
+        };
+        *result = status as u8;
+    }
+
+    Ok((count, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID: u8 = PoolStatus::Valid as u8;
+    const INVALID: u8 = PoolStatus::Invalid as u8;
+    const MALFORMED: u8 = PoolStatus::Malformed as u8;
+
+    #[test]
+    fn test_offset_types() {
+        let data = b"79927398713180";
+        assert_eq!(
+            validate_pool(data, &[0i32, 11, 13, 14]).unwrap(),
+            [VALID, VALID, MALFORMED]
+        );
+        assert_eq!(validate_pool(data, &[0i64, 11]).unwrap(), [VALID]);
+        assert_eq!(validate_pool(data, &[0u32, 12]).unwrap(), [INVALID]);
+        assert_eq!(
+            validate_pool(data, &[0usize, 11, 11]).unwrap(),
+            [VALID, MALFORMED]
+        );
+    }
+
+    #[test]
+    fn test_empty_pool() {
+        assert!(validate_pool::<i32>(b"", &[]).unwrap().is_empty());
+        assert!(validate_pool(b"", &[0i32]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_non_utf8_is_malformed() {
+        let data = [b'1', 0xff, b'8'];
+        assert_eq!(
+            validate_pool(&data, &[0, 2, 3]).unwrap(),
+            [MALFORMED, MALFORMED]
+        );
+        assert_eq!(validate_pool(&data, &[0, 1]).unwrap(), [MALFORMED]);
+    }
+
+    #[test]
+    fn test_bad_offsets() {
+        let error = LuhnError::ParseError(
+            "offsets must be non-negative, non-decreasing and within the data buffer".to_string(),
+        );
+        assert_eq!(validate_pool(b"18", &[0i32, 3]).unwrap_err(), error);
+        assert_eq!(validate_pool(b"18", &[2i32, 1]).unwrap_err(), error);
+        assert_eq!(validate_pool(b"18", &[-1i32, 2]).unwrap_err(), error);
+    }
+
+    #[test]
+    fn test_results_buffer_size() {
+        let mut results = [0u8; 1];
+        assert_eq!(
+            validate_pool_into(b"1818", &[0, 2, 4], &mut results).unwrap_err(),
+            LuhnError::InvalidLength("results buffer must hold exactly 2 values".to_string())
+        );
+    }
+
+    #[test]
+    fn test_until() {
+        let data = b"79927398713180";
+        let offsets = [0i32, 11, 13, 14];
+        let outcome = validate_pool_until(data, &offsets, &Limit::default()).unwrap();
+        assert_eq!(
+            outcome,
+            BatchOutcome::Complete(vec![VALID, VALID, MALFORMED])
+        );
+
+        let expired = Limit {
+            deadline: Some(std::time::Instant::now()),
+            cancel: None,
+        };
+        assert_eq!(
+            validate_pool_until(data, &offsets, &expired).unwrap(),
+            BatchOutcome::Interrupted {
+                partial: vec![],
+                completed: 0,
+                reason: Interruption::Deadline
+            }
+        );
+    }
+}
@@ -0,0 +1,135 @@
+//! Check digits at positions other than the last.
+//!
+//! Some identifier formats put the check digit first, or at a fixed offset inside
+//! the number. The check digit is still computed over the remaining digits in order,
+//! exactly as if it had been appended at the end; only where it is stored differs.
+
+use crate::{generate, handle_errors, validate, LuhnError};
+
+/// Generates a Luhn check digit for `value` and inserts it at `check_pos`.
+///
+/// # Arguments
+/// * `value` - The payload, without a check digit
+/// * `check_pos` - Zero-based index the check digit will occupy in the result
+///
+/// # Returns
+/// * `Ok(String)` - The payload with the check digit inserted at `check_pos`
+/// * `Err(LuhnError)` - Error if the input is invalid or the position is out of range
+///
+/// # Examples
+/// ```
+/// use luhn_tools::generate_at;
+///
+/// assert_eq!(generate_at("7992739871", 0).unwrap(), "37992739871");
+/// assert_eq!(generate_at("7992739871", 4).unwrap(), "79923739871");
+/// assert_eq!(generate_at("7992739871", 10).unwrap(), "79927398713");
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * The input fails the usual input checks (empty, spaces, non-numeric, ...)
+/// * `check_pos` is greater than the length of `value`
+pub fn generate_at(value: &str, check_pos: usize) -> Result<String, LuhnError> {
+    handle_errors(value)?;
+    if check_pos > value.len() {
+        return Err(LuhnError::InvalidLength(format!(
+            "check digit position must be at most {}",
+            value.len()
+        )));
+    }
+
+    let generated = generate(value, None)?;
+    let (payload, check_digit) = generated.split_at(value.len());
+    let (head, tail) = payload.split_at(check_pos);
+    Ok([head, check_digit, tail].concat())
+}
+
+/// Validates a number whose Luhn check digit is stored at `check_pos`.
+///
+/// # Arguments
+/// * `value` - The full number, including its check digit
+/// * `check_pos` - Zero-based index of the check digit
+///
+/// # Returns
+/// * `Ok(true)` - If the check digit matches the remaining digits
+/// * `Ok(false)` - If it does not
+/// * `Err(LuhnError)` - Error if the input is invalid or the position is out of range
+///
+/// # Examples
+/// ```
+/// use luhn_tools::validate_at;
+///
+/// assert!(validate_at("37992739871", 0).unwrap());
+/// assert!(validate_at("79923739871", 4).unwrap());
+/// assert!(!validate_at("79927398713", 0).unwrap());
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * The input fails the usual input checks (empty, spaces, non-numeric, ...)
+/// * `check_pos` is not a valid index into `value`
+pub fn validate_at(value: &str, check_pos: usize) -> Result<bool, LuhnError> {
+    handle_errors(value)?;
+    let check_digit = check_pos
+        .checked_add(1)
+        .and_then(|end| value.get(check_pos..end))
+        .ok_or_else(|| {
+            LuhnError::InvalidLength(format!(
+                "check digit position must be less than {}",
+                value.len()
+            ))
+        })?;
+
+    let (head, rest) = value.split_at(check_pos);
+    let tail = rest.get(1..).unwrap_or_default();
+    validate(&[head, tail, check_digit].concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_at_every_position() {
+        for pos in 0..=10 {
+            let number = generate_at("7992739871", pos).unwrap();
+            assert_eq!(&number[pos..pos + 1], "3");
+            assert!(validate_at(&number, pos).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_validate_at_detects_errors() {
+        assert!(!validate_at("47992739871", 0).unwrap());
+        assert!(!validate_at("37992739872", 0).unwrap());
+        assert!(validate_at("79927398713", 10).unwrap());
+    }
+
+    #[test]
+    fn test_position_out_of_range() {
+        assert_eq!(
+            generate_at("123", 4).unwrap_err(),
+            LuhnError::InvalidLength("check digit position must be at most 3".to_string())
+        );
+        assert_eq!(
+            validate_at("1230", 4).unwrap_err(),
+            LuhnError::InvalidLength("check digit position must be less than 4".to_string())
+        );
+        assert_eq!(
+            validate_at("79927398713", usize::MAX).unwrap_err(),
+            LuhnError::InvalidLength("check digit position must be less than 11".to_string())
+        );
+    }
+
+    #[test]
+    fn test_input_errors() {
+        assert_eq!(generate_at("", 0).unwrap_err(), LuhnError::EmptyString);
+        assert_eq!(
+            validate_at("12a4", 0).unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 2,
+                character: 'a'
+            }
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! Helpers for the [`quickcheck`] implementations on the validated wrapper types.
+//!
+//! Generated values are always valid, and shrinking keeps them valid: a failing
+//! number is cut down to shorter payloads, each given a fresh check digit, so a
+//! counterexample ends up as the shortest valid number that still fails.
+
+use quickcheck::Gen;
+
+use crate::{payload_checksum, Parity};
+
+/// Draws a valid number with a payload of `payload_len` uniform digits.
+pub(crate) fn valid_digits(g: &mut Gen, payload_len: usize) -> String {
+    let payload: String = (0..payload_len)
+        .map(|_| char::from(*g.choose(b"0123456789").unwrap_or(&b'0')))
+        .collect();
+    with_check_digit(&payload)
+}
+
+/// Returns valid numbers built from prefixes of `payload`, shortest first and no
+/// shorter than `min_len` payload digits: the minimum, then successive halvings
+/// of the remaining gap.
+pub(crate) fn shrink_payload(payload: &str, min_len: usize) -> Vec<String> {
+    let mut lens = Vec::new();
+    let mut gap = payload.len().saturating_sub(min_len);
+    while gap > 0 {
+        lens.push(payload.len() - gap);
+        gap /= 2;
+    }
+    lens.dedup();
+    lens.into_iter()
+        .filter_map(|len| payload.get(..len))
+        .map(with_check_digit)
+        .collect()
+}
+
+/// Appends the standard check digit to an ASCII digit payload.
+fn with_check_digit(payload: &str) -> String {
+    let check = payload_checksum(payload, Parity::FromRight).unwrap_or(0);
+    format!("{}{}", payload, check)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{validate, CardNumber, LuhnNumber};
+    use quickcheck::{Arbitrary, QuickCheck, TestResult};
+
+    #[test]
+    fn test_shrink_payload() {
+        assert_eq!(shrink_payload("79927398", 1), ["75", "799270", "79927398"]);
+        assert!(shrink_payload("7", 1).is_empty());
+        assert_eq!(shrink_payload("12345678901234", 11).len(), 2);
+    }
+
+    #[test]
+    fn test_generated_values_are_valid() {
+        fn luhn(number: LuhnNumber) -> bool {
+            validate(number.as_str()).unwrap()
+        }
+        fn card(card: CardNumber) -> bool {
+            (12..=19).contains(&card.as_str().len()) && validate(card.as_str()).unwrap()
+        }
+        QuickCheck::new().quickcheck(luhn as fn(LuhnNumber) -> bool);
+        QuickCheck::new().quickcheck(card as fn(CardNumber) -> bool);
+    }
+
+    #[test]
+    fn test_shrinks_stay_valid_and_shorter() {
+        let mut g = Gen::new(40);
+        for _ in 0..100 {
+            let number = LuhnNumber::arbitrary(&mut g);
+            for smaller in number.shrink() {
+                assert!(validate(smaller.as_str()).unwrap());
+                assert!(smaller.as_str().len() < number.as_str().len());
+            }
+            let card = CardNumber::arbitrary(&mut g);
+            for smaller in card.shrink() {
+                assert!(smaller.as_str().len() >= 12);
+                assert!(smaller.as_str().len() < card.as_str().len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_counterexample_is_minimal() {
+        // Fails for every number of 6 or more digits; the shrunk counterexample
+        // should be a 6-digit number
+        fn short(number: LuhnNumber) -> TestResult {
+            TestResult::from_bool(number.as_str().len() < 6)
+        }
+        let mut g = Gen::new(50);
+        let failing = (0..1000)
+            .map(|_| LuhnNumber::arbitrary(&mut g))
+            .find(|n| n.as_str().len() > 20)
+            .unwrap();
+        let mut smallest = failing;
+        while let Some(next) = smallest.shrink().find(|n| short(n.clone()).is_failure()) {
+            smallest = next;
+        }
+        assert_eq!(smallest.as_str().len(), 6);
+    }
+}
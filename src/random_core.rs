@@ -0,0 +1,175 @@
+//! Random generation without the standard library.
+//!
+//! Embedded targets have no `thread_rng`, but often a hardware RNG peripheral
+//! exposed through [`rand_core::RngCore`]. [`random_core`] fills a caller-provided
+//! buffer from such a source, so valid serials can be generated on-target without
+//! `std` or `rand`.
+
+use rand_core::RngCore;
+
+use crate::{payload_checksum, LuhnError, Parity};
+
+/// Largest multiple of 10 that fits in a `u32`; draws at or above it are rejected
+/// so that every digit is equally likely.
+const DIGIT_ZONE: u32 = u32::MAX - u32::MAX % 10;
+
+/// Fills `out` with a random number of `out.len()` ASCII digits and a valid Luhn
+/// checksum, drawing from `rng`.
+///
+/// Digits are drawn without modulo bias. Nothing is allocated unless an error is
+/// returned.
+///
+/// # Arguments
+/// * `rng` - The random source, e.g. a hardware RNG peripheral
+/// * `out` - The buffer to fill; its length is the length of the number
+///
+/// # Returns
+/// * `Ok(())` - If `out` now holds a valid number
+/// * `Err(LuhnError)` - Error if the buffer is too short
+///
+/// # Examples
+/// ```
+/// use luhn_tools::random_core;
+/// use rand_core::{RngCore, impls};
+///
+/// // A stand-in for a hardware RNG peripheral
+/// struct Counter(u32);
+/// impl RngCore for Counter {
+///     fn next_u32(&mut self) -> u32 {
+///         self.0 = self.0.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+///         self.0
+///     }
+///     fn next_u64(&mut self) -> u64 {
+///         impls::next_u64_via_u32(self)
+///     }
+///     fn fill_bytes(&mut self, dest: &mut [u8]) {
+///         impls::fill_bytes_via_next(self, dest)
+///     }
+///     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+///         self.fill_bytes(dest);
+///         Ok(())
+///     }
+/// }
+///
+/// let mut serial = [0u8; 12];
+/// random_core(&mut Counter(7), &mut serial).unwrap();
+/// # #[cfg(feature = "std")]
+/// assert!(luhn_tools::validate(core::str::from_utf8(&serial).unwrap()).unwrap());
+/// ```
+///
+/// # Errors
+/// Returns `InvalidLength` if `out` holds fewer than 2 bytes.
+///
+/// With the `forbid-real-iins` feature, candidates that begin with a real card
+/// issuer prefix are discarded and redrawn.
+pub fn random_core<R: RngCore + ?Sized>(rng: &mut R, out: &mut [u8]) -> Result<(), LuhnError> {
+    let payload_len = match out.len() {
+        0 | 1 => {
+            return Err(LuhnError::InvalidLength(
+                "string must be greater than 1".into(),
+            ))
+        }
+        len => len - 1,
+    };
+
+    loop {
+        for byte in out.iter_mut().take(payload_len) {
+            *byte = b'0' + random_digit(rng);
+        }
+        let payload = out
+            .get(..payload_len)
+            .and_then(|payload| core::str::from_utf8(payload).ok())
+            .unwrap_or_default();
+        let check = payload_checksum(payload, Parity::FromRight)?;
+        if let Some(byte) = out.get_mut(payload_len) {
+            *byte = b'0' + check;
+        }
+
+        if !is_forbidden(out) {
+            return Ok(());
+        }
+    }
+}
+
+/// Returns true if a candidate must be redrawn: with the `forbid-real-iins`
+/// feature, if it begins with a real card issuer prefix.
+fn is_forbidden(candidate: &[u8]) -> bool {
+    #[cfg(feature = "forbid-real-iins")]
+    return crate::iin::is_real_iin(core::str::from_utf8(candidate).unwrap_or_default());
+    #[cfg(not(feature = "forbid-real-iins"))]
+    {
+        let _ = candidate;
+        false
+    }
+}
+
+/// Draws a uniformly distributed digit.
+fn random_digit<R: RngCore + ?Sized>(rng: &mut R) -> u8 {
+    loop {
+        let draw = rng.next_u32();
+        if draw < DIGIT_ZONE {
+            return (draw % 10) as u8;
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::validate;
+    use rand_core::impls;
+
+    /// A deterministic generator stepping through a fixed sequence.
+    struct Sequence(Vec<u32>, usize);
+
+    impl RngCore for Sequence {
+        fn next_u32(&mut self) -> u32 {
+            let value = self.0[self.1 % self.0.len()];
+            self.1 += 1;
+            value
+        }
+        fn next_u64(&mut self) -> u64 {
+            impls::next_u64_via_u32(self)
+        }
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            impls::fill_bytes_via_next(self, dest)
+        }
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_fills_buffer() {
+        let mut rng = Sequence(vec![7, 9, 9, 2, 7, 3, 9, 8, 7, 1], 0);
+        let mut out = [0u8; 11];
+        random_core(&mut rng, &mut out).unwrap();
+        assert_eq!(&out, b"79927398713");
+
+        for len in [2, 16, 64] {
+            let mut out = vec![0u8; len];
+            random_core(&mut Sequence(vec![0, 11, 222, 3333], 0), &mut out).unwrap();
+            assert!(validate(core::str::from_utf8(&out).unwrap()).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_rejects_biased_draws() {
+        // Draws in the incomplete top zone are skipped
+        let mut rng = Sequence(vec![u32::MAX, DIGIT_ZONE, 5], 0);
+        assert_eq!(random_digit(&mut rng), 5);
+        assert_eq!(rng.1, 3);
+    }
+
+    #[test]
+    fn test_short_buffer() {
+        let mut rng = Sequence(vec![1], 0);
+        for len in [0, 1] {
+            assert_eq!(
+                random_core(&mut rng, &mut vec![0u8; len]).unwrap_err(),
+                LuhnError::InvalidLength("string must be greater than 1".to_string())
+            );
+        }
+    }
+}
@@ -0,0 +1,340 @@
+//! Counting and indexing valid numbers without enumerating them.
+//!
+//! Every run of ten numbers sharing a payload holds exactly one valid number, so
+//! the valid numbers of a fixed width are in one-to-one correspondence with their
+//! payloads. Counting them in a range, stepping over `n` of them, or finding the
+//! nearest neighbours of a number is plain arithmetic on the payload plus a look
+//! at the check digits at either end.
+
+use crate::arithmetic::decrement_digits;
+use crate::repair::split_check_digit;
+use crate::{payload_checksum, LuhnError, Parity};
+
+/// Counts the valid numbers between `lo` and `hi`, inclusive.
+///
+/// Both bounds are full numbers of the same width and need not be valid
+/// themselves. The count is computed from the payloads of the bounds, so ranges
+/// far too large to enumerate are answered instantly.
+///
+/// # Arguments
+/// * `lo` - The lower bound, including its last digit
+/// * `hi` - The upper bound, the same width as `lo`
+///
+/// # Returns
+/// * `Ok(u128)` - The number of valid numbers in the range, zero if `lo > hi`
+/// * `Err(LuhnError)` - Error if a bound is malformed or the bounds differ in width
+///
+/// # Examples
+/// ```
+/// use luhn_tools::count_valid_in_range;
+///
+/// // One in ten: every valid 5-digit number under the prefix "12"
+/// assert_eq!(count_valid_in_range("12000", "12999").unwrap(), 100);
+/// assert_eq!(count_valid_in_range("79927398710", "79927398719").unwrap(), 1);
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * A bound fails the usual input checks (empty, spaces, non-numeric, ...)
+/// * A bound is only one character long, or the bounds differ in length
+/// * The range spans more payloads than fit in a `u128`
+pub fn count_valid_in_range(lo: &str, hi: &str) -> Result<u128, LuhnError> {
+    let (lo_payload, lo_last) = split_check_digit(lo)?;
+    let (hi_payload, hi_last) = split_check_digit(hi)?;
+    if lo.len() != hi.len() {
+        return Err(LuhnError::InvalidLength(format!(
+            "range bounds must have the same length ({} and {})",
+            lo.len(),
+            hi.len()
+        )));
+    }
+    if lo > hi {
+        return Ok(0);
+    }
+
+    // Every payload from lo's to hi's has one valid number, except that the ones
+    // at either end may fall outside the bounds.
+    let span = payload_distance(lo_payload, hi_payload)?;
+    let lo_excluded = payload_checksum(lo_payload, Parity::FromRight)? < lo_last;
+    let hi_excluded = payload_checksum(hi_payload, Parity::FromRight)? > hi_last;
+    // span + 1 - excluded, which only overflows if neither end is excluded
+    match u128::from(lo_excluded) + u128::from(hi_excluded) {
+        0 => span.checked_add(1).ok_or_else(span_too_large),
+        excluded => Ok(span - (excluded - 1)),
+    }
+}
+
+/// Returns the `n`th valid number after `start`, counting from zero.
+///
+/// `start` itself is never returned, valid or not; `n = 0` gives the nearest
+/// valid number above it. The width of `start` is preserved, including leading
+/// zeros.
+///
+/// # Arguments
+/// * `start` - The number to count from, including its last digit
+/// * `n` - How many valid numbers to skip
+///
+/// # Returns
+/// * `Ok(String)` - The valid number found, the same width as `start`
+/// * `Err(LuhnError)` - Error if `start` is malformed or the result would overflow
+///
+/// # Examples
+/// ```
+/// use luhn_tools::nth_valid_after;
+///
+/// assert_eq!(nth_valid_after("79927398713", 0).unwrap(), "79927398721");
+/// assert_eq!(nth_valid_after("79927398710", 0).unwrap(), "79927398713");
+/// assert_eq!(nth_valid_after("0000", 99).unwrap(), "1008");
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * `start` fails the usual input checks (empty, spaces, non-numeric, ...)
+/// * `start` is only one character long
+/// * The result would need more digits than `start`
+pub fn nth_valid_after(start: &str, n: usize) -> Result<String, LuhnError> {
+    let (payload, last) = split_check_digit(start)?;
+    let mut digits = payload.as_bytes().to_vec();
+
+    // The valid number sharing start's payload only counts if it lies above it.
+    let first_is_next = payload_checksum(payload, Parity::FromRight)? > last;
+    if !(first_is_next || add(&mut digits, 1)) || !add(&mut digits, n) {
+        return Err(LuhnError::InvalidLength(format!(
+            "incremented payload must not exceed {} digits",
+            digits.len()
+        )));
+    }
+
+    let payload = std::str::from_utf8(&digits).map_err(|e| LuhnError::ParseError(e.to_string()))?;
+    let check = payload_checksum(payload, Parity::FromRight)?;
+    Ok(format!("{}{}", payload, check))
+}
+
+/// Returns the nearest valid number above `value`, of the same width.
+///
+/// `value` need not be valid itself; if it is, the next one is returned. This is
+/// [`nth_valid_after`] with `n = 0`.
+///
+/// # Arguments
+/// * `value` - The number to search from, including its last digit
+///
+/// # Returns
+/// * `Ok(String)` - The smallest valid number greater than `value`
+/// * `Err(LuhnError)` - Error if `value` is malformed or no larger number of its width is valid
+///
+/// # Examples
+/// ```
+/// use luhn_tools::next_valid;
+///
+/// assert_eq!(next_valid("79927398713").unwrap(), "79927398721");
+/// assert_eq!(next_valid("79927398714").unwrap(), "79927398721");
+/// assert_eq!(next_valid("00").unwrap(), "18");
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * `value` fails the usual input checks (empty, spaces, non-numeric, ...)
+/// * `value` is only one character long
+/// * The next valid number would need more digits than `value`
+pub fn next_valid(value: &str) -> Result<String, LuhnError> {
+    nth_valid_after(value, 0)
+}
+
+/// Returns the nearest valid number below `value`, of the same width.
+///
+/// `value` need not be valid itself; if it is, the previous one is returned.
+///
+/// # Arguments
+/// * `value` - The number to search from, including its last digit
+///
+/// # Returns
+/// * `Ok(String)` - The largest valid number less than `value`
+/// * `Err(LuhnError)` - Error if `value` is malformed or no smaller number of its width is valid
+///
+/// # Examples
+/// ```
+/// use luhn_tools::prev_valid;
+///
+/// assert_eq!(prev_valid("79927398721").unwrap(), "79927398713");
+/// assert_eq!(prev_valid("79927398714").unwrap(), "79927398713");
+/// assert_eq!(prev_valid("18").unwrap(), "00");
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * `value` fails the usual input checks (empty, spaces, non-numeric, ...)
+/// * `value` is only one character long
+/// * `value` is at or below the smallest valid number of its width
+pub fn prev_valid(value: &str) -> Result<String, LuhnError> {
+    let (payload, last) = split_check_digit(value)?;
+    let check = payload_checksum(payload, Parity::FromRight)?;
+    if check < last {
+        return Ok(format!("{}{}", payload, check));
+    }
+
+    let mut digits = payload.as_bytes().to_vec();
+    if !decrement_digits(&mut digits) {
+        return Err(LuhnError::NegativeNumber);
+    }
+    let payload = std::str::from_utf8(&digits).map_err(|e| LuhnError::ParseError(e.to_string()))?;
+    let check = payload_checksum(payload, Parity::FromRight)?;
+    Ok(format!("{}{}", payload, check))
+}
+
+/// Returns `hi - lo` for two equal-width decimal payloads with `lo <= hi`.
+///
+/// A shared leading run cancels out, so only the differing tail is parsed.
+fn payload_distance(lo: &str, hi: &str) -> Result<u128, LuhnError> {
+    let shared = lo
+        .bytes()
+        .zip(hi.bytes())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let parse = |digits: &str| match digits.get(shared..).unwrap_or_default() {
+        "" => Ok(0),
+        // The tail is all digits, so parsing can only fail by overflowing
+        tail => tail.parse::<u128>().map_err(|_| span_too_large()),
+    };
+    Ok(parse(hi)? - parse(lo)?)
+}
+
+/// The error for a range with more payloads than a `u128` can count.
+fn span_too_large() -> LuhnError {
+    LuhnError::InvalidLength("range spans more payloads than fit in a u128".to_string())
+}
+
+/// Adds `step` to ASCII `digits` in place, returning false if the sum needs more
+/// digits than are available.
+fn add(digits: &mut [u8], mut step: usize) -> bool {
+    for digit in digits.iter_mut().rev() {
+        if step == 0 {
+            return true;
+        }
+        let sum = usize::from(*digit - b'0') + step % 10;
+        *digit = b'0' + (sum % 10) as u8;
+        step = step / 10 + sum / 10;
+    }
+    step == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate;
+
+    /// Counts valid numbers in a range the slow way.
+    fn brute_count(lo: u32, hi: u32, width: usize) -> u128 {
+        (lo..=hi)
+            .filter(|v| validate(&format!("{:0width$}", v)).unwrap())
+            .count() as u128
+    }
+
+    #[test]
+    fn test_count_matches_brute_force() {
+        for (lo, hi) in [(0, 999), (7, 7), (5, 25), (118, 181), (990, 999), (3, 2)] {
+            assert_eq!(
+                count_valid_in_range(&format!("{:03}", lo), &format!("{:03}", hi)).unwrap(),
+                brute_count(lo, hi, 3),
+                "{lo}..={hi}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_count_large_ranges() {
+        let lo = "0".repeat(40);
+        let hi = format!("{}{}", "0".repeat(10), "9".repeat(30));
+        assert_eq!(count_valid_in_range(&lo, &hi).unwrap(), 10u128.pow(29));
+
+        // A long shared prefix does not limit the span
+        let lo = format!("{}{}", "4".repeat(60), "000");
+        let hi = format!("{}{}", "4".repeat(60), "999");
+        assert_eq!(count_valid_in_range(&lo, &hi).unwrap(), 100);
+
+        let too_large =
+            LuhnError::InvalidLength("range spans more payloads than fit in a u128".to_string());
+        assert_eq!(
+            count_valid_in_range(&"0".repeat(41), &"9".repeat(41)).unwrap_err(),
+            too_large
+        );
+
+        // A payload span of exactly u128::MAX counts u128::MAX + 1 payloads
+        let max = u128::MAX.to_string();
+        let lo = "0".repeat(40);
+        assert_eq!(
+            count_valid_in_range(&lo, &format!("{}9", max)).unwrap_err(),
+            too_large
+        );
+        // unless the valid number at an end falls outside the bounds
+        assert_eq!(
+            count_valid_in_range(&lo, &format!("{}3", max)).unwrap(),
+            u128::MAX
+        );
+    }
+
+    #[test]
+    fn test_nth_valid_after() {
+        let valid: Vec<String> = (0..1000)
+            .map(|v| format!("{:03}", v))
+            .filter(|v| validate(v).unwrap())
+            .collect();
+        for start in [0, 1, 8, 9, 10, 500] {
+            let start = format!("{:03}", start);
+            let above: Vec<&String> = valid.iter().filter(|v| **v > start).collect();
+            for n in [0, 1, 7] {
+                assert_eq!(&nth_valid_after(&start, n).unwrap(), above[n]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_nth_valid_after_overflow() {
+        assert_eq!(nth_valid_after("990", 0).unwrap(), "992");
+        assert_eq!(
+            nth_valid_after("992", 0).unwrap_err(),
+            LuhnError::InvalidLength("incremented payload must not exceed 2 digits".to_string())
+        );
+        assert_eq!(
+            nth_valid_after("10", usize::MAX).unwrap_err(),
+            LuhnError::InvalidLength("incremented payload must not exceed 1 digits".to_string())
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let valid: Vec<String> = (0..1000)
+            .map(|v| format!("{:03}", v))
+            .filter(|v| validate(v).unwrap())
+            .collect();
+        for value in (0..1000).map(|v| format!("{:03}", v)) {
+            let next = valid.iter().find(|v| **v > value);
+            let prev = valid.iter().rev().find(|v| **v < value);
+            assert_eq!(next_valid(&value).ok().as_ref(), next, "{value}");
+            assert_eq!(prev_valid(&value).ok().as_ref(), prev, "{value}");
+        }
+
+        assert_eq!(
+            next_valid("992").unwrap_err(),
+            LuhnError::InvalidLength("incremented payload must not exceed 2 digits".to_string())
+        );
+        assert_eq!(prev_valid("000").unwrap_err(), LuhnError::NegativeNumber);
+    }
+
+    #[test]
+    fn test_error_cases() {
+        assert_eq!(
+            count_valid_in_range("", "10").unwrap_err(),
+            LuhnError::EmptyString
+        );
+        assert_eq!(
+            count_valid_in_range("100", "1000").unwrap_err(),
+            LuhnError::InvalidLength(
+                "range bounds must have the same length (3 and 4)".to_string()
+            )
+        );
+        assert_eq!(
+            nth_valid_after("7", 0).unwrap_err(),
+            LuhnError::InvalidLength("string must be longer than 1 character".to_string())
+        );
+    }
+}
@@ -0,0 +1,315 @@
+//! Streaming validation of check-digit fields in fixed-width record files.
+//!
+//! Bank and clearing files (NACHA and similar) are made of fixed-width records, one
+//! per line, with identifiers at known offsets. [`validate_records`] reads such a file
+//! one record at a time and reports every field that fails validation together with
+//! its record number, so files of any size can be checked without loading them.
+
+use std::io::BufRead;
+
+use crate::limit::Checker;
+use crate::{validate, BatchOutcome, Interruption, Limit, LuhnError};
+
+/// Location of a Luhn-checked field within a fixed-width record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldSpec {
+    /// Zero-based byte offset of the field within the record.
+    pub offset: usize,
+    /// Length of the field in bytes, including its check digit.
+    pub length: usize,
+}
+
+/// Why a field failed validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldFailure {
+    /// The field is well formed but its check digit is wrong.
+    InvalidChecksum,
+    /// The field could not be validated, e.g. the record is too short or the
+    /// field contains non-numeric characters.
+    Error(LuhnError),
+}
+
+/// A field that failed validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordFailure {
+    /// One-based record (line) number within the input.
+    pub record: usize,
+    /// Index of the failing field in the descriptor passed to [`validate_records`].
+    pub field: usize,
+    /// Why the field failed.
+    pub failure: FieldFailure,
+}
+
+/// Validates Luhn-checked fields in a fixed-width record stream.
+///
+/// Records are separated by `\n`; a trailing `\r` is ignored. Only failures are
+/// kept in memory.
+///
+/// # Arguments
+/// * `reader` - The record stream
+/// * `fields` - Descriptor listing the checked fields in each record
+///
+/// # Returns
+/// * `Ok(Vec<RecordFailure>)` - Every failing field, in file order; empty if all pass
+/// * `Err(std::io::Error)` - Error reading from `reader`
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{validate_records, FieldFailure, FieldSpec};
+///
+/// let file = "AB79927398713XY\nAB79927398714XY\n";
+/// let fields = [FieldSpec { offset: 2, length: 11 }];
+///
+/// let failures = validate_records(file.as_bytes(), &fields).unwrap();
+/// assert_eq!(failures.len(), 1);
+/// assert_eq!(failures[0].record, 2);
+/// assert_eq!(failures[0].failure, FieldFailure::InvalidChecksum);
+/// ```
+///
+/// # Errors
+/// Returns an error only if reading from `reader` fails; validation problems are
+/// reported as [`RecordFailure`]s.
+pub fn validate_records<R: BufRead>(
+    reader: R,
+    fields: &[FieldSpec],
+) -> std::io::Result<Vec<RecordFailure>> {
+    let mut failures = Vec::new();
+    scan_records(
+        reader,
+        fields,
+        &mut failures,
+        &mut Limit::default().checker(),
+    )?;
+    Ok(failures)
+}
+
+/// Validates Luhn-checked fields in a fixed-width record stream, stopping early
+/// when `limit` is reached.
+///
+/// The same as [`validate_records`], except that an interrupted run returns the
+/// failures found so far; `completed` is the number of records checked, so a
+/// later run can skip them.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+/// use luhn_tools::{validate_records_until, FieldSpec, Limit};
+///
+/// let file = "AB79927398713XY\nAB79927398714XY\n";
+/// let fields = [FieldSpec { offset: 2, length: 11 }];
+///
+/// let limit = Limit::timeout(Duration::from_secs(5));
+/// let outcome = validate_records_until(file.as_bytes(), &fields, &limit).unwrap();
+/// assert!(outcome.is_complete());
+/// assert_eq!(outcome.value().len(), 1);
+/// ```
+///
+/// # Errors
+/// Returns the same errors as [`validate_records`].
+pub fn validate_records_until<R: BufRead>(
+    reader: R,
+    fields: &[FieldSpec],
+    limit: &Limit,
+) -> std::io::Result<BatchOutcome<Vec<RecordFailure>>> {
+    let mut failures = Vec::new();
+    let (completed, interruption) =
+        scan_records(reader, fields, &mut failures, &mut limit.checker())?;
+    Ok(BatchOutcome::new(failures, completed, interruption))
+}
+
+/// Checks records into `failures`, polling `checker` before each record. Returns
+/// how many records were checked and why it stopped early, if it did.
+fn scan_records<R: BufRead>(
+    mut reader: R,
+    fields: &[FieldSpec],
+    failures: &mut Vec<RecordFailure>,
+    checker: &mut Checker,
+) -> std::io::Result<(usize, Option<Interruption>)> {
+    let mut line = Vec::new();
+    let mut record = 0;
+
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            break;
+        }
+        if let Some(reason) = checker.poll() {
+            return Ok((record, Some(reason)));
+        }
+        record += 1;
+
+        let content = trim_line_ending(&line);
+        for (field, spec) in fields.iter().enumerate() {
+            let failure = match validate_field(content, spec) {
+                Ok(true) => continue,
+                Ok(false) => FieldFailure::InvalidChecksum,
+                Err(error) => FieldFailure::Error(error),
+            };
+            failures.push(RecordFailure {
+                record,
+                field,
+                failure,
+            });
+        }
+    }
+
+    Ok((record, None))
+}
+
+/// Strips a trailing `\n` or `\r\n` from a record.
+fn trim_line_ending(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+/// Extracts a field from a record and validates it.
+fn validate_field(record: &[u8], spec: &FieldSpec) -> Result<bool, LuhnError> {
+    let bytes = spec
+        .offset
+        .checked_add(spec.length)
+        .and_then(|end| record.get(spec.offset..end))
+        .ok_or_else(|| {
+            LuhnError::InvalidLength(format!(
+                "record is shorter than field ending at byte {}",
+                spec.offset.saturating_add(spec.length)
+            ))
+        })?;
+    let value = std::str::from_utf8(bytes).map_err(|e| LuhnError::NonNumeric {
+        index: e.valid_up_to(),
+        character: char::REPLACEMENT_CHARACTER,
+    })?;
+    validate(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIELDS: [FieldSpec; 2] = [
+        FieldSpec {
+            offset: 0,
+            length: 4,
+        },
+        FieldSpec {
+            offset: 5,
+            length: 11,
+        },
+    ];
+
+    #[test]
+    fn test_all_valid() {
+        let file = "1230 79927398713\r\n0018 00000000000\r\n";
+        assert!(validate_records(file.as_bytes(), &FIELDS)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_reports_record_and_field() {
+        let file = "1230 79927398713\n1231 79927398713\n1230 79927398714";
+        let failures = validate_records(file.as_bytes(), &FIELDS).unwrap();
+        assert_eq!(
+            failures,
+            vec![
+                RecordFailure {
+                    record: 2,
+                    field: 0,
+                    failure: FieldFailure::InvalidChecksum,
+                },
+                RecordFailure {
+                    record: 3,
+                    field: 1,
+                    failure: FieldFailure::InvalidChecksum,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_malformed_fields() {
+        let file = "12a0 79927398713\n1230 7992\n";
+        let failures = validate_records(file.as_bytes(), &FIELDS).unwrap();
+        assert_eq!(failures.len(), 2);
+        assert_eq!(
+            failures[0].failure,
+            FieldFailure::Error(LuhnError::NonNumeric {
+                index: 2,
+                character: 'a'
+            })
+        );
+        assert_eq!(
+            failures[1].failure,
+            FieldFailure::Error(LuhnError::InvalidLength(
+                "record is shorter than field ending at byte 16".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_non_utf8_record() {
+        let file: &[u8] = b"12\xff0 79927398713\n";
+        let failures = validate_records(file, &FIELDS).unwrap();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(
+            failures[0].failure,
+            FieldFailure::Error(LuhnError::NonNumeric {
+                index: 2,
+                character: char::REPLACEMENT_CHARACTER
+            })
+        );
+    }
+
+    #[test]
+    fn test_until() {
+        use crate::CancelToken;
+
+        let file = "0018 79927398714\n0018 79927398714\n0018 79927398714\n";
+        let outcome = validate_records_until(file.as_bytes(), &FIELDS, &Limit::default()).unwrap();
+        assert!(outcome.is_complete());
+        assert_eq!(outcome.value().len(), 3);
+
+        let token = CancelToken::new();
+        let limit = Limit {
+            cancel: Some(token.clone()),
+            ..Limit::default()
+        };
+        /// Hands out one record per read, cancelling `token` with the second.
+        struct Lines<'a> {
+            lines: std::str::Lines<'a>,
+            served: usize,
+            token: CancelToken,
+        }
+        impl std::io::Read for Lines<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let Some(line) = self.lines.next() else {
+                    return Ok(0);
+                };
+                self.served += 1;
+                if self.served == 2 {
+                    self.token.cancel();
+                }
+                let line = format!("{}\n", line);
+                buf[..line.len()].copy_from_slice(line.as_bytes());
+                Ok(line.len())
+            }
+        }
+        let reader = std::io::BufReader::new(Lines {
+            lines: file.lines(),
+            served: 0,
+            token,
+        });
+        match validate_records_until(reader, &FIELDS, &limit).unwrap() {
+            BatchOutcome::Interrupted {
+                partial,
+                completed,
+                reason,
+            } => {
+                assert_eq!(completed, 1);
+                assert_eq!(partial.len(), 1);
+                assert_eq!(partial[0].record, 1);
+                assert_eq!(reason, Interruption::Cancelled);
+            }
+            BatchOutcome::Complete(_) => panic!("expected an interruption"),
+        }
+    }
+}
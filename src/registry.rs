@@ -0,0 +1,227 @@
+//! Check digit algorithms looked up by name at runtime.
+//!
+//! A job configured from a file only knows which algorithm it needs once the file
+//! is read. [`AlgorithmRegistry`] maps names such as `"luhn"` to boxed
+//! [`CheckDigit`] implementations, so the choice is a string lookup rather than a
+//! `match` over types compiled into the job.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::damm::Damm;
+use crate::isbn::{Isbn10, Isbn13};
+use crate::mod11_2::Mod11_2;
+use crate::mod97_10::Mod97_10;
+use crate::verhoeff::Verhoeff;
+use crate::{CheckDigit, WeightedMod10};
+
+/// An algorithm stored in an [`AlgorithmRegistry`].
+pub type BoxedCheckDigit = Box<dyn CheckDigit + Send + Sync>;
+
+/// A map from names to check digit algorithms.
+///
+/// Names are matched ignoring ASCII case. [`AlgorithmRegistry::new`] starts with
+/// the algorithms this crate implements:
+/// * `"damm"` - The [Damm algorithm](crate::damm)
+/// * `"isbn-10"`, `"isbn-13"` - [ISBN](crate::isbn) check digits
+/// * `"luhn"` - The Luhn algorithm
+/// * `"mod11-2"` - [ISO/IEC 7064 MOD 11-2](crate::mod11_2), as used by ORCID
+/// * `"mod97-10"` - [ISO/IEC 7064 MOD 97-10](crate::mod97_10), with two check
+///   digits
+/// * `"upc"` - UPC/EAN 3-1 weighting
+/// * `"verhoeff"` - The [Verhoeff algorithm](crate::verhoeff)
+///
+/// Other algorithms are added with [`AlgorithmRegistry::register`] by implementing
+/// [`CheckDigit`] for them.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::AlgorithmRegistry;
+///
+/// let registry = AlgorithmRegistry::new();
+/// // e.g. read from a config file
+/// let name = "Luhn";
+///
+/// let algorithm = registry.get(name).expect("unknown algorithm");
+/// assert!(algorithm.validate("79927398713").unwrap());
+/// assert_eq!(registry.get("damm").unwrap().append("572").unwrap(), "5724");
+/// assert!(registry.get("crc32").is_none());
+/// ```
+pub struct AlgorithmRegistry {
+    algorithms: BTreeMap<String, BoxedCheckDigit>,
+}
+
+impl AlgorithmRegistry {
+    /// Returns a registry holding the built-in algorithms.
+    pub fn new() -> Self {
+        let mut registry = AlgorithmRegistry::empty();
+        registry.register("luhn", WeightedMod10::LUHN);
+        registry.register("damm", Damm);
+        registry.register("isbn-10", Isbn10);
+        registry.register("isbn-13", Isbn13);
+        registry.register("mod11-2", Mod11_2);
+        registry.register("mod97-10", Mod97_10);
+        registry.register("upc", WeightedMod10::UPC);
+        registry.register("verhoeff", Verhoeff);
+        registry
+    }
+
+    /// Returns a registry with no algorithms.
+    pub fn empty() -> Self {
+        AlgorithmRegistry {
+            algorithms: BTreeMap::new(),
+        }
+    }
+
+    /// Registers `algorithm` under `name`, returning the algorithm it replaces, if any.
+    ///
+    /// # Examples
+    /// ```
+    /// use luhn_tools::{AlgorithmRegistry, WeightedMod10};
+    ///
+    /// let mut registry = AlgorithmRegistry::empty();
+    /// let weights = WeightedMod10 { weights: &[7, 3, 1], modulus: 10, reduce_products: false };
+    /// assert!(registry.register("ref-731", weights).is_none());
+    /// assert_eq!(registry.get("REF-731").unwrap().compute("12345").unwrap(), "3");
+    /// ```
+    pub fn register<A>(&mut self, name: &str, algorithm: A) -> Option<BoxedCheckDigit>
+    where
+        A: CheckDigit + Send + Sync + 'static,
+    {
+        self.algorithms
+            .insert(name.to_ascii_lowercase(), Box::new(algorithm))
+    }
+
+    /// Returns the algorithm registered under `name`, ignoring ASCII case.
+    pub fn get(&self, name: &str) -> Option<&(dyn CheckDigit + Send + Sync)> {
+        self.algorithms
+            .get(&name.to_ascii_lowercase())
+            .map(|algorithm| &**algorithm)
+    }
+
+    /// Removes and returns the algorithm registered under `name`.
+    pub fn remove(&mut self, name: &str) -> Option<BoxedCheckDigit> {
+        self.algorithms.remove(&name.to_ascii_lowercase())
+    }
+
+    /// Returns whether an algorithm is registered under `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Iterates over the registered names in sorted order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.algorithms.keys().map(String::as_str)
+    }
+}
+
+impl Default for AlgorithmRegistry {
+    fn default() -> Self {
+        AlgorithmRegistry::new()
+    }
+}
+
+impl fmt::Debug for AlgorithmRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.names()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LuhnError, Parity};
+
+    #[test]
+    fn test_builtins() {
+        let registry = AlgorithmRegistry::default();
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            ["damm", "isbn-10", "isbn-13", "luhn", "mod11-2", "mod97-10", "upc", "verhoeff"]
+        );
+        assert_eq!(
+            format!("{:?}", registry),
+            r#"{"damm", "isbn-10", "isbn-13", "luhn", "mod11-2", "mod97-10", "upc", "verhoeff"}"#
+        );
+
+        let luhn = registry.get("LUHN").unwrap();
+        assert_eq!(luhn.append("7992739871").unwrap(), "79927398713");
+        assert_eq!(
+            luhn.validate("7992 7398713").unwrap_err(),
+            LuhnError::ContainsSpaces {
+                index: 4,
+                character: ' '
+            }
+        );
+        assert!(registry
+            .get("upc")
+            .unwrap()
+            .validate("036000291452")
+            .unwrap());
+        assert!(registry.get("damm").unwrap().validate("5724").unwrap());
+        assert!(registry.get("Verhoeff").unwrap().validate("2363").unwrap());
+        assert!(registry
+            .get("isbn-10")
+            .unwrap()
+            .validate("0-306-40615-2")
+            .unwrap());
+        assert_eq!(
+            registry
+                .get("MOD11-2")
+                .unwrap()
+                .compute("000000021694233")
+                .unwrap(),
+            "X"
+        );
+        let mod97 = registry.get("mod97-10").unwrap();
+        assert_eq!(mod97.append("794").unwrap(), "79444");
+        assert!(mod97.validate("79444").unwrap());
+    }
+
+    #[test]
+    fn test_register_and_remove() {
+        let mut registry = AlgorithmRegistry::empty();
+        assert_eq!(registry.names().count(), 0);
+
+        let left = crate::Luhn::builder().parity(Parity::FromLeft).build();
+        assert!(registry.register("Luhn-Left", left).is_none());
+        assert!(registry.contains("luhn-left"));
+        assert_eq!(
+            registry
+                .get("luhn-left")
+                .unwrap()
+                .compute("7992739871")
+                .unwrap(),
+            "2"
+        );
+        assert!(registry
+            .register("luhn-left", WeightedMod10::LUHN)
+            .is_some());
+        assert_eq!(
+            registry
+                .get("luhn-left")
+                .unwrap()
+                .compute("7992739871")
+                .unwrap(),
+            "3"
+        );
+
+        assert!(registry.remove("LUHN-LEFT").is_some());
+        assert!(registry.get("luhn-left").is_none());
+        assert!(registry.remove("luhn-left").is_none());
+    }
+
+    #[test]
+    fn test_shared_across_threads() {
+        let registry = std::sync::Arc::new(AlgorithmRegistry::new());
+        let handles: Vec<_> = ["luhn", "damm"]
+            .into_iter()
+            .map(|name| {
+                let registry = std::sync::Arc::clone(&registry);
+                std::thread::spawn(move || registry.get(name).unwrap().append("572").unwrap())
+            })
+            .collect();
+        let results: Vec<String> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(results, ["5728", "5724"]);
+    }
+}
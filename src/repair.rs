@@ -0,0 +1,371 @@
+//! Check digit completion and repair.
+//!
+//! Helpers for numbers that are not valid yet: partial input still missing its
+//! check digit, and numbers whose check digit or payload contains a mistake.
+//! [`verify_or_fix`] combines validation and the repair suggesters in one call.
+
+use crate::{handle_errors, luhn_contribution, luhn_sum, payload_checksum, LuhnError, Parity};
+
+/// Returns the check digit that completes a partial number.
+///
+/// Given everything but the final digit, returns the digit that must be entered
+/// last for the number to be valid. This is the same digit [`generate`](crate::generate)
+/// would append, returned as a number for keypad feedback.
+///
+/// # Arguments
+/// * `partial` - The number typed so far, without its check digit
+///
+/// # Returns
+/// * `Ok(u8)` - The required check digit (0–9)
+/// * `Err(LuhnError)` - Error if the partial input is malformed
+///
+/// # Examples
+/// ```
+/// use luhn_tools::required_check_digit;
+///
+/// assert_eq!(required_check_digit("7992739871").unwrap(), 3);
+/// assert_eq!(required_check_digit("1").unwrap(), 8);
+/// ```
+///
+/// # Errors
+/// Returns an error if the input fails the usual input checks (empty, spaces,
+/// non-numeric, ...).
+pub fn required_check_digit(partial: &str) -> Result<u8, LuhnError> {
+    payload_checksum(partial, Parity::FromRight)
+}
+
+/// Replaces the last digit of a number so that it becomes Luhn-valid.
+///
+/// Numbers that are already valid are returned unchanged. Only the check digit is
+/// touched; the payload is assumed correct.
+///
+/// # Arguments
+/// * `value` - A number including its (possibly wrong) check digit
+///
+/// # Returns
+/// * `Ok(String)` - The number with a correct check digit
+/// * `Err(LuhnError)` - Error if the input is malformed
+///
+/// # Examples
+/// ```
+/// use luhn_tools::fix_check_digit;
+///
+/// assert_eq!(fix_check_digit("79927398710").unwrap(), "79927398713");
+/// assert_eq!(fix_check_digit("79927398713").unwrap(), "79927398713");
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * The input fails the usual input checks (empty, spaces, non-numeric, ...)
+/// * The input is only one character long
+pub fn fix_check_digit(value: &str) -> Result<String, LuhnError> {
+    let (payload, _) = split_check_digit(value)?;
+    let check_digit = required_check_digit(payload)?;
+    Ok(format!("{}{}", payload, check_digit))
+}
+
+/// Lists every number that differs from `value` in exactly one digit and is valid.
+///
+/// Luhn detects every single-digit error, so for an invalid number each position
+/// has at most one replacement digit that makes it valid; the result holds one
+/// candidate per such position, ordered from left to right. A valid number yields
+/// no suggestions.
+///
+/// # Arguments
+/// * `value` - A number including its check digit
+///
+/// # Returns
+/// * `Ok(Vec<String>)` - The valid single-digit corrections, left to right
+/// * `Err(LuhnError)` - Error if the input is malformed
+///
+/// # Examples
+/// ```
+/// use luhn_tools::suggest_single_digit_fixes;
+///
+/// let fixes = suggest_single_digit_fixes("79927398714").unwrap();
+/// assert!(fixes.contains(&"79927398713".to_string()));
+/// assert!(fixes.contains(&"69927398714".to_string()));
+/// assert!(suggest_single_digit_fixes("79927398713").unwrap().is_empty());
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * The input fails the usual input checks (empty, spaces, non-numeric, ...)
+/// * The input is only one character long
+pub fn suggest_single_digit_fixes(value: &str) -> Result<Vec<String>, LuhnError> {
+    split_check_digit(value)?;
+    let sum = luhn_sum(value, false)?;
+    if sum == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut fixes = Vec::new();
+    let mut candidate = value.as_bytes().to_vec();
+    let len = candidate.len();
+    for index in 0..len {
+        let original = candidate[index];
+        let doubled = (len - 1 - index) % 2 == 1;
+        let current = luhn_contribution(original - b'0', doubled);
+        // The replacement must change this position's contribution by -sum (mod 10).
+        let target = (current + 10 - sum as u8) % 10;
+        if let Some(digit) = (0..10u8).find(|&d| luhn_contribution(d, doubled) == target) {
+            candidate[index] = b'0' + digit;
+            fixes.push(String::from_utf8_lossy(&candidate).into_owned());
+            candidate[index] = original;
+        }
+    }
+    Ok(fixes)
+}
+
+/// Lists every valid number reachable by swapping one pair of adjacent digits.
+///
+/// Adjacent transpositions are, with single-digit typos, the most common data-entry
+/// error. Luhn detects every such swap except `09` ↔ `90`, so a valid number yields
+/// no suggestions. Candidates are ordered by the position of the swap, left to
+/// right.
+///
+/// # Arguments
+/// * `value` - A number including its check digit
+///
+/// # Returns
+/// * `Ok(Vec<String>)` - The valid numbers one adjacent swap away, left to right
+/// * `Err(LuhnError)` - Error if the input is malformed
+///
+/// # Examples
+/// ```
+/// use luhn_tools::suggest_transposition_fixes;
+///
+/// assert_eq!(
+///     suggest_transposition_fixes("79927389713").unwrap(),
+///     vec!["79927398713"]
+/// );
+/// assert!(suggest_transposition_fixes("79927398713").unwrap().is_empty());
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * The input fails the usual input checks (empty, spaces, non-numeric, ...)
+/// * The input is only one character long
+pub fn suggest_transposition_fixes(value: &str) -> Result<Vec<String>, LuhnError> {
+    split_check_digit(value)?;
+    if luhn_sum(value, false)? == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut fixes = Vec::new();
+    let mut candidate = value.as_bytes().to_vec();
+    for index in 0..candidate.len() - 1 {
+        if candidate[index] == candidate[index + 1] {
+            continue;
+        }
+        candidate.swap(index, index + 1);
+        let swapped = String::from_utf8_lossy(&candidate).into_owned();
+        if luhn_sum(&swapped, false)? == 0 {
+            fixes.push(swapped);
+        }
+        candidate.swap(index, index + 1);
+    }
+    Ok(fixes)
+}
+
+/// The result of [`verify_or_fix`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LuhnOutcome {
+    /// The number is already valid
+    Valid,
+    /// The number was invalid and exactly one adjacent transposition repairs it
+    Fixed(String),
+    /// The number is invalid and no unique repair exists; holds every valid number
+    /// one adjacent transposition or one digit change away, transpositions first
+    Unfixable(Vec<String>),
+}
+
+/// Validates a number and, if it is invalid, tries to repair a single data-entry error.
+///
+/// Luhn cannot locate a single wrong digit: every position has exactly one
+/// replacement that makes the number valid. Only an adjacent transposition can be
+/// pinned down, so a number is reported as [`LuhnOutcome::Fixed`] when exactly one
+/// adjacent swap makes it valid. Otherwise the candidates from
+/// [`suggest_transposition_fixes`] and [`suggest_single_digit_fixes`] are returned
+/// for a human to choose from.
+///
+/// # Arguments
+/// * `value` - A number including its check digit
+///
+/// # Returns
+/// * `Ok(LuhnOutcome)` - Whether the number is valid, was repaired, or needs review
+/// * `Err(LuhnError)` - Error if the input is malformed
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{verify_or_fix, LuhnOutcome};
+///
+/// assert_eq!(verify_or_fix("79927398713").unwrap(), LuhnOutcome::Valid);
+/// assert_eq!(
+///     verify_or_fix("79927389713").unwrap(),
+///     LuhnOutcome::Fixed("79927398713".to_string())
+/// );
+/// assert!(matches!(
+///     verify_or_fix("79927398710").unwrap(),
+///     LuhnOutcome::Unfixable(candidates) if candidates.contains(&"79927398713".to_string())
+/// ));
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * The input fails the usual input checks (empty, spaces, non-numeric, ...)
+/// * The input is only one character long
+pub fn verify_or_fix(value: &str) -> Result<LuhnOutcome, LuhnError> {
+    split_check_digit(value)?;
+    if luhn_sum(value, false)? == 0 {
+        return Ok(LuhnOutcome::Valid);
+    }
+
+    let mut candidates = suggest_transposition_fixes(value)?;
+    if let [fix] = candidates.as_slice() {
+        return Ok(LuhnOutcome::Fixed(fix.clone()));
+    }
+    candidates.extend(suggest_single_digit_fixes(value)?);
+    Ok(LuhnOutcome::Unfixable(candidates))
+}
+
+/// Validates the input and splits it into payload and check digit.
+pub(crate) fn split_check_digit(value: &str) -> Result<(&str, u8), LuhnError> {
+    handle_errors(value)?;
+    match value.as_bytes().split_last() {
+        Some((&check, payload)) if !payload.is_empty() => {
+            Ok((value.get(..payload.len()).unwrap_or_default(), check - b'0'))
+        }
+        _ => Err(LuhnError::InvalidLength(
+            "string must be longer than 1 character".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate, validate};
+
+    #[test]
+    fn test_required_check_digit() {
+        for partial in ["0", "1", "7992739871", "799273987100000", "00000"] {
+            let digit = required_check_digit(partial).unwrap();
+            assert!(digit <= 9);
+            let complete = format!("{}{}", partial, digit);
+            assert!(validate(&complete).unwrap());
+            assert_eq!(generate(partial, None).unwrap(), complete);
+        }
+    }
+
+    #[test]
+    fn test_fix_check_digit() {
+        for check in 0..=9 {
+            let value = format!("7992739871{}", check);
+            assert_eq!(fix_check_digit(&value).unwrap(), "79927398713");
+        }
+        assert_eq!(fix_check_digit("00").unwrap(), "00");
+        assert_eq!(fix_check_digit("15").unwrap(), "18");
+    }
+
+    #[test]
+    fn test_single_digit_fixes() {
+        let fixes = suggest_single_digit_fixes("79927398714").unwrap();
+        assert_eq!(fixes.len(), 11);
+        for fix in &fixes {
+            assert!(validate(fix).unwrap());
+            let changed = fix
+                .bytes()
+                .zip("79927398714".bytes())
+                .filter(|(a, b)| a != b);
+            assert_eq!(changed.count(), 1);
+        }
+        assert_eq!(fixes.last().unwrap(), "79927398713");
+
+        // A typo in the payload is among the suggestions
+        let fixes = suggest_single_digit_fixes("79927598713").unwrap();
+        assert!(fixes.contains(&"79927398713".to_string()));
+        assert!(suggest_single_digit_fixes("18").unwrap().is_empty());
+        assert_eq!(
+            suggest_single_digit_fixes("1").unwrap_err(),
+            LuhnError::InvalidLength("string must be longer than 1 character".to_string())
+        );
+    }
+
+    #[test]
+    fn test_transposition_fixes() {
+        assert_eq!(
+            suggest_transposition_fixes("79927398731").unwrap(),
+            vec!["97927398731", "79297398731", "79927398713"]
+        );
+        assert_eq!(
+            suggest_transposition_fixes("97927398713").unwrap(),
+            vec!["79927398713", "99727398713", "97927398731"]
+        );
+        // Repeated digits swap to the same number, so they are skipped
+        assert!(suggest_transposition_fixes("11").unwrap().is_empty());
+        assert!(suggest_transposition_fixes("18").unwrap().is_empty());
+        assert_eq!(
+            suggest_transposition_fixes("7992a").unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 4,
+                character: 'a'
+            }
+        );
+    }
+
+    #[test]
+    fn test_verify_or_fix() {
+        assert_eq!(verify_or_fix("79927398713").unwrap(), LuhnOutcome::Valid);
+        assert_eq!(
+            verify_or_fix("79927389713").unwrap(),
+            LuhnOutcome::Fixed("79927398713".to_string())
+        );
+
+        // Several transpositions work, so all candidates are returned
+        let mut expected = suggest_transposition_fixes("79927398731").unwrap();
+        assert_eq!(expected.len(), 3);
+        expected.extend(suggest_single_digit_fixes("79927398731").unwrap());
+        assert_eq!(
+            verify_or_fix("79927398731").unwrap(),
+            LuhnOutcome::Unfixable(expected)
+        );
+
+        // No transposition works, so only single-digit changes are suggested
+        assert_eq!(
+            verify_or_fix("79927398710").unwrap(),
+            LuhnOutcome::Unfixable(suggest_single_digit_fixes("79927398710").unwrap())
+        );
+        assert_eq!(verify_or_fix("").unwrap_err(), LuhnError::EmptyString);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            fix_check_digit("1").unwrap_err(),
+            LuhnError::InvalidLength("string must be longer than 1 character".to_string())
+        );
+        assert_eq!(
+            fix_check_digit("7992 7398 710").unwrap_err(),
+            LuhnError::ContainsSpaces {
+                index: 4,
+                character: ' '
+            }
+        );
+        assert_eq!(
+            required_check_digit("").unwrap_err(),
+            LuhnError::EmptyString
+        );
+        assert_eq!(
+            required_check_digit("12a").unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 2,
+                character: 'a'
+            }
+        );
+        assert_eq!(
+            required_check_digit("1-2").unwrap_err(),
+            LuhnError::NegativeNumber
+        );
+    }
+}
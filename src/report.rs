@@ -0,0 +1,183 @@
+//! Detailed validation reports.
+//!
+//! A bare `false` from [`validate`](crate::validate) is hard to explain to a customer
+//! disputing a rejected number. [`validate_report`] returns the intermediate values
+//! instead: the digits that were checked, the Luhn sum, and the expected and found
+//! check digits.
+
+use crate::{
+    luhn_contribution, payload_checksum, validate_normalized, LuhnError, Parity, ValidateOptions,
+};
+
+/// The details behind a validation result.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ValidationReport {
+    /// The digits that were checked, after any normalization.
+    pub digits: String,
+    /// Number of digits checked, including the check digit.
+    pub length: usize,
+    /// The Luhn sum over all digits, including the check digit. The number is
+    /// valid when this is a multiple of 10.
+    pub sum: u64,
+    /// The check digit the payload requires.
+    pub expected_check_digit: u8,
+    /// The check digit the number carries.
+    pub found_check_digit: u8,
+    /// Whether the number is valid.
+    pub valid: bool,
+    /// Whether normalization (trimming, separators, ...) changed the input.
+    pub formatting_stripped: bool,
+}
+
+/// Validates a number and reports how the result was reached.
+///
+/// # Arguments
+/// * `value` - A string slice that holds the number to validate
+///
+/// # Returns
+/// * `Ok(ValidationReport)` - The details, for valid and invalid numbers alike
+/// * `Err(LuhnError)` - Error if the input is malformed
+///
+/// # Examples
+/// ```
+/// use luhn_tools::validate_report;
+///
+/// let report = validate_report("79927398714").unwrap();
+/// assert!(!report.valid);
+/// assert_eq!(report.sum, 71);
+/// assert_eq!(report.expected_check_digit, 3);
+/// assert_eq!(report.found_check_digit, 4);
+/// ```
+///
+/// # Errors
+/// Returns the same errors as [`validate`](crate::validate).
+pub fn validate_report(value: &str) -> Result<ValidationReport, LuhnError> {
+    validate_report_with(value, None)
+}
+
+/// Validates a number with options and reports how the result was reached.
+///
+/// # Arguments
+/// * `value` - A string slice that holds the number to validate
+/// * `options` - Optional normalization and policy settings
+///
+/// # Returns
+/// * `Ok(ValidationReport)` - The details, for valid and invalid numbers alike
+/// * `Err(LuhnError)` - Error if the input is malformed or violates a policy
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{validate_report_with, ValidateOptions};
+///
+/// let options = Some(ValidateOptions { allow_separators: true, ..Default::default() });
+/// let report = validate_report_with("7992 7398 713", options).unwrap();
+/// assert!(report.valid);
+/// assert!(report.formatting_stripped);
+/// assert_eq!(report.digits, "79927398713");
+/// ```
+///
+/// # Errors
+/// Returns the same errors as [`validate_with`](crate::validate_with).
+pub fn validate_report_with(
+    value: &str,
+    options: Option<ValidateOptions>,
+) -> Result<ValidationReport, LuhnError> {
+    let options = options.unwrap_or_default();
+    let (digits, valid) = validate_normalized(value, &options)?;
+
+    // Validation succeeded, so `digits` is at least two ASCII digits.
+    let payload = digits.get(..digits.len() - 1).unwrap_or_default();
+    let expected_check_digit = payload_checksum(payload, options.parity)?;
+
+    Ok(ValidationReport {
+        length: digits.len(),
+        sum: full_sum(&digits, options.parity),
+        expected_check_digit,
+        found_check_digit: digits.bytes().last().map_or(0, |b| b - b'0'),
+        valid,
+        formatting_stripped: &*digits != value,
+        digits: digits.into_owned(),
+    })
+}
+
+/// Computes the unreduced Luhn sum of a well-formed number.
+fn full_sum(digits: &str, parity: Parity) -> u64 {
+    let rightmost_doubled = match parity {
+        Parity::FromRight => false,
+        Parity::FromLeft => digits.len() % 2 == 1,
+    };
+    digits
+        .bytes()
+        .rev()
+        .enumerate()
+        .map(|(i, b)| {
+            u64::from(luhn_contribution(
+                b - b'0',
+                (i % 2 == 0) == rightmost_doubled,
+            ))
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_report() {
+        let report = validate_report("79927398713").unwrap();
+        assert_eq!(
+            report,
+            ValidationReport {
+                digits: "79927398713".to_string(),
+                length: 11,
+                sum: 70,
+                expected_check_digit: 3,
+                found_check_digit: 3,
+                valid: true,
+                formatting_stripped: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_sum_matches_validity() {
+        for value in ["18", "10", "4111111111111111", "4111111111111112", "00"] {
+            let report = validate_report(value).unwrap();
+            assert_eq!(report.valid, report.sum % 10 == 0);
+            assert_eq!(
+                report.valid,
+                report.expected_check_digit == report.found_check_digit
+            );
+        }
+    }
+
+    #[test]
+    fn test_left_parity() {
+        let options = Some(ValidateOptions {
+            parity: Parity::FromLeft,
+            ..Default::default()
+        });
+        let report = validate_report_with("79927398713", options).unwrap();
+        assert!(!report.valid);
+        assert_eq!(report.expected_check_digit, 2);
+        assert_ne!(report.sum % 10, 0);
+        assert!(validate_report_with("79927398712", options).unwrap().valid);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(validate_report("").unwrap_err(), LuhnError::EmptyString);
+        assert_eq!(
+            validate_report("1").unwrap_err(),
+            LuhnError::InvalidLength("string must be longer than 1 character".to_string())
+        );
+        assert_eq!(
+            validate_report("7992 7398 713").unwrap_err(),
+            LuhnError::ContainsSpaces {
+                index: 4,
+                character: ' '
+            }
+        );
+    }
+}
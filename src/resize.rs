@@ -0,0 +1,291 @@
+//! Shortening and lengthening Luhn numbers to a target width.
+//!
+//! Systems with different fixed field widths often need the same identifier at a
+//! different length. [`truncate_to`] and [`extend_to`] remove or add payload digits,
+//! recompute the check digit, and report exactly what changed.
+
+use crate::repair::split_check_digit;
+use crate::{generate, LuhnError};
+
+/// The end of the payload where digits are removed or added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ResizeSide {
+    /// The start of the number. Extending with zeros here keeps the numeric value.
+    #[default]
+    Leading,
+    /// The end of the payload, just before the check digit.
+    Trailing,
+}
+
+/// The outcome of resizing a number.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ResizeReport {
+    /// The resized number, including its recomputed check digit.
+    pub value: String,
+    /// Payload digits that were removed (empty when extending).
+    pub removed: String,
+    /// Filler digits that were added (empty when truncating).
+    pub added: String,
+    /// The check digit of the input.
+    pub previous_check_digit: u8,
+    /// The check digit of the resized number.
+    pub check_digit: u8,
+}
+
+impl ResizeReport {
+    /// Returns true if the check digit differs from the input's.
+    pub fn check_digit_changed(&self) -> bool {
+        self.previous_check_digit != self.check_digit
+    }
+}
+
+/// Shortens a number to `length` digits by dropping payload digits and recomputing
+/// the check digit.
+///
+/// The input's check digit is discarded, so the input does not need to be valid.
+///
+/// # Arguments
+/// * `value` - A number including its trailing check digit
+/// * `length` - The target length, including the check digit
+/// * `side` - Which end of the payload to drop digits from
+///
+/// # Returns
+/// * `Ok(ResizeReport)` - The shortened number and what changed
+/// * `Err(LuhnError)` - Error if the input is malformed or the length is invalid
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{truncate_to, ResizeSide};
+///
+/// let report = truncate_to("79927398713", 8, ResizeSide::Leading).unwrap();
+/// assert_eq!(report.value, "27398718");
+/// assert_eq!(report.removed, "799");
+/// assert!(report.check_digit_changed());
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * The input fails the usual input checks (empty, spaces, non-numeric, ...)
+/// * The input is only one character long
+/// * `length` is less than 2 or greater than the input length
+pub fn truncate_to(
+    value: &str,
+    length: usize,
+    side: ResizeSide,
+) -> Result<ResizeReport, LuhnError> {
+    let (payload, previous_check_digit) = split_check_digit(value)?;
+    check_target_length(length)?;
+    if length > value.len() {
+        return Err(LuhnError::InvalidLength(format!(
+            "cannot truncate a {} character string to {}",
+            value.len(),
+            length
+        )));
+    }
+
+    let keep = length - 1;
+    let (kept, removed) = match side {
+        ResizeSide::Leading => {
+            let (removed, kept) = payload.split_at(payload.len() - keep);
+            (kept, removed)
+        }
+        ResizeSide::Trailing => payload.split_at(keep),
+    };
+
+    finish(
+        kept,
+        removed.to_string(),
+        String::new(),
+        previous_check_digit,
+    )
+}
+
+/// Lengthens a number to `length` digits by inserting filler digits into the payload
+/// and recomputing the check digit.
+///
+/// The input's check digit is discarded, so the input does not need to be valid.
+///
+/// # Arguments
+/// * `value` - A number including its trailing check digit
+/// * `length` - The target length, including the check digit
+/// * `side` - Which end of the payload to add digits to
+/// * `filler` - The digit to insert
+///
+/// # Returns
+/// * `Ok(ResizeReport)` - The lengthened number and what changed
+/// * `Err(LuhnError)` - Error if the input is malformed or the length is invalid
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{extend_to, ResizeSide};
+///
+/// // Zeros at the front keep the value and the check digit
+/// let report = extend_to("79927398713", 14, ResizeSide::Leading, '0').unwrap();
+/// assert_eq!(report.value, "00079927398713");
+/// assert!(!report.check_digit_changed());
+///
+/// let report = extend_to("79927398713", 13, ResizeSide::Trailing, '0').unwrap();
+/// assert_eq!(report.value, "7992739871003");
+/// assert_eq!(report.added, "00");
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * The input fails the usual input checks (empty, spaces, non-numeric, ...)
+/// * The input is only one character long
+/// * `filler` is not an ASCII digit
+/// * `length` is less than the input length
+/// * A number of `length` digits cannot be allocated
+pub fn extend_to(
+    value: &str,
+    length: usize,
+    side: ResizeSide,
+    filler: char,
+) -> Result<ResizeReport, LuhnError> {
+    let (payload, previous_check_digit) = split_check_digit(value)?;
+    if !filler.is_ascii_digit() {
+        return Err(LuhnError::InvalidConfiguration(
+            "filler must be an ASCII digit".to_string(),
+        ));
+    }
+    if length < value.len() {
+        return Err(LuhnError::InvalidLength(format!(
+            "cannot extend a {} character string to {}",
+            value.len(),
+            length
+        )));
+    }
+
+    let cannot_allocate =
+        |_| LuhnError::InvalidLength(format!("cannot allocate a number extended to {}", length));
+    let mut added = String::new();
+    added
+        .try_reserve(length - value.len())
+        .map_err(cannot_allocate)?;
+    added.extend(core::iter::repeat(filler).take(length - value.len()));
+    let mut extended = String::new();
+    extended.try_reserve(length).map_err(cannot_allocate)?;
+    match side {
+        ResizeSide::Leading => {
+            extended.push_str(&added);
+            extended.push_str(payload);
+        }
+        ResizeSide::Trailing => {
+            extended.push_str(payload);
+            extended.push_str(&added);
+        }
+    }
+
+    finish(&extended, String::new(), added, previous_check_digit)
+}
+
+/// Rejects target lengths that cannot hold a payload and a check digit.
+fn check_target_length(length: usize) -> Result<(), LuhnError> {
+    if length < 2 {
+        return Err(LuhnError::InvalidLength(
+            "target length must be greater than 1".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Recomputes the check digit for a resized payload and builds the report.
+fn finish(
+    payload: &str,
+    removed: String,
+    added: String,
+    previous_check_digit: u8,
+) -> Result<ResizeReport, LuhnError> {
+    let value = generate(payload, None)?;
+    let check_digit = value.as_bytes().last().map_or(0, |b| b - b'0');
+
+    Ok(ResizeReport {
+        value,
+        removed,
+        added,
+        previous_check_digit,
+        check_digit,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate;
+
+    #[test]
+    fn test_truncate() {
+        let report = truncate_to("79927398713", 8, ResizeSide::Leading).unwrap();
+        assert_eq!(report.value, "27398718");
+        assert_eq!(report.removed, "799");
+        assert_eq!(report.added, "");
+        assert_eq!(report.previous_check_digit, 3);
+        assert_eq!(report.check_digit, 8);
+
+        let report = truncate_to("79927398713", 8, ResizeSide::Trailing).unwrap();
+        assert_eq!(report.value, "79927398");
+        assert_eq!(report.removed, "871");
+        assert!(validate(&report.value).unwrap());
+    }
+
+    #[test]
+    fn test_truncate_to_same_length() {
+        let report = truncate_to("79927398713", 11, ResizeSide::Leading).unwrap();
+        assert_eq!(report.value, "79927398713");
+        assert_eq!(report.removed, "");
+        assert!(!report.check_digit_changed());
+
+        // An invalid input is repaired
+        let report = truncate_to("79927398710", 11, ResizeSide::Leading).unwrap();
+        assert_eq!(report.value, "79927398713");
+        assert!(report.check_digit_changed());
+    }
+
+    #[test]
+    fn test_extend() {
+        let report = extend_to("79927398713", 14, ResizeSide::Leading, '0').unwrap();
+        assert_eq!(report.value, "00079927398713");
+        assert_eq!(report.added, "000");
+        assert!(!report.check_digit_changed());
+
+        let report = extend_to("79927398713", 13, ResizeSide::Trailing, '9').unwrap();
+        assert_eq!(report.added, "99");
+        assert!(report.value.starts_with("799273987199"));
+        assert!(validate(&report.value).unwrap());
+    }
+
+    #[test]
+    fn test_error_cases() {
+        assert_eq!(
+            truncate_to("", 2, ResizeSide::Leading).unwrap_err(),
+            LuhnError::EmptyString
+        );
+        assert_eq!(
+            truncate_to("1", 2, ResizeSide::Leading).unwrap_err(),
+            LuhnError::InvalidLength("string must be longer than 1 character".to_string())
+        );
+        assert_eq!(
+            truncate_to("1230", 1, ResizeSide::Leading).unwrap_err(),
+            LuhnError::InvalidLength("target length must be greater than 1".to_string())
+        );
+        assert_eq!(
+            truncate_to("1230", 5, ResizeSide::Leading).unwrap_err(),
+            LuhnError::InvalidLength("cannot truncate a 4 character string to 5".to_string())
+        );
+        assert_eq!(
+            extend_to("1230", 3, ResizeSide::Leading, '0').unwrap_err(),
+            LuhnError::InvalidLength("cannot extend a 4 character string to 3".to_string())
+        );
+        assert_eq!(
+            extend_to("1230", 6, ResizeSide::Leading, 'x').unwrap_err(),
+            LuhnError::InvalidConfiguration("filler must be an ASCII digit".to_string())
+        );
+        assert_eq!(
+            extend_to("1230", usize::MAX, ResizeSide::Trailing, '0').unwrap_err(),
+            LuhnError::InvalidLength(format!(
+                "cannot allocate a number extended to {}",
+                usize::MAX
+            ))
+        );
+    }
+}
@@ -0,0 +1,23 @@
+//! JSON Schema descriptions of the validated wrapper types.
+//!
+//! A schema cannot express the Luhn checksum itself, so each type is described as
+//! a digit string of the right length with the checksum noted in its description.
+
+use schemars::schema::{InstanceType, Metadata, Schema, SchemaObject, StringValidation};
+
+/// Builds the schema of a string of ASCII digits matching `pattern`.
+pub(crate) fn digit_string(pattern: &str, description: &str) -> Schema {
+    SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        string: Some(Box::new(StringValidation {
+            pattern: Some(pattern.to_string()),
+            ..Default::default()
+        })),
+        metadata: Some(Box::new(Metadata {
+            description: Some(description.to_string()),
+            ..Default::default()
+        })),
+        ..Default::default()
+    }
+    .into()
+}
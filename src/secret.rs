@@ -0,0 +1,139 @@
+//! Card numbers that never leave [`secrecy`] wrappers.
+//!
+//! Code handling card data under a security review often has to keep it in
+//! [`SecretString`] from the edge inwards, so reading it is always an explicit
+//! `expose_secret()` call. The functions here take `&SecretString` directly, and
+//! [`SecretCardNumber`] is the validated counterpart of
+//! [`CardNumber`](crate::CardNumber) that stays secret.
+
+use std::fmt;
+
+use secrecy::{ExposeSecret, SecretString};
+
+use crate::{validate, Brand, CardNumber, LuhnError};
+
+/// Validates a Luhn number held in a [`SecretString`].
+///
+/// # Returns
+/// * `Ok(true)` - If the number is valid
+/// * `Ok(false)` - If it is not
+/// * `Err(LuhnError)` - The same errors as [`validate`](crate::validate)
+///
+/// # Examples
+/// ```
+/// use luhn_tools::validate_secret;
+/// use secrecy::SecretString;
+///
+/// assert!(validate_secret(&SecretString::from("79927398713")).unwrap());
+/// assert!(!validate_secret(&SecretString::from("79927398714")).unwrap());
+/// ```
+pub fn validate_secret(value: &SecretString) -> Result<bool, LuhnError> {
+    validate(value.expose_secret())
+}
+
+/// A validated payment card number that can only be read through
+/// [`ExposeSecret::expose_secret`].
+///
+/// It accepts the same input as [`CardNumber`](crate::CardNumber) and stores the
+/// canonical digits. The last four digits and the brand are available without
+/// exposing the rest; `Debug` shows nothing at all.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{Brand, SecretCardNumber};
+/// use secrecy::{ExposeSecret, SecretString};
+///
+/// let input = SecretString::from("4111 1111 1111 1111");
+/// let card = SecretCardNumber::new(&input).unwrap();
+/// assert_eq!(card.last4(), "1111");
+/// assert_eq!(card.brand(), Some(Brand::Visa));
+/// assert_eq!(format!("{:?}", card), "SecretCardNumber([REDACTED])");
+/// assert_eq!(card.expose_secret(), "4111111111111111");
+/// ```
+#[derive(Clone)]
+pub struct SecretCardNumber(SecretString);
+
+impl SecretCardNumber {
+    /// Normalizes and validates a card number held in a [`SecretString`].
+    ///
+    /// # Errors
+    /// Returns the same errors as [`CardNumber::new`](crate::CardNumber::new).
+    pub fn new(value: &SecretString) -> Result<Self, LuhnError> {
+        CardNumber::new(value.expose_secret()).map(SecretCardNumber::from)
+    }
+
+    /// Returns the last four digits, as printed on receipts.
+    pub fn last4(&self) -> &str {
+        let digits = self.0.expose_secret();
+        digits.get(digits.len() - 4..).unwrap_or_default()
+    }
+
+    /// Returns the card network whose prefix and length the number matches, if any.
+    pub fn brand(&self) -> Option<Brand> {
+        Brand::ALL
+            .into_iter()
+            .find(|brand| brand.matches(self.0.expose_secret()))
+    }
+}
+
+impl ExposeSecret<str> for SecretCardNumber {
+    fn expose_secret(&self) -> &str {
+        self.0.expose_secret()
+    }
+}
+
+impl fmt::Debug for SecretCardNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("SecretCardNumber([REDACTED])")
+    }
+}
+
+impl From<CardNumber> for SecretCardNumber {
+    fn from(card: CardNumber) -> Self {
+        SecretCardNumber(SecretString::from(card.into_string()))
+    }
+}
+
+impl TryFrom<&SecretString> for SecretCardNumber {
+    type Error = LuhnError;
+
+    fn try_from(value: &SecretString) -> Result<Self, Self::Error> {
+        SecretCardNumber::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_card_number() {
+        let card = SecretCardNumber::new(&SecretString::from(" 3782-822463-10005 ")).unwrap();
+        assert_eq!(card.expose_secret(), "378282246310005");
+        assert_eq!(card.last4(), "0005");
+        assert_eq!(card.brand(), Some(Brand::Amex));
+        assert_eq!(format!("{:?}", card), "SecretCardNumber([REDACTED])");
+        assert_eq!(
+            format!("{:?}", Some(card.clone())),
+            "Some(SecretCardNumber([REDACTED]))"
+        );
+
+        let from_card = SecretCardNumber::from(CardNumber::new("378282246310005").unwrap());
+        assert_eq!(from_card.expose_secret(), card.expose_secret());
+    }
+
+    #[test]
+    fn test_error_cases() {
+        assert_eq!(
+            SecretCardNumber::try_from(&SecretString::from("4111111111111112")).unwrap_err(),
+            LuhnError::InvalidChecksum {
+                expected: 1,
+                found: 2
+            }
+        );
+        assert_eq!(
+            validate_secret(&SecretString::from("")).unwrap_err(),
+            LuhnError::EmptyString
+        );
+    }
+}
@@ -0,0 +1,301 @@
+//! Consecutive valid numbers from a starting payload.
+//!
+//! Systems that assign IDs in order need each payload in turn with its check digit
+//! appended. [`sequence`] yields exactly that: the starting payload, then each
+//! following payload, with the width (including leading zeros) held fixed.
+//! [`valid_with_prefix`] walks the same way through every payload that shares a
+//! prefix, which enumerates all valid numbers of a given length.
+
+use std::iter::FusedIterator;
+
+use crate::arithmetic::increment_digits;
+use crate::{handle_errors, payload_checksum, LuhnError, Parity};
+
+/// An iterator over consecutive valid numbers, created by [`sequence`].
+#[derive(Debug, Clone)]
+pub struct Sequence {
+    payload: Vec<u8>,
+    remaining: usize,
+}
+
+/// Returns an iterator over `count` consecutive valid numbers, starting with
+/// `start` and its check digit.
+///
+/// Each number is the previous payload plus one, with a fresh check digit
+/// appended. The width of `start` is preserved, so `"0099"` is followed by
+/// `"0100"`. The whole range is checked up front; the iterator never fails
+/// part-way through.
+///
+/// # Arguments
+/// * `start` - The first payload, without a check digit
+/// * `count` - How many numbers to yield
+///
+/// # Returns
+/// * `Ok(Sequence)` - An iterator yielding `count` valid numbers
+/// * `Err(LuhnError)` - Error if `start` is malformed or the range would overflow
+///
+/// # Examples
+/// ```
+/// use luhn_tools::sequence;
+///
+/// let ids: Vec<String> = sequence("0098", 3).unwrap().collect();
+/// assert_eq!(ids, ["00984", "00992", "01008"]);
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * `start` fails the usual input checks (empty, spaces, non-numeric, ...)
+/// * The last payload of the range would need more digits than `start`
+pub fn sequence(start: &str, count: usize) -> Result<Sequence, LuhnError> {
+    handle_errors(start)?;
+
+    if !fits(start.as_bytes(), count.saturating_sub(1)) {
+        return Err(LuhnError::InvalidLength(format!(
+            "incremented payload must not exceed {} digits",
+            start.len()
+        )));
+    }
+
+    Ok(Sequence {
+        payload: start.as_bytes().to_vec(),
+        remaining: count,
+    })
+}
+
+/// Returns true if adding `step` to the decimal `payload` needs no extra digits.
+fn fits(payload: &[u8], step: usize) -> bool {
+    let mut carry = step;
+    for &digit in payload.iter().rev() {
+        if carry == 0 {
+            return true;
+        }
+        carry = carry / 10 + (carry % 10 + usize::from(digit - b'0')) / 10;
+    }
+    carry == 0
+}
+
+/// Returns an ASCII digit payload followed by its check digit.
+fn with_check_digit(payload: &[u8]) -> Option<String> {
+    let payload = std::str::from_utf8(payload).ok()?;
+    let check = payload_checksum(payload, Parity::FromRight).ok()?;
+    let mut number = String::with_capacity(payload.len() + 1);
+    number.push_str(payload);
+    number.push(char::from(b'0' + check));
+    Some(number)
+}
+
+impl Iterator for Sequence {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        self.remaining = self.remaining.checked_sub(1)?;
+
+        let number = with_check_digit(&self.payload);
+        if self.remaining > 0 {
+            increment_digits(&mut self.payload);
+        }
+        number
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for Sequence {}
+
+impl FusedIterator for Sequence {}
+
+/// Returns an iterator over every valid number of `total_len` digits that starts
+/// with `prefix`, in ascending order.
+///
+/// Each payload completing the prefix is visited once and given its check digit,
+/// so a number of `n` free digits yields `10^n` results. Nothing is generated
+/// until the iterator is advanced. An empty prefix enumerates every valid number
+/// of the length.
+///
+/// # Arguments
+/// * `prefix` - The leading digits every result shares
+/// * `total_len` - The length of each result, including its check digit
+///
+/// # Returns
+/// * `Ok(impl Iterator)` - An iterator over the valid completions
+/// * `Err(LuhnError)` - Error if the prefix is malformed or leaves no room for a check digit
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{valid_with_prefix, validate};
+///
+/// let ids: Vec<String> = valid_with_prefix("4000", 6).unwrap().collect();
+/// assert_eq!(ids.len(), 10);
+/// assert_eq!(ids[0], "400002");
+/// assert_eq!(ids[9], "400093");
+/// assert!(ids.iter().all(|id| validate(id).unwrap()));
+/// ```
+///
+/// # Errors
+/// Returns an error if:
+/// * A non-empty `prefix` fails the usual input checks (spaces, non-numeric, ...)
+/// * `total_len` is less than 2 or not longer than `prefix`
+/// * A number of `total_len` digits cannot be allocated
+pub fn valid_with_prefix(
+    prefix: &str,
+    total_len: usize,
+) -> Result<impl Iterator<Item = String>, LuhnError> {
+    if !prefix.is_empty() {
+        handle_errors(prefix)?;
+    }
+    if total_len < 2 || total_len <= prefix.len() {
+        return Err(LuhnError::InvalidLength(format!(
+            "total length must be at least {}",
+            (prefix.len() + 1).max(2)
+        )));
+    }
+
+    let mut payload = Vec::new();
+    payload.try_reserve(total_len - 1).map_err(|_| {
+        LuhnError::InvalidLength(format!("cannot allocate a number of {} digits", total_len))
+    })?;
+    payload.extend_from_slice(prefix.as_bytes());
+    payload.resize(total_len - 1, b'0');
+    Ok(Completions {
+        payload: Some(payload),
+        fixed: prefix.len(),
+    })
+}
+
+/// An iterator over the valid completions of a prefix, created by
+/// [`valid_with_prefix`].
+struct Completions {
+    /// The next payload, or `None` once the free digits have wrapped around.
+    payload: Option<Vec<u8>>,
+    fixed: usize,
+}
+
+impl Iterator for Completions {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let payload = self.payload.as_mut()?;
+        let number = with_check_digit(payload);
+        let wrapped = payload
+            .get_mut(self.fixed..)
+            .map_or(true, |free| !increment_digits(free));
+        if wrapped {
+            self.payload = None;
+        }
+        number
+    }
+}
+
+impl FusedIterator for Completions {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate, validate};
+
+    #[test]
+    fn test_consecutive() {
+        let ids: Vec<String> = sequence("7992739871", 3).unwrap().collect();
+        assert_eq!(ids, ["79927398713", "79927398721", "79927398739"]);
+
+        // Carries propagate and the width is preserved
+        let ids: Vec<String> = sequence("0199", 2).unwrap().collect();
+        assert_eq!(ids, ["01990", "02006"]);
+
+        let mut payload = 0;
+        for id in sequence("000", 1000).unwrap() {
+            assert!(validate(&id).unwrap());
+            assert_eq!(id, generate(&format!("{payload:03}"), None).unwrap());
+            payload += 1;
+        }
+        assert_eq!(payload, 1000);
+    }
+
+    #[test]
+    fn test_len() {
+        let mut ids = sequence("5", 3).unwrap();
+        assert_eq!(ids.len(), 3);
+        ids.next();
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids.by_ref().count(), 2);
+        assert_eq!(ids.next(), None);
+        assert_eq!(sequence("5", 0).unwrap().next(), None);
+    }
+
+    #[test]
+    fn test_error_cases() {
+        assert_eq!(sequence("", 1).unwrap_err(), LuhnError::EmptyString);
+        assert_eq!(
+            sequence("1a", 1).unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 1,
+                character: 'a'
+            }
+        );
+        // The last payload of "990".."999" still fits; one more does not
+        assert!(sequence("990", 10).is_ok());
+        assert_eq!(
+            sequence("990", 11).unwrap_err(),
+            LuhnError::InvalidLength("incremented payload must not exceed 3 digits".to_string())
+        );
+        assert_eq!(
+            sequence("1", usize::MAX).unwrap_err(),
+            LuhnError::InvalidLength("incremented payload must not exceed 1 digits".to_string())
+        );
+    }
+
+    #[test]
+    fn test_valid_with_prefix() {
+        // Only the check digit is free
+        let ids: Vec<String> = valid_with_prefix("7992739871", 11).unwrap().collect();
+        assert_eq!(ids, ["79927398713"]);
+
+        let ids: Vec<String> = valid_with_prefix("12", 5).unwrap().collect();
+        assert_eq!(ids.len(), 100);
+        assert_eq!(ids.first().unwrap(), "12005");
+        assert_eq!(ids.last().unwrap(), "12997");
+        assert!(ids
+            .iter()
+            .all(|id| id.starts_with("12") && validate(id).unwrap()));
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+
+        // An empty prefix covers the whole space
+        assert_eq!(valid_with_prefix("", 4).unwrap().count(), 1000);
+
+        // Huge spaces are enumerated lazily
+        let mut ids = valid_with_prefix("", 64).unwrap();
+        assert_eq!(ids.next().unwrap(), "0".repeat(64));
+    }
+
+    #[test]
+    fn test_valid_with_prefix_errors() {
+        assert_eq!(
+            valid_with_prefix("1a", 5).err(),
+            Some(LuhnError::NonNumeric {
+                index: 1,
+                character: 'a'
+            })
+        );
+        assert_eq!(
+            valid_with_prefix("123", 3).err(),
+            Some(LuhnError::InvalidLength(
+                "total length must be at least 4".to_string()
+            ))
+        );
+        assert_eq!(
+            valid_with_prefix("", 1).err(),
+            Some(LuhnError::InvalidLength(
+                "total length must be at least 2".to_string()
+            ))
+        );
+        assert_eq!(
+            valid_with_prefix("", usize::MAX).err(),
+            Some(LuhnError::InvalidLength(format!(
+                "cannot allocate a number of {} digits",
+                usize::MAX
+            )))
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! Validation for plain `String` fields during deserialization.
+//!
+//! [`LuhnNumber`](crate::LuhnNumber) and [`CardNumber`](crate::CardNumber) validate
+//! themselves when deserialized, but switching existing DTOs to them touches every
+//! use of the field. The functions here keep the field a `String` and only add a
+//! `#[serde(deserialize_with = "...")]` attribute.
+//!
+//! # Examples
+//! ```
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Account {
+//!     #[serde(deserialize_with = "luhn_tools::serde::valid_luhn")]
+//!     id: String,
+//!     #[serde(deserialize_with = "luhn_tools::serde::valid_luhn_with_separators")]
+//!     card: String,
+//! }
+//!
+//! let account: Account =
+//!     serde_json::from_str(r#"{"id": "79927398713", "card": "4111 1111 1111 1111"}"#).unwrap();
+//! assert_eq!(account.id, "79927398713");
+//! assert_eq!(account.card, "4111111111111111");
+//!
+//! assert!(serde_json::from_str::<Account>(r#"{"id": "79927398714", "card": ""}"#).is_err());
+//! ```
+
+use ::serde::de::Error;
+use ::serde::{Deserialize, Deserializer};
+
+use crate::{locate_in_input, normalize_input, strict_validate, Scratch, ValidateOptions};
+
+/// Deserializes a string that must be a valid Luhn number, exactly as given.
+///
+/// # Errors
+/// Fails deserialization with the same errors as [`strict_validate`]. The message
+/// never echoes the input.
+pub fn valid_luhn<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let value = Scratch(String::deserialize(deserializer)?.into());
+    strict_validate(&value)
+        .map_err(|e| D::Error::custom(format_args!("invalid Luhn number: {}", e)))?;
+    Ok(value.into_owned())
+}
+
+/// Deserializes a Luhn number that may contain spaces and dashes, returning only
+/// its digits.
+///
+/// # Errors
+/// Fails deserialization with the same errors as [`strict_validate`], applied after
+/// separators are stripped. The message never echoes the input.
+pub fn valid_luhn_with_separators<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<String, D::Error> {
+    let value = Scratch(String::deserialize(deserializer)?.into());
+    let options = ValidateOptions {
+        allow_separators: true,
+        ..ValidateOptions::STRICT
+    };
+    let digits = Scratch(normalize_input(&value, &options));
+    strict_validate(&digits)
+        .map_err(|e| locate_in_input(e, &value, &options))
+        .map_err(|e| D::Error::custom(format_args!("invalid Luhn number: {}", e)))?;
+    Ok(digits.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Row {
+        #[serde(deserialize_with = "valid_luhn")]
+        exact: String,
+        #[serde(deserialize_with = "valid_luhn_with_separators")]
+        formatted: String,
+    }
+
+    fn parse(exact: &str, formatted: &str) -> Result<Row, String> {
+        let json = serde_json::json!({ "exact": exact, "formatted": formatted });
+        serde_json::from_value(json).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_valid_fields() {
+        let row = parse("79927398713", "7992-7398-713").unwrap();
+        assert_eq!(row.exact, "79927398713");
+        assert_eq!(row.formatted, "79927398713");
+    }
+
+    #[test]
+    fn test_invalid_fields() {
+        assert_eq!(
+            parse("79927398714", "79927398713").unwrap_err(),
+            "invalid Luhn number: check digit is invalid (expected 3, found 4)"
+        );
+        // Only the lenient field strips separators
+        assert_eq!(
+            parse("7992 7398713", "79927398713").unwrap_err(),
+            "invalid Luhn number: string cannot contain spaces (at byte 4)"
+        );
+        assert_eq!(
+            parse("79927398713", "7992 7398 714").unwrap_err(),
+            "invalid Luhn number: check digit is invalid (expected 3, found 4)"
+        );
+    }
+}
@@ -0,0 +1,383 @@
+//! Synthetic card datasets with realistic shape.
+//!
+//! Load tests for fraud models need more than uniformly random digits: real traffic
+//! is dominated by a few brands, issued at a handful of lengths, and concentrated in
+//! some BINs. A [`Synthetic`] generator is configured with weighted buckets, either
+//! whole card brands or specific BINs with their own length mix, and draws numbers
+//! in those proportions.
+
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
+
+use crate::card::random_card_from_rng;
+use crate::{
+    check_random_len, handle_errors, random_from_rng, BatchOutcome, Brand, Limit, LuhnError,
+    RandomOptions,
+};
+
+/// A number drawn by [`Synthetic`], with the brand it belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SyntheticCard {
+    /// The Luhn-valid number
+    pub number: String,
+    /// The brand whose prefixes and length the number matches, if any
+    pub brand: Option<Brand>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Bucket {
+    Brand(Brand),
+    Bin {
+        prefix: String,
+        lengths: Vec<(usize, u32)>,
+    },
+}
+
+/// A generator of card numbers following a configured brand, BIN and length mix.
+///
+/// Each bucket has a weight; a bucket is picked in proportion to its weight, then a
+/// number is drawn inside it.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{validate, Synthetic};
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let generator = Synthetic::new()
+///     .bin("799273", &[(16, 90), (19, 10)], 70)
+///     .bin("9100", &[(15, 1)], 30);
+///
+/// let cards = generator.dataset(1000, &mut StdRng::seed_from_u64(1)).unwrap();
+/// let short = cards.iter().filter(|card| card.number.len() == 15).count();
+/// assert!((200..400).contains(&short));
+/// assert!(cards.iter().all(|card| validate(&card.number).unwrap()));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Synthetic {
+    buckets: Vec<(Bucket, u32)>,
+}
+
+impl Synthetic {
+    /// Returns a generator with no buckets.
+    pub fn new() -> Self {
+        Synthetic::default()
+    }
+
+    /// Adds a bucket of cards of `brand`, using its prefix ranges and card length.
+    pub fn brand(mut self, brand: Brand, weight: u32) -> Self {
+        self.buckets.push((Bucket::Brand(brand), weight));
+        self
+    }
+
+    /// Adds a bucket of cards starting with `prefix`, with lengths drawn from the
+    /// weighted `lengths` pairs of `(length, weight)`. An empty prefix gives
+    /// uniformly random numbers.
+    pub fn bin(mut self, prefix: &str, lengths: &[(usize, u32)], weight: u32) -> Self {
+        let bucket = Bucket::Bin {
+            prefix: prefix.to_string(),
+            lengths: lengths.to_vec(),
+        };
+        self.buckets.push((bucket, weight));
+        self
+    }
+
+    /// Draws one card.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Synthetic::dataset`].
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<SyntheticCard, LuhnError> {
+        self.sampler()?.sample(rng)
+    }
+
+    /// Draws `count` cards.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No bucket has a positive weight, or a BIN bucket has no length with a
+    ///   positive weight (`InvalidConfiguration`)
+    /// * A BIN prefix fails the usual input checks or leaves no room for the check
+    ///   digit
+    /// * With the `forbid-real-iins` feature, a bucket lies entirely in a real
+    ///   issuer range (`RealIssuerPrefix`); brand buckets always do
+    pub fn dataset<R: Rng + ?Sized>(
+        &self,
+        count: usize,
+        rng: &mut R,
+    ) -> Result<Vec<SyntheticCard>, LuhnError> {
+        self.dataset_until(count, rng, &Limit::default())
+            .map(BatchOutcome::into_inner)
+    }
+
+    /// Draws `count` cards, stopping early when `limit` is reached.
+    ///
+    /// The same as [`Synthetic::dataset`], except that an interrupted run returns
+    /// only the cards drawn before the interruption.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use luhn_tools::{Limit, Synthetic};
+    ///
+    /// let generator = Synthetic::new().bin("799273", &[(16, 1)], 1);
+    /// let limit = Limit::timeout(Duration::from_secs(5));
+    /// let outcome = generator.dataset_until(10, &mut rand::thread_rng(), &limit).unwrap();
+    /// assert!(outcome.is_complete());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Synthetic::dataset`].
+    pub fn dataset_until<R: Rng + ?Sized>(
+        &self,
+        count: usize,
+        rng: &mut R,
+        limit: &Limit,
+    ) -> Result<BatchOutcome<Vec<SyntheticCard>>, LuhnError> {
+        let sampler = self.sampler()?;
+        let mut checker = limit.checker();
+        let mut cards = Vec::new();
+        for completed in 0..count {
+            if let Some(reason) = checker.poll() {
+                return Ok(BatchOutcome::new(cards, completed, Some(reason)));
+            }
+            cards.push(sampler.sample(rng)?);
+        }
+        Ok(BatchOutcome::Complete(cards))
+    }
+
+    /// Checks the configuration and builds the weight tables for drawing.
+    pub(crate) fn sampler(&self) -> Result<Sampler<'_>, LuhnError> {
+        // Weights are summed as u64, so no mix of u32 weights can overflow
+        let buckets = WeightedIndex::new(self.buckets.iter().map(|(_, weight)| u64::from(*weight)))
+            .map_err(|_| {
+                LuhnError::InvalidConfiguration("no bucket has a positive weight".to_string())
+            })?;
+
+        let mut lengths = Vec::with_capacity(self.buckets.len());
+        for (bucket, weight) in &self.buckets {
+            let Bucket::Bin {
+                prefix,
+                lengths: mix,
+            } = bucket
+            else {
+                #[cfg(feature = "forbid-real-iins")]
+                if *weight > 0 {
+                    return Err(LuhnError::RealIssuerPrefix);
+                }
+                lengths.push(None);
+                continue;
+            };
+            if !prefix.is_empty() {
+                handle_errors(prefix)?;
+            }
+            let index = WeightedIndex::new(mix.iter().map(|(_, weight)| u64::from(*weight))).ok();
+            if *weight > 0 {
+                if index.is_none() {
+                    return Err(LuhnError::InvalidConfiguration(format!(
+                        "BIN {} has no length with a positive weight",
+                        prefix
+                    )));
+                }
+                for (len, _) in mix.iter().filter(|(_, weight)| *weight > 0) {
+                    check_random_len(*len, &RandomOptions::default())?;
+                    if prefix.len() >= *len {
+                        return Err(LuhnError::InvalidLength(
+                            "prefix must be shorter than the total length".to_string(),
+                        ));
+                    }
+                }
+            }
+            lengths.push(index);
+        }
+
+        Ok(Sampler {
+            synthetic: self,
+            buckets,
+            lengths,
+        })
+    }
+}
+
+/// A checked [`Synthetic`] configuration with its weight tables built once.
+pub(crate) struct Sampler<'a> {
+    synthetic: &'a Synthetic,
+    buckets: WeightedIndex<u64>,
+    /// The length distribution of each BIN bucket, by bucket position
+    lengths: Vec<Option<WeightedIndex<u64>>>,
+}
+
+impl Sampler<'_> {
+    /// Draws one card.
+    pub(crate) fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<SyntheticCard, LuhnError> {
+        let index = self.buckets.sample(rng);
+        let number = match (self.synthetic.buckets.get(index), self.lengths.get(index)) {
+            (Some((Bucket::Brand(brand), _)), _) => random_card_from_rng(*brand, rng)?,
+            (Some((Bucket::Bin { prefix, lengths }, _)), Some(Some(mix))) => {
+                let len = lengths.get(mix.sample(rng)).map_or(0, |(len, _)| *len);
+                random_from_rng(len, prefix, "", RandomOptions::default(), rng)?
+            }
+            _ => {
+                return Err(LuhnError::InvalidConfiguration(
+                    "no bucket has a positive weight".to_string(),
+                ))
+            }
+        };
+
+        let brand = Brand::ALL.into_iter().find(|brand| brand.matches(&number));
+        Ok(SyntheticCard { number, brand })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_bin_and_length_mix() {
+        let generator =
+            Synthetic::new()
+                .bin("799273", &[(16, 3), (19, 1)], 3)
+                .bin("91", &[(15, 1)], 1);
+        let cards = generator
+            .dataset(4000, &mut StdRng::seed_from_u64(3))
+            .unwrap();
+
+        let first = cards
+            .iter()
+            .filter(|c| c.number.starts_with("799273"))
+            .count();
+        assert!((2700..3300).contains(&first), "{}", first);
+        let nineteen = cards.iter().filter(|c| c.number.len() == 19).count();
+        assert!((600..900).contains(&nineteen), "{}", nineteen);
+        for card in &cards {
+            assert!(validate(&card.number).unwrap());
+            assert_eq!(card.brand, None);
+            assert!(card.number.starts_with("799273") || card.number.len() == 15);
+        }
+    }
+
+    #[test]
+    fn test_reproducible() {
+        let generator = Synthetic::new().bin("8", &[(12, 1), (16, 1)], 1);
+        assert_eq!(
+            generator.dataset(20, &mut StdRng::seed_from_u64(9)),
+            generator.dataset(20, &mut StdRng::seed_from_u64(9))
+        );
+    }
+
+    #[cfg(not(feature = "forbid-real-iins"))]
+    #[test]
+    fn test_brand_mix() {
+        let generator = Synthetic::new()
+            .brand(Brand::Visa, 1)
+            .brand(Brand::Amex, 1)
+            .bin("799273", &[(16, 1)], 0);
+        let cards = generator
+            .dataset(500, &mut StdRng::seed_from_u64(5))
+            .unwrap();
+        let visa = cards
+            .iter()
+            .filter(|c| c.brand == Some(Brand::Visa))
+            .count();
+        let amex = cards
+            .iter()
+            .filter(|c| c.brand == Some(Brand::Amex))
+            .count();
+        assert_eq!(visa + amex, 500);
+        assert!(visa > 150 && amex > 150);
+    }
+
+    #[cfg(feature = "forbid-real-iins")]
+    #[test]
+    fn test_brand_forbidden() {
+        let generator = Synthetic::new().brand(Brand::Visa, 1);
+        assert_eq!(
+            generator.sample(&mut rand::thread_rng()),
+            Err(LuhnError::RealIssuerPrefix)
+        );
+    }
+
+    #[test]
+    fn test_configuration_errors() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        assert_eq!(
+            Synthetic::new().sample(rng).unwrap_err(),
+            LuhnError::InvalidConfiguration("no bucket has a positive weight".to_string())
+        );
+        assert_eq!(
+            Synthetic::new()
+                .bin("8", &[(16, 1)], 0)
+                .sample(rng)
+                .unwrap_err(),
+            LuhnError::InvalidConfiguration("no bucket has a positive weight".to_string())
+        );
+        assert_eq!(
+            Synthetic::new().bin("8", &[], 1).sample(rng).unwrap_err(),
+            LuhnError::InvalidConfiguration(
+                "BIN 8 has no length with a positive weight".to_string()
+            )
+        );
+        assert_eq!(
+            Synthetic::new()
+                .bin("8x", &[(16, 1)], 1)
+                .dataset(1, rng)
+                .unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 1,
+                character: 'x'
+            }
+        );
+        assert_eq!(
+            Synthetic::new()
+                .bin("799273", &[(6, 1)], 1)
+                .sample(rng)
+                .unwrap_err(),
+            LuhnError::InvalidLength("prefix must be shorter than the total length".to_string())
+        );
+    }
+
+    #[test]
+    fn test_dataset_until() {
+        use crate::{CancelToken, Interruption};
+
+        let generator = Synthetic::new().bin("8", &[(16, 1)], 1);
+        let token = CancelToken::new();
+        token.cancel();
+        let limit = Limit {
+            cancel: Some(token),
+            ..Limit::default()
+        };
+        let outcome = generator
+            .dataset_until(5, &mut StdRng::seed_from_u64(1), &limit)
+            .unwrap();
+        assert_eq!(outcome.interruption(), Some(Interruption::Cancelled));
+        assert!(outcome.value().is_empty());
+        assert_eq!(
+            generator
+                .dataset_until(5, &mut StdRng::seed_from_u64(1), &Limit::default())
+                .unwrap()
+                .into_inner(),
+            generator.dataset(5, &mut StdRng::seed_from_u64(1)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_extreme_weights() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let cards = Synthetic::new()
+            .bin("8", &[(16, 1)], u32::MAX)
+            .bin("9", &[(16, 1)], 1)
+            .dataset(10, rng)
+            .unwrap();
+        assert!(cards.iter().all(|card| card.number.starts_with('8')));
+
+        let card = Synthetic::new()
+            .bin("8", &[(16, u32::MAX), (17, 1)], 1)
+            .sample(rng)
+            .unwrap();
+        assert!(validate(&card.number).unwrap());
+    }
+}
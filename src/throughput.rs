@@ -0,0 +1,150 @@
+//! Throughput measurement for validation workloads.
+//!
+//! Whether an optional feature or configuration pays off depends on the machine and
+//! the data. [`Throughput`] runs a validator over a caller-provided workload for a
+//! minimum wall-clock time and reports values/second and bytes/second, so the
+//! comparison can be made in the deployment environment rather than on our
+//! benchmarks.
+
+use std::time::{Duration, Instant};
+
+use crate::validate;
+
+/// The result of timing a validator over a workload.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+/// use luhn_tools::Throughput;
+///
+/// let workload = ["79927398713", "4111111111111111", "1234"];
+/// let result = Throughput::measure(&workload, Duration::from_millis(10));
+///
+/// assert_eq!(result.valid, result.iterations * 2);
+/// assert!(result.values_per_second() > 0.0);
+/// println!("{:.0} validations/s, {:.0} bytes/s", result.values_per_second(), result.bytes_per_second());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Throughput {
+    /// Number of complete passes over the workload.
+    pub iterations: usize,
+    /// Total number of values validated.
+    pub values: usize,
+    /// Total number of input bytes validated.
+    pub bytes: usize,
+    /// Total number of values the validator accepted.
+    pub valid: usize,
+    /// Wall-clock time spent validating.
+    pub elapsed: Duration,
+}
+
+impl Throughput {
+    /// Times [`validate`] over `workload`, repeating whole passes until at least
+    /// `min_duration` has elapsed. Malformed values count as rejected.
+    ///
+    /// At least one pass is always made.
+    pub fn measure<S: AsRef<str>>(workload: &[S], min_duration: Duration) -> Throughput {
+        Throughput::measure_with(workload, min_duration, |value| {
+            validate(value).unwrap_or(false)
+        })
+    }
+
+    /// Times an arbitrary validator over `workload`, such as a configured
+    /// [`Luhn`](crate::Luhn) or [`Pipeline`](crate::Pipeline), repeating whole passes
+    /// until at least `min_duration` has elapsed.
+    ///
+    /// At least one pass is always made.
+    pub fn measure_with<S, F>(workload: &[S], min_duration: Duration, validator: F) -> Throughput
+    where
+        S: AsRef<str>,
+        F: Fn(&str) -> bool,
+    {
+        let pass_bytes: usize = workload.iter().map(|v| v.as_ref().len()).sum();
+        let mut result = Throughput {
+            iterations: 0,
+            values: 0,
+            bytes: 0,
+            valid: 0,
+            elapsed: Duration::ZERO,
+        };
+
+        let start = Instant::now();
+        loop {
+            for value in workload {
+                if validator(value.as_ref()) {
+                    result.valid += 1;
+                }
+            }
+            result.iterations += 1;
+            result.values += workload.len();
+            result.bytes += pass_bytes;
+            result.elapsed = start.elapsed();
+            if result.elapsed >= min_duration {
+                return result;
+            }
+        }
+    }
+
+    /// Returns validated values per second.
+    pub fn values_per_second(&self) -> f64 {
+        per_second(self.values, self.elapsed)
+    }
+
+    /// Returns validated input bytes per second.
+    pub fn bytes_per_second(&self) -> f64 {
+        per_second(self.bytes, self.elapsed)
+    }
+}
+
+fn per_second(count: usize, elapsed: Duration) -> f64 {
+    let seconds = elapsed.as_secs_f64();
+    if seconds > 0.0 {
+        count as f64 / seconds
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Luhn;
+
+    #[test]
+    fn test_counts() {
+        let workload = vec!["79927398713".to_string(), "79927398714".to_string()];
+        let result = Throughput::measure(&workload, Duration::ZERO);
+        assert_eq!(result.iterations, 1);
+        assert_eq!(result.values, 2);
+        assert_eq!(result.bytes, 22);
+        assert_eq!(result.valid, 1);
+    }
+
+    #[test]
+    fn test_runs_for_min_duration() {
+        let min = Duration::from_millis(20);
+        let result = Throughput::measure(&["79927398713"], min);
+        assert!(result.elapsed >= min);
+        assert!(result.iterations > 1);
+        assert_eq!(result.valid, result.iterations);
+        assert!(result.values_per_second() > 0.0);
+        assert!(result.bytes_per_second() > result.values_per_second());
+    }
+
+    #[test]
+    fn test_custom_validator() {
+        let luhn = Luhn::builder().allow_separators(true).build();
+        let result = Throughput::measure_with(&["7992 7398 713"], Duration::ZERO, |v| {
+            luhn.validate(v).unwrap_or(false)
+        });
+        assert_eq!(result.valid, 1);
+    }
+
+    #[test]
+    fn test_empty_workload() {
+        let empty: [&str; 0] = [];
+        let result = Throughput::measure(&empty, Duration::ZERO);
+        assert_eq!(result.values, 0);
+        assert_eq!(per_second(0, Duration::ZERO), 0.0);
+    }
+}
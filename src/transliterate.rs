@@ -0,0 +1,214 @@
+//! Letter-to-digit transliteration for alphanumeric check digit schemes.
+//!
+//! Identifiers such as ISINs carry letters, but their check digits are computed
+//! over a purely numeric form: each letter is first replaced by a digit string and a
+//! weighted mod-10 scheme runs on the result. [`Transliteration`] is that first step,
+//! kept separate so schemes of this shape can be expressed as "transliterate, then
+//! [`WeightedMod10`]" without bespoke code.
+//!
+//! Schemes that weight each character's value rather than each resulting digit are
+//! out of reach: CUSIP doubles a letter's whole two-digit value by its position,
+//! and VIN check digits are mod 11 with position weights and an `X` check digit.
+
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use crate::{LuhnError, WeightedMod10};
+
+/// A table of character substitutions applied before a weighted check digit scheme.
+///
+/// ASCII digits always pass through unchanged. Characters with no substitution are
+/// also left in place, so the scheme reports them with the usual input errors.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::{Transliteration, WeightedMod10};
+///
+/// // ISIN: letters become A=10 ... Z=35, then Luhn
+/// let isin = Transliteration::BASE36;
+/// assert_eq!(isin.apply("US037833100"), "3028037833100");
+/// assert!(isin.validate(&WeightedMod10::LUHN, "US0378331005").unwrap());
+/// assert_eq!(isin.generate(&WeightedMod10::LUHN, "AU0000XVGZA").unwrap(), "AU0000XVGZA3");
+///
+/// // A custom table
+/// let table = Transliteration { table: &[('X', "10"), ('*', "0")], base36_letters: false };
+/// assert_eq!(table.apply("12X*"), "12100");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Transliteration<'t> {
+    /// Characters and the digit strings they expand to. Checked before
+    /// `base36_letters`, so entries here override the letter values.
+    pub table: &'t [(char, &'t str)],
+    /// If true, ASCII letters (either case) expand to their base-36 value,
+    /// `A` = 10 through `Z` = 35.
+    pub base36_letters: bool,
+}
+
+impl Transliteration<'static> {
+    /// Letters as base-36 values, as used by ISIN.
+    pub const BASE36: Transliteration<'static> = Transliteration {
+        table: &[],
+        base36_letters: true,
+    };
+}
+
+impl Transliteration<'_> {
+    /// Replaces every mapped character in `value` with its digits.
+    ///
+    /// Returns the input unchanged (borrowed) if nothing needs replacing.
+    pub fn apply<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        if value.chars().all(|c| self.lookup(c).is_none()) {
+            return Cow::Borrowed(value);
+        }
+
+        let mut result = String::with_capacity(value.len() * 2);
+        for c in value.chars() {
+            match self.lookup(c) {
+                Some(Expansion::Table(digits)) => result.push_str(digits),
+                Some(Expansion::Base36(n)) => {
+                    result.push(char::from(b'0' + n / 10));
+                    result.push(char::from(b'0' + n % 10));
+                }
+                None => result.push(c),
+            }
+        }
+        Cow::Owned(result)
+    }
+
+    /// Computes the check digit of `scheme` over the transliterated payload.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`WeightedMod10::checksum`] for the transliterated
+    /// payload; unmapped characters are reported as usual (e.g. `NonNumeric`), with
+    /// byte positions in the transliterated string.
+    pub fn checksum(&self, scheme: &WeightedMod10<'_>, payload: &str) -> Result<u8, LuhnError> {
+        scheme.checksum(&self.apply(payload))
+    }
+
+    /// Appends the check digit of `scheme` to the original (untransliterated) payload.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Transliteration::checksum`].
+    pub fn generate(&self, scheme: &WeightedMod10<'_>, payload: &str) -> Result<String, LuhnError> {
+        Ok(format!("{}{}", payload, self.checksum(scheme, payload)?))
+    }
+
+    /// Validates an identifier whose last character is a numeric check digit.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Transliteration::checksum`], plus
+    /// `InvalidLength` if the input is only one character long and `NonNumeric` if
+    /// the check digit is not an ASCII digit.
+    pub fn validate(&self, scheme: &WeightedMod10<'_>, value: &str) -> Result<bool, LuhnError> {
+        let mut chars = value.chars();
+        let check = chars.next_back().ok_or(LuhnError::EmptyString)?;
+        let payload = chars.as_str();
+        if payload.is_empty() {
+            return Err(LuhnError::InvalidLength(
+                "string must be longer than 1 character".to_string(),
+            ));
+        }
+
+        let checksum = self.checksum(scheme, payload)?;
+        match check.to_digit(10) {
+            Some(digit) => Ok(u32::from(checksum) == digit),
+            None => Err(LuhnError::NonNumeric {
+                index: payload.len(),
+                character: check,
+            }),
+        }
+    }
+
+    fn lookup(&self, c: char) -> Option<Expansion<'_>> {
+        if let Some(&(_, digits)) = self.table.iter().find(|&&(from, _)| from == c) {
+            return Some(Expansion::Table(digits));
+        }
+        if self.base36_letters && c.is_ascii_alphabetic() {
+            return Some(Expansion::Base36(c.to_ascii_uppercase() as u8 - b'A' + 10));
+        }
+        None
+    }
+}
+
+enum Expansion<'t> {
+    Table(&'t str),
+    Base36(u8),
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_isin() {
+        let isin = Transliteration::BASE36;
+        for value in ["US0378331005", "AU0000XVGZA3", "GB0002634946"] {
+            assert!(isin.validate(&WeightedMod10::LUHN, value).unwrap());
+        }
+        assert!(!isin.validate(&WeightedMod10::LUHN, "US0378331006").unwrap());
+        assert_eq!(
+            isin.generate(&WeightedMod10::LUHN, "GB000263494").unwrap(),
+            "GB0002634946"
+        );
+        // Letters are case-insensitive
+        assert!(isin.validate(&WeightedMod10::LUHN, "us0378331005").unwrap());
+    }
+
+    #[test]
+    fn test_apply_borrows_when_unchanged() {
+        assert!(matches!(
+            Transliteration::BASE36.apply("12345"),
+            Cow::Borrowed("12345")
+        ));
+        let table = Transliteration {
+            table: &[('A', "1")],
+            base36_letters: true,
+        };
+        assert_eq!(table.apply("AB"), "111");
+    }
+
+    #[test]
+    fn test_other_schemes() {
+        let table = Transliteration {
+            table: &[('X', "5")],
+            base36_letters: false,
+        };
+        assert_eq!(
+            table.checksum(&WeightedMod10::UPC, "0360002914X").unwrap(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_error_cases() {
+        let isin = Transliteration::BASE36;
+        let luhn = &WeightedMod10::LUHN;
+        assert_eq!(isin.validate(luhn, "").unwrap_err(), LuhnError::EmptyString);
+        assert_eq!(
+            isin.validate(luhn, "5").unwrap_err(),
+            LuhnError::InvalidLength("string must be longer than 1 character".to_string())
+        );
+        assert_eq!(
+            isin.validate(luhn, "US03783310X").unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 10,
+                character: 'X'
+            }
+        );
+        assert_eq!(
+            isin.validate(luhn, "US 0378331005").unwrap_err(),
+            LuhnError::ContainsSpaces {
+                index: 4,
+                character: ' '
+            }
+        );
+        assert_eq!(
+            isin.checksum(luhn, "US#037").unwrap_err(),
+            LuhnError::NonNumeric {
+                index: 4,
+                character: '#'
+            }
+        );
+    }
+}
@@ -0,0 +1,87 @@
+//! Recognition of non-ASCII decimal digits.
+//!
+//! `char::to_digit` only understands ASCII, so localized numerals need a lookup of
+//! their own. Every script in Unicode encodes its decimal digits as a contiguous run
+//! of ten code points starting at zero, so a table of those starting points is enough.
+
+/// The code points of digit zero for the decimal digit sets that are recognized.
+const DIGIT_ZEROS: &[u32] = &[
+    0x0660,  // Arabic-Indic
+    0x06F0,  // Extended Arabic-Indic (Persian, Urdu)
+    0x07C0,  // NKo
+    0x0966,  // Devanagari
+    0x09E6,  // Bengali
+    0x0A66,  // Gurmukhi
+    0x0AE6,  // Gujarati
+    0x0B66,  // Oriya
+    0x0BE6,  // Tamil
+    0x0C66,  // Telugu
+    0x0CE6,  // Kannada
+    0x0D66,  // Malayalam
+    0x0DE6,  // Sinhala Lith
+    0x0E50,  // Thai
+    0x0ED0,  // Lao
+    0x0F20,  // Tibetan
+    0x1040,  // Myanmar
+    0x1090,  // Myanmar Shan
+    0x17E0,  // Khmer
+    0x1810,  // Mongolian
+    0x1946,  // Limbu
+    0x19D0,  // New Tai Lue
+    0xA8D0,  // Saurashtra
+    0xA900,  // Kayah Li
+    0xA9D0,  // Javanese
+    0xAA50,  // Cham
+    0xABF0,  // Meetei Mayek
+    0xFF10,  // Fullwidth
+    0x1D7CE, // Mathematical bold
+    0x1D7D8, // Mathematical double-struck
+    0x1D7E2, // Mathematical sans-serif
+    0x1D7EC, // Mathematical sans-serif bold
+    0x1D7F6, // Mathematical monospace
+];
+
+/// Returns the ASCII digit for a recognized decimal digit in any script, or `None`.
+pub(crate) fn to_ascii_digit(c: char) -> Option<char> {
+    if c.is_ascii_digit() {
+        return Some(c);
+    }
+
+    let code = u32::from(c);
+    DIGIT_ZEROS
+        .iter()
+        .find(|&&zero| (zero..zero + 10).contains(&code))
+        .and_then(|&zero| char::from_digit(code - zero, 10))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_digits() {
+        for c in '0'..='9' {
+            assert_eq!(to_ascii_digit(c), Some(c));
+        }
+    }
+
+    #[test]
+    fn test_localized_digits() {
+        assert_eq!(to_ascii_digit('٠'), Some('0'));
+        assert_eq!(to_ascii_digit('٩'), Some('9'));
+        assert_eq!(to_ascii_digit('۴'), Some('4'));
+        assert_eq!(to_ascii_digit('७'), Some('7'));
+        assert_eq!(to_ascii_digit('１'), Some('1'));
+        assert_eq!(to_ascii_digit('๕'), Some('5'));
+        assert_eq!(to_ascii_digit('𝟗'), Some('9'));
+    }
+
+    #[test]
+    fn test_non_digits() {
+        assert_eq!(to_ascii_digit('a'), None);
+        assert_eq!(to_ascii_digit('½'), None);
+        assert_eq!(to_ascii_digit('²'), None);
+        assert_eq!(to_ascii_digit('Ⅳ'), None);
+        assert_eq!(to_ascii_digit('\u{065F}'), None);
+    }
+}
@@ -0,0 +1,175 @@
+//! The Verhoeff algorithm.
+//!
+//! Verhoeff computes its check digit in the dihedral group D5 instead of summing
+//! digits. It catches every single-digit error and every adjacent transposition,
+//! and is used by national ID schemes such as India's Aadhaar.
+//!
+//! # Examples
+//! ```
+//! use luhn_tools::verhoeff;
+//!
+//! assert_eq!(verhoeff::generate("236").unwrap(), "2363");
+//! assert!(verhoeff::validate("2363").unwrap());
+//! assert!(!verhoeff::validate("2633").unwrap());
+//! ```
+
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use crate::{handle_errors, CheckDigit, LuhnError};
+
+/// Multiplication table of the dihedral group D5.
+const MULTIPLICATION: [[u8; 10]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    [1, 2, 3, 4, 0, 6, 7, 8, 9, 5],
+    [2, 3, 4, 0, 1, 7, 8, 9, 5, 6],
+    [3, 4, 0, 1, 2, 8, 9, 5, 6, 7],
+    [4, 0, 1, 2, 3, 9, 5, 6, 7, 8],
+    [5, 9, 8, 7, 6, 0, 4, 3, 2, 1],
+    [6, 5, 9, 8, 7, 1, 0, 4, 3, 2],
+    [7, 6, 5, 9, 8, 2, 1, 0, 4, 3],
+    [8, 7, 6, 5, 9, 3, 2, 1, 0, 4],
+    [9, 8, 7, 6, 5, 4, 3, 2, 1, 0],
+];
+
+/// The permutation applied to a digit, by position from the right modulo 8.
+const PERMUTATION: [[u8; 10]; 8] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    [1, 5, 7, 6, 2, 8, 3, 0, 9, 4],
+    [5, 8, 0, 3, 7, 9, 6, 1, 4, 2],
+    [8, 9, 1, 6, 0, 4, 3, 5, 2, 7],
+    [9, 4, 5, 3, 1, 2, 6, 8, 7, 0],
+    [4, 2, 8, 6, 5, 7, 3, 9, 0, 1],
+    [2, 7, 9, 3, 8, 0, 6, 4, 1, 5],
+    [7, 0, 4, 6, 9, 1, 3, 2, 5, 8],
+];
+
+/// Group inverses, used to turn the payload's product into the check digit.
+const INVERSE: [u8; 10] = [0, 4, 3, 2, 1, 5, 6, 7, 8, 9];
+
+/// The Verhoeff algorithm as a [`CheckDigit`] implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Verhoeff;
+
+impl CheckDigit for Verhoeff {
+    fn compute(&self, payload: &str) -> Result<String, LuhnError> {
+        Ok(checksum(payload)?.to_string())
+    }
+
+    fn validate(&self, value: &str) -> Result<bool, LuhnError> {
+        validate(value)
+    }
+}
+
+/// Computes the Verhoeff check digit for a payload.
+///
+/// # Errors
+/// Returns an error if the payload fails the usual input checks (empty, spaces,
+/// non-numeric, ...).
+pub fn checksum(payload: &str) -> Result<u8, LuhnError> {
+    handle_errors(payload)?;
+    let product = product(payload, 1);
+    Ok(INVERSE.get(usize::from(product)).copied().unwrap_or(0))
+}
+
+/// Appends the Verhoeff check digit to a payload.
+///
+/// # Errors
+/// Returns the same errors as [`checksum`].
+pub fn generate(payload: &str) -> Result<String, LuhnError> {
+    Ok(format!("{}{}", payload, checksum(payload)?))
+}
+
+/// Validates a number whose last digit is its Verhoeff check digit.
+///
+/// # Errors
+/// Returns the same errors as [`checksum`], plus `InvalidLength` if the input is
+/// only one character long.
+pub fn validate(value: &str) -> Result<bool, LuhnError> {
+    handle_errors(value)?;
+    if value.len() < 2 {
+        return Err(LuhnError::InvalidLength(
+            "string must be longer than 1 character".to_string(),
+        ));
+    }
+    Ok(product(value, 0) == 0)
+}
+
+/// Folds ASCII digits, rightmost first, into a D5 element. `offset` is the
+/// position of the rightmost digit: 1 for a payload whose check digit is still to
+/// come, 0 for a complete number.
+fn product(digits: &str, offset: usize) -> u8 {
+    digits
+        .bytes()
+        .rev()
+        .enumerate()
+        .fold(0, |state, (i, byte)| {
+            let permuted = PERMUTATION
+                .get((i + offset) % 8)
+                .and_then(|row| row.get(usize::from(byte.wrapping_sub(b'0'))))
+                .copied()
+                .unwrap_or(0);
+            MULTIPLICATION
+                .get(usize::from(state))
+                .and_then(|row| row.get(usize::from(permuted)))
+                .copied()
+                .unwrap_or(state)
+        })
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_values() {
+        assert_eq!(checksum("236").unwrap(), 3);
+        assert_eq!(checksum("12345").unwrap(), 1);
+        assert_eq!(checksum("0").unwrap(), 4);
+        assert!(validate("2363").unwrap());
+        assert!(validate("123451").unwrap());
+        assert!(!validate("123452").unwrap());
+    }
+
+    #[test]
+    fn test_detects_errors() {
+        let number = generate("8675309421").unwrap().into_bytes();
+        for i in 0..number.len() {
+            for shift in 1..10 {
+                let mut changed = number.clone();
+                changed[i] = b'0' + (changed[i] - b'0' + shift) % 10;
+                let changed = String::from_utf8(changed).unwrap();
+                assert!(!validate(&changed).unwrap(), "{changed}");
+            }
+            if i + 1 < number.len() && number[i] != number[i + 1] {
+                let mut swapped = number.clone();
+                swapped.swap(i, i + 1);
+                let swapped = String::from_utf8(swapped).unwrap();
+                assert!(!validate(&swapped).unwrap(), "{swapped}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_check_digit_trait() {
+        assert_eq!(Verhoeff.compute("236").unwrap(), "3");
+        assert_eq!(Verhoeff.append("236").unwrap(), "2363");
+        assert!(CheckDigit::validate(&Verhoeff, "2363").unwrap());
+    }
+
+    #[test]
+    fn test_error_cases() {
+        assert_eq!(checksum("").unwrap_err(), LuhnError::EmptyString);
+        assert_eq!(
+            validate("7").unwrap_err(),
+            LuhnError::InvalidLength("string must be longer than 1 character".to_string())
+        );
+        assert_eq!(
+            validate("23 63").unwrap_err(),
+            LuhnError::ContainsSpaces {
+                index: 2,
+                character: ' '
+            }
+        );
+    }
+}
@@ -0,0 +1,267 @@
+//! A generic weighted mod-10 check digit engine.
+//!
+//! Luhn is one member of a family of schemes that weight each payload digit,
+//! sum the results and pick the check digit that brings the total to a multiple of
+//! the modulus. [`WeightedMod10`] captures that family so schemes such as UPC (3-1
+//! weighting) or internal reference formats can reuse the same plumbing; the crate's
+//! own Luhn functions run on the same core loop.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use crate::{handle_errors, CheckDigit, LuhnError};
+
+/// A weighted check digit scheme.
+///
+/// Weights are applied to the payload from its rightmost digit leftwards, cycling
+/// through `weights`. The check digit carries weight 1 and is chosen so that the
+/// weighted sum of the whole number is a multiple of `modulus`.
+///
+/// # Examples
+/// ```
+/// use luhn_tools::WeightedMod10;
+///
+/// // Luhn is the special case of weights 2-1 with product digits summed
+/// assert_eq!(WeightedMod10::LUHN.generate("7992739871").unwrap(), "79927398713");
+///
+/// // UPC-A uses 3-1 weighting
+/// assert!(WeightedMod10::UPC.validate("036000291452").unwrap());
+///
+/// // A custom scheme
+/// let scheme = WeightedMod10 { weights: &[7, 3, 1], modulus: 10, reduce_products: false };
+/// assert_eq!(scheme.checksum("12345").unwrap(), 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WeightedMod10<'w> {
+    /// Weights applied from the rightmost payload digit leftwards, cycling.
+    pub weights: &'w [u32],
+    /// The modulus of the weighted sum. Must be between 1 and 10 so the check
+    /// digit fits in a single decimal digit.
+    pub modulus: u32,
+    /// If true, a product of two or more digits contributes the sum of its digits
+    /// (as Luhn does with doubled digits); otherwise the product itself.
+    pub reduce_products: bool,
+}
+
+impl WeightedMod10<'static> {
+    /// The Luhn algorithm.
+    pub const LUHN: WeightedMod10<'static> = WeightedMod10 {
+        weights: &[2, 1],
+        modulus: 10,
+        reduce_products: true,
+    };
+
+    /// UPC-A, EAN-8 and EAN-13 (3-1 weighting from the check digit).
+    pub const UPC: WeightedMod10<'static> = WeightedMod10 {
+        weights: &[3, 1],
+        modulus: 10,
+        reduce_products: false,
+    };
+}
+
+impl WeightedMod10<'_> {
+    /// Computes the check digit for a payload.
+    ///
+    /// # Errors
+    /// Returns an error if the payload fails the usual input checks (empty, spaces,
+    /// non-numeric, ...), or `InvalidConfiguration` if the weights are empty or the
+    /// modulus is outside 1–10.
+    pub fn checksum(&self, payload: &str) -> Result<u8, LuhnError> {
+        self.check_configuration()?;
+        let sum = weighted_sum(payload, self.weights, self.modulus, self.reduce_products)?;
+        Ok(((self.modulus - sum) % self.modulus) as u8)
+    }
+
+    /// Appends the check digit to a payload.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`WeightedMod10::checksum`].
+    pub fn generate(&self, payload: &str) -> Result<String, LuhnError> {
+        Ok(format!("{}{}", payload, self.checksum(payload)?))
+    }
+
+    /// Validates a number whose last digit is its check digit.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`WeightedMod10::checksum`], plus `InvalidLength`
+    /// if the input is only one character long.
+    pub fn validate(&self, value: &str) -> Result<bool, LuhnError> {
+        handle_errors(value)?;
+        match value.as_bytes().split_last() {
+            Some((&check, payload)) if !payload.is_empty() => {
+                let payload = value.get(..payload.len()).unwrap_or_default();
+                Ok(self.checksum(payload)? == check - b'0')
+            }
+            _ => Err(LuhnError::InvalidLength(
+                "string must be longer than 1 character".to_string(),
+            )),
+        }
+    }
+
+    /// Rejects configurations that cannot produce a single-digit check digit.
+    fn check_configuration(&self) -> Result<(), LuhnError> {
+        if self.weights.is_empty() {
+            return Err(LuhnError::InvalidConfiguration(
+                "weights cannot be empty".to_string(),
+            ));
+        }
+        if !(1..=10).contains(&self.modulus) {
+            return Err(LuhnError::InvalidConfiguration(
+                "modulus must be between 1 and 10".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl CheckDigit for WeightedMod10<'_> {
+    fn compute(&self, payload: &str) -> Result<String, LuhnError> {
+        Ok(self.checksum(payload)?.to_string())
+    }
+
+    fn validate(&self, value: &str) -> Result<bool, LuhnError> {
+        WeightedMod10::validate(self, value)
+    }
+
+    fn append(&self, payload: &str) -> Result<String, LuhnError> {
+        self.generate(payload)
+    }
+}
+
+/// Computes the weighted sum of a numeric string, modulo `modulus`, in a single pass.
+///
+/// Weights apply from the rightmost digit leftwards, cycling. The sum is reduced as
+/// it is accumulated, so inputs of any length are handled without overflow.
+/// `weights` must not be empty and `modulus` must not be zero.
+///
+/// # Returns
+/// * `Ok(u32)` - The weighted sum modulo `modulus`
+/// * `Err(LuhnError)` - The same error `handle_errors` reports for the input
+pub(crate) fn weighted_sum(
+    value: &str,
+    weights: &[u32],
+    modulus: u32,
+    reduce_products: bool,
+) -> Result<u32, LuhnError> {
+    if value.is_empty() {
+        return Err(LuhnError::EmptyString);
+    }
+
+    let mut sum: u32 = 0;
+
+    for (byte, &weight) in value.bytes().rev().zip(weights.iter().cycle()) {
+        if !byte.is_ascii_digit() {
+            // Slow path: classify the error with the usual precedence.
+            return Err(handle_errors(value).err().unwrap_or(LuhnError::NonNumeric {
+                index: 0,
+                character: char::from(byte),
+            }));
+        }
+
+        let product = u64::from(byte - b'0') * u64::from(weight);
+        let mut contribution = if reduce_products {
+            digit_sum(product)
+        } else {
+            product
+        };
+        if contribution >= u64::from(modulus) {
+            contribution %= u64::from(modulus);
+        }
+
+        // Both terms are below `modulus`, so one subtraction restores the bound.
+        sum += contribution as u32;
+        if sum >= modulus {
+            sum -= modulus;
+        }
+    }
+
+    Ok(sum)
+}
+
+/// Sums the decimal digits of a number.
+fn digit_sum(mut n: u64) -> u64 {
+    let mut sum = 0;
+    while n > 0 {
+        sum += n % 10;
+        n /= 10;
+    }
+    sum
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{generate, validate};
+
+    #[test]
+    fn test_luhn_matches_crate_functions() {
+        for payload in ["1", "12", "123", "7992739871", "00123", "9999999999"] {
+            let expected = generate(payload, None).unwrap();
+            assert_eq!(WeightedMod10::LUHN.generate(payload).unwrap(), expected);
+            assert!(WeightedMod10::LUHN.validate(&expected).unwrap());
+            assert_eq!(
+                WeightedMod10::LUHN
+                    .validate(&format!("{}0", payload))
+                    .unwrap(),
+                validate(&format!("{}0", payload)).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_upc() {
+        assert_eq!(WeightedMod10::UPC.checksum("03600029145").unwrap(), 2);
+        assert!(WeightedMod10::UPC.validate("036000291452").unwrap());
+        assert!(!WeightedMod10::UPC.validate("036000291453").unwrap());
+        // EAN-13
+        assert!(WeightedMod10::UPC.validate("4006381333931").unwrap());
+    }
+
+    #[test]
+    fn test_custom_weights() {
+        let scheme = WeightedMod10 {
+            weights: &[7, 3, 1],
+            modulus: 10,
+            reduce_products: false,
+        };
+        // 5*7 + 4*3 + 3*1 + 2*7 + 1*3 = 67
+        assert_eq!(scheme.checksum("12345").unwrap(), 3);
+        assert!(scheme.validate("123453").unwrap());
+    }
+
+    #[test]
+    fn test_error_cases() {
+        assert_eq!(
+            WeightedMod10::UPC.checksum("").unwrap_err(),
+            LuhnError::EmptyString
+        );
+        assert_eq!(
+            WeightedMod10::UPC.validate("1").unwrap_err(),
+            LuhnError::InvalidLength("string must be longer than 1 character".to_string())
+        );
+        assert_eq!(
+            WeightedMod10::UPC.validate("1 2").unwrap_err(),
+            LuhnError::ContainsSpaces {
+                index: 1,
+                character: ' '
+            }
+        );
+
+        let no_weights = WeightedMod10 {
+            weights: &[],
+            ..WeightedMod10::LUHN
+        };
+        assert_eq!(
+            no_weights.checksum("12").unwrap_err(),
+            LuhnError::InvalidConfiguration("weights cannot be empty".to_string())
+        );
+        let zero_modulus = WeightedMod10 {
+            modulus: 0,
+            ..WeightedMod10::LUHN
+        };
+        assert_eq!(
+            zero_modulus.checksum("12").unwrap_err(),
+            LuhnError::InvalidConfiguration("modulus must be between 1 and 10".to_string())
+        );
+    }
+}